@@ -0,0 +1,318 @@
+// SPDX-License-Identifier: GPL-2.0-only
+
+//! BBR (Bottleneck Bandwidth and RTT) congestion control algorithm.
+//!
+//! Based on:
+//!     Neal Cardwell, Yuchung Cheng, C. Stephen Gunn, Soheil Hassas Yeganeh,
+//!     Van Jacobson,
+//!     BBR: Congestion-Based Congestion Control,
+//!     Communications of the ACM, Vol. 60 No. 2, 2017, Pages 58-66.
+//!     <https://doi.org/10.1145/3009824>
+//!
+//! Unlike CUBIC and BIC, BBR is rate-based: it drives `snd_cwnd` and the
+//! pacing rate off of a model of the path (`BtlBw`, the bottleneck
+//! bandwidth, and `RTprop`, the round-trip propagation time) built from
+//! [`cong::RateSample`]s, rather than off of cwnd-based ACK counting or
+//! loss. `ssthresh`/`undo_cwnd` are therefore mostly inert here.
+
+use core::cmp::max;
+use kernel::c_str;
+use kernel::net::sock::Pacing;
+use kernel::net::tcp;
+use kernel::net::tcp::cong::{self, module_cca};
+use kernel::prelude::*;
+use kernel::time;
+
+/// Duration of the bandwidth max-filter window, in round-trips.
+const BTLBW_FILTER_WIN: usize = 10;
+/// Duration of the `RTprop` min-filter window.
+const RTPROP_FILTER_EXPIRY: time::Msecs32 = 10 * time::MSEC_PER_SEC;
+/// How long PROBE_RTT caps cwnd to re-measure `RTprop`.
+const PROBE_RTT_DURATION: time::Msecs32 = 200;
+/// cwnd floor, in packets, while in PROBE_RTT.
+const PROBE_RTT_CWND: u32 = 4;
+/// `cwnd_gain` used in every phase except PROBE_RTT (~2, in Q8 fixed point).
+const CWND_GAIN_Q8: u64 = 2 * 256;
+/// `pacing_gain` used in STARTUP (~2/ln(2), in Q8 fixed point), chosen so
+/// that both the estimated bandwidth and cwnd double each round.
+const STARTUP_GAIN_Q8: u64 = 739;
+/// `pacing_gain` used in DRAIN: the inverse of [`STARTUP_GAIN_Q8`], so that
+/// the queue built up in STARTUP is flushed in one round.
+const DRAIN_GAIN_Q8: u64 = 256 * 256 / STARTUP_GAIN_Q8;
+/// The 8-phase `pacing_gain` cycle used in PROBE_BW, in Q8 fixed point:
+/// probe up by 5/4, drain back down by 3/4, then cruise at 1 six times.
+const PROBE_BW_GAIN_CYCLE_Q8: [u64; 8] = [320, 192, 256, 256, 256, 256, 256, 256];
+/// Number of consecutive rounds with < 25% bandwidth growth needed to
+/// conclude STARTUP has found the bottleneck.
+const STARTUP_FULL_BW_ROUNDS: u32 = 3;
+
+module_cca! {
+    type: Bbr,
+    name: "tcp_bbr_rust",
+    author: "Rust for Linux Contributors",
+    description: "BBR congestion control algorithm, Rust implementation",
+    license: "GPL v2",
+}
+
+struct Bbr {}
+
+#[vtable]
+impl cong::Algorithm for Bbr {
+    type Data = BbrState;
+
+    const NAME: &'static CStr = c_str!("bbr_rust");
+
+    // Matches the C implementation (tcp_bbr.c): BBR drives cwnd and pacing
+    // itself through `cong_control`, so there's no reason to restrict it to
+    // privileged sockets the way a cwnd-shrinking loss-based CCA might be.
+    const FLAGS: cong::Flags = cong::Flags::NON_RESTRICTED;
+
+    fn init(sk: &mut cong::Sock<'_, Self>) {
+        let snd_nxt = sk.tcp_sk().snd_nxt();
+        sk.inet_csk_ca_mut().round_end = snd_nxt;
+        sk.request_pacing_status(Pacing::Needed);
+    }
+
+    fn ssthresh(sk: &mut cong::Sock<'_, Self>) -> u32 {
+        // BBR does not react to ssthresh requests by shrinking cwnd; it
+        // keeps whatever `cong_control` last computed.
+        sk.tcp_sk().snd_cwnd()
+    }
+
+    fn undo_cwnd(sk: &mut cong::Sock<'_, Self>) -> u32 {
+        sk.tcp_sk().snd_cwnd()
+    }
+
+    fn cong_control(sk: &mut cong::Sock<'_, Self>, _ack: u32, sample: &cong::RateSample) {
+        let Some(delivery_rate) = delivery_rate(sample) else {
+            return;
+        };
+
+        let is_new_round = tcp::after(sk.tcp_sk().snd_una(), sk.inet_csk_ca().round_end);
+        if is_new_round {
+            let snd_nxt = sk.tcp_sk().snd_nxt();
+            sk.inet_csk_ca_mut().round_end = snd_nxt;
+        }
+
+        let ca = sk.inet_csk_ca_mut();
+        ca.update_btlbw(delivery_rate, is_new_round);
+        if let Some(rtt_us) = sample.rtt_us() {
+            ca.update_rtprop(rtt_us);
+        }
+
+        if is_new_round {
+            ca.advance_phase();
+        }
+
+        let Some(btlbw) = ca.btlbw else { return };
+        let Some(rtprop) = ca.rtprop else { return };
+
+        let pacing_gain_q8 = ca.pacing_gain_q8();
+        let pacing_rate = (btlbw * pacing_gain_q8) / 256;
+        ca.pacing_rate = pacing_rate;
+        let phase = ca.phase;
+
+        sk.set_sk_pacing_rate(pacing_rate);
+
+        if phase == Phase::ProbeRtt {
+            return set_probe_rtt_cwnd(sk);
+        }
+
+        let bdp = ((btlbw as u128 * rtprop as u128) / time::USEC_PER_SEC as u128) as u64;
+        let target_cwnd = max(4, (bdp * CWND_GAIN_Q8) / 256);
+
+        sk.tcp_sk_mut()
+            .set_snd_cwnd(u32::try_from(target_cwnd).unwrap_or(u32::MAX));
+    }
+
+    fn get_info(
+        sk: &cong::Sock<'_, Self>,
+        attr: u32,
+        writer: &mut cong::InfoWriter<'_>,
+    ) -> Option<u32> {
+        if attr != kernel::bindings::INET_DIAG_VEGASINFO {
+            return None;
+        }
+
+        let ca = sk.inet_csk_ca();
+        let bw = ca.pacing_rate;
+        let info = kernel::bindings::tcp_bbr_info {
+            bbr_bw_lo: bw as u32,
+            bbr_bw_hi: (bw >> 32) as u32,
+            bbr_min_rtt: ca.rtprop.unwrap_or(0),
+            bbr_pacing_gain: ca.pacing_gain_q8() as u32,
+            bbr_cwnd_gain: CWND_GAIN_Q8 as u32,
+        };
+
+        writer.write(&info)?;
+        Some(kernel::bindings::INET_DIAG_VEGASINFO)
+    }
+}
+
+fn set_probe_rtt_cwnd(sk: &mut cong::Sock<'_, Bbr>) {
+    sk.tcp_sk_mut().set_snd_cwnd(PROBE_RTT_CWND);
+}
+
+/// Computes the delivery rate (packets/sec) carried by `sample`, or `None` if
+/// the sample has no usable interval.
+fn delivery_rate(sample: &cong::RateSample) -> Option<u64> {
+    let interval_us = sample.interval_us();
+    if interval_us <= 0 || sample.delivered() <= 0 {
+        return None;
+    }
+
+    Some((sample.delivered() as u64 * time::USEC_PER_SEC) / interval_us as u64)
+}
+
+/// Phase of the BBR state machine.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Doubling the sending rate each round, looking for `BtlBw`.
+    Startup,
+    /// Flushing the queue built up during [`Phase::Startup`].
+    Drain,
+    /// Steady state: cycling `pacing_gain` to probe for more bandwidth
+    /// while otherwise cruising at the estimated `BtlBw`.
+    ProbeBw,
+    /// Periodically capping cwnd to re-measure `RTprop` on an
+    /// uncongested path.
+    ProbeRtt,
+}
+
+/// Internal state of each instance of the algorithm.
+struct BbrState {
+    /// Phase of the state machine.
+    phase: Phase,
+    /// Max-filtered estimate of the bottleneck bandwidth, in packets/sec.
+    btlbw: Option<u64>,
+    /// Rolling window of per-round delivery-rate samples backing
+    /// [`Self::btlbw`]. `btlbw` is the maximum of this window.
+    btlbw_samples: [u64; BTLBW_FILTER_WIN],
+    /// Index of the entry in `btlbw_samples` for the current round.
+    btlbw_idx: usize,
+    /// Min-filtered estimate of the round-trip propagation time.
+    rtprop: Option<time::Usecs32>,
+    /// Time `rtprop` was last updated, used to expire stale samples.
+    rtprop_stamp: time::Msecs32,
+    /// Sequence number marking the end of the current round.
+    round_end: u32,
+    /// Number of consecutive rounds since `btlbw` last grew by at least
+    /// 25%, used to decide when to leave STARTUP.
+    full_bw_rounds: u32,
+    /// `btlbw` the last time `full_bw_rounds` was reset.
+    full_bw: u64,
+    /// Current position in [`PROBE_BW_GAIN_CYCLE_Q8`].
+    cycle_idx: usize,
+    /// Time PROBE_RTT's cwnd cap has been held since, if currently probing.
+    probe_rtt_round_done_stamp: Option<time::Msecs32>,
+    /// Target pacing rate in bytes/sec, last written to the socket by
+    /// `cong_control`.
+    pacing_rate: u64,
+}
+
+impl Default for BbrState {
+    fn default() -> Self {
+        Self {
+            phase: Phase::Startup,
+            btlbw: None,
+            btlbw_samples: [0; BTLBW_FILTER_WIN],
+            btlbw_idx: 0,
+            rtprop: None,
+            rtprop_stamp: 0,
+            round_end: 0,
+            full_bw_rounds: 0,
+            full_bw: 0,
+            cycle_idx: 0,
+            probe_rtt_round_done_stamp: None,
+            pacing_rate: 0,
+        }
+    }
+}
+
+impl BbrState {
+    /// Feeds a new per-ACK delivery-rate sample into the `BtlBw` max-filter,
+    /// advancing the filter window on round boundaries.
+    fn update_btlbw(&mut self, rate: u64, is_new_round: bool) {
+        let slot = &mut self.btlbw_samples[self.btlbw_idx];
+        *slot = max(*slot, rate);
+
+        if is_new_round {
+            self.btlbw_idx = (self.btlbw_idx + 1) % BTLBW_FILTER_WIN;
+            self.btlbw_samples[self.btlbw_idx] = 0;
+        }
+
+        let windowed_max = self.btlbw_samples.iter().copied().max().unwrap_or(0);
+        self.btlbw = Some(max(windowed_max, rate));
+    }
+
+    /// Feeds a new RTT sample into the `RTprop` min-filter, expiring it
+    /// after [`RTPROP_FILTER_EXPIRY`] without a lower sample.
+    fn update_rtprop(&mut self, rtt_us: time::Usecs32) {
+        let now = time::ktime_get_boot_fast_ms32();
+
+        let expired = now.wrapping_sub(self.rtprop_stamp) > RTPROP_FILTER_EXPIRY;
+        let is_lower = match self.rtprop {
+            Some(r) => rtt_us <= r,
+            None => true,
+        };
+        if is_lower || expired {
+            self.rtprop = Some(rtt_us);
+            self.rtprop_stamp = now;
+        }
+    }
+
+    /// Advances the state machine by one round, transitioning between
+    /// STARTUP/DRAIN/PROBE_BW/PROBE_RTT as appropriate.
+    fn advance_phase(&mut self) {
+        let now = time::ktime_get_boot_fast_ms32();
+        let btlbw = self.btlbw.unwrap_or(0);
+
+        match self.phase {
+            Phase::Startup => {
+                if btlbw >= (self.full_bw * 5) / 4 {
+                    self.full_bw = btlbw;
+                    self.full_bw_rounds = 0;
+                } else {
+                    self.full_bw_rounds += 1;
+                }
+
+                if self.full_bw_rounds >= STARTUP_FULL_BW_ROUNDS {
+                    self.phase = Phase::Drain;
+                }
+            }
+            Phase::Drain => {
+                // Drain for exactly one round, then cruise.
+                self.phase = Phase::ProbeBw;
+            }
+            Phase::ProbeBw => {
+                self.cycle_idx = (self.cycle_idx + 1) % PROBE_BW_GAIN_CYCLE_Q8.len();
+
+                let rtprop_stale = self.rtprop.is_none()
+                    || now.wrapping_sub(self.rtprop_stamp) >= RTPROP_FILTER_EXPIRY;
+                if rtprop_stale && self.probe_rtt_round_done_stamp.is_none() {
+                    self.phase = Phase::ProbeRtt;
+                    self.probe_rtt_round_done_stamp = Some(now);
+                }
+            }
+            Phase::ProbeRtt => {
+                if let Some(since) = self.probe_rtt_round_done_stamp {
+                    if now.wrapping_sub(since) >= PROBE_RTT_DURATION {
+                        self.phase = Phase::ProbeBw;
+                        self.probe_rtt_round_done_stamp = None;
+                        self.rtprop_stamp = now;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the `pacing_gain` for the current phase, in Q8 fixed point.
+    fn pacing_gain_q8(&self) -> u64 {
+        match self.phase {
+            Phase::Startup => STARTUP_GAIN_Q8,
+            Phase::Drain => DRAIN_GAIN_Q8,
+            Phase::ProbeBw => PROBE_BW_GAIN_CYCLE_Q8[self.cycle_idx],
+            Phase::ProbeRtt => 256,
+        }
+    }
+}
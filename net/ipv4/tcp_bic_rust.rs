@@ -11,10 +11,31 @@
 use core::cmp::{max, min};
 use core::num::NonZeroU32;
 use kernel::c_str;
-use kernel::net::tcp::cong::{self, module_cca};
+use kernel::declare_params;
+use kernel::net::tcp::cong::{self, hystart, hystart::HystartDetect, module_cca, param::Param};
 use kernel::prelude::*;
 use kernel::time;
 
+/// Tracepoints for `tcp_bic_rust`, replacing the ad-hoc `pr_info!` calls that
+/// used to live next to the code below. The `TRACE_EVENT` definitions live in
+/// `include/trace/events/tcp.h`, alongside the ones the C implementation uses.
+mod trace {
+    use kernel::declare_trace;
+
+    declare_trace! {
+        /// A BIC socket was initialized.
+        pub(crate) fn tcp_bic_init(cwnd: u32);
+        /// A BIC socket was destroyed.
+        pub(crate) fn tcp_bic_release(cwnd: u32);
+        /// The sender entered the `Loss` state; BIC state was reset.
+        pub(crate) fn tcp_bic_loss(cwnd: u32);
+        /// `ssthresh` was recalculated, e.g. on entering CWR/Recovery/Loss.
+        pub(crate) fn tcp_bic_ssthresh(cwnd: u32, last_max_cwnd: u32, ssthresh: u32);
+        /// `cong_avoid` recalculated cwnd for this ACK.
+        pub(crate) fn tcp_bic_cong_avoid(cwnd: u32, ssthresh: u32, slow_start: bool);
+    }
+}
+
 const ACK_RATIO_SHIFT: u32 = 4;
 
 // TODO: Convert to module parameters once they are available.
@@ -54,6 +75,30 @@ const BETA: u32 = 819;
 const BETA_SCALE: NonZeroU32 = unsafe { NonZeroU32::new_unchecked(1024) };
 /// The minimum amount of time that has to pass between two updates of the cwnd.
 const MIN_UPDATE_INTERVAL: time::Msecs32 = time::MSEC_PER_SEC / 32;
+/// Whether to use the [HyStart] slow start algorithm.
+///
+/// [HyStart]: hystart::HyStart
+const HYSTART: bool = true;
+
+declare_params! {
+    /// Which heuristic the [HyStart] slow start algorithm should use to find
+    /// the exit point for slow start. Mirrors C's `hystart_detect`.
+    ///
+    /// [HyStart]: hystart::HyStart
+    pub static HYSTART_DETECT: Param = Param::new(HystartDetect::Both as u32), perm: 0o644;
+    /// Lower bound for cwnd during hybrid slow start. Mirrors C's
+    /// `hystart_low_window`.
+    pub static HYSTART_LOW_WINDOW: Param = Param::new(16), perm: 0o644;
+    /// Spacing between ACKs indicating an ACK-train. (Dimension: Time. Unit:
+    /// us). Mirrors C's `hystart_ack_delta_us`.
+    pub static HYSTART_ACK_DELTA: Param = Param::new(2000), perm: 0o644;
+}
+
+impl hystart::HyStart for Bic {
+    const DETECT: &'static Param = &HYSTART_DETECT;
+    const LOW_WINDOW: &'static Param = &HYSTART_LOW_WINDOW;
+    const ACK_DELTA: &'static Param = &HYSTART_ACK_DELTA;
+}
 
 module_cca! {
     type: Bic,
@@ -83,19 +128,34 @@ impl cong::Algorithm for Bic {
                     .wrapping_sub(ca.delayed_ack >> ACK_RATIO_SHIFT),
             );
         }
+
+        let Some(rtt_us) = sample.rtt_us() else {
+            return;
+        };
+
+        let delay = max(1, rtt_us);
+        let cwnd = sk.tcp_sk().snd_cwnd();
+        let in_slow_start = sk.tcp_sk().in_slow_start();
+        let ca = sk.inet_csk_ca_mut();
+
+        if ca.hystart_state.delay_min.is_none()
+            || ca
+                .hystart_state
+                .delay_min
+                .is_some_and(|delay_min| delay_min > delay)
+        {
+            ca.hystart_state.delay_min = Some(delay);
+        }
+
+        if in_slow_start && HYSTART && ca.hystart_state.in_hystart::<Self>(cwnd) {
+            hystart::HyStart::update(sk, delay);
+        }
     }
 
     fn ssthresh(sk: &mut cong::Sock<'_, Self>) -> u32 {
         let cwnd = sk.tcp_sk().snd_cwnd();
         let ca = sk.inet_csk_ca_mut();
 
-        pr_info!(
-            // TODO: remove
-            "Enter fast retransmit: time {}, start {}",
-            time::ktime_get_boot_fast_ns(),
-            ca.start_time
-        );
-
         // Epoch has ended.
         ca.epoch_start = 0;
         ca.last_max_cwnd = if cwnd < ca.last_max_cwnd && FAST_CONVERGENCE {
@@ -104,12 +164,16 @@ impl cong::Algorithm for Bic {
             cwnd
         };
 
-        if cwnd <= LOW_WINDOW {
+        let ssthresh = if cwnd <= LOW_WINDOW {
             // Act like normal TCP.
             max(cwnd >> 1, 2)
         } else {
             max((cwnd * BETA) / BETA_SCALE, 2)
-        }
+        };
+
+        // SAFETY: `tcp_bic_ssthresh` is a valid tracepoint.
+        unsafe { trace::tcp_bic_ssthresh(cwnd, ca.last_max_cwnd, ssthresh) };
+        ssthresh
     }
 
     fn cong_avoid(sk: &mut cong::Sock<'_, Self>, _ack: u32, mut acked: u32) {
@@ -122,14 +186,14 @@ impl cong::Algorithm for Bic {
         if tp.in_slow_start() {
             acked = tp.slow_start(acked);
             if acked == 0 {
-                pr_info!(
-                    // TODO: remove
-                    "New cwnd {}, time {}, ssthresh {}, start {}, ss 1",
-                    sk.tcp_sk().snd_cwnd(),
-                    time::ktime_get_boot_fast_ns(),
-                    sk.tcp_sk().snd_ssthresh(),
-                    sk.inet_csk_ca().start_time
-                );
+                // SAFETY: `tcp_bic_cong_avoid` is a valid tracepoint.
+                unsafe {
+                    trace::tcp_bic_cong_avoid(
+                        sk.tcp_sk().snd_cwnd(),
+                        sk.tcp_sk().snd_ssthresh(),
+                        true,
+                    )
+                };
                 return;
             }
         }
@@ -138,55 +202,39 @@ impl cong::Algorithm for Bic {
         let cnt = sk.inet_csk_ca_mut().update(cwnd);
         sk.tcp_sk_mut().cong_avoid_ai(cnt, acked);
 
-        pr_info!(
-            // TODO: remove
-            "New cwnd {}, time {}, ssthresh {}, start {}, ss 0",
-            sk.tcp_sk().snd_cwnd(),
-            time::ktime_get_boot_fast_ns(),
-            sk.tcp_sk().snd_ssthresh(),
-            sk.inet_csk_ca().start_time
-        );
+        // SAFETY: `tcp_bic_cong_avoid` is a valid tracepoint.
+        unsafe {
+            trace::tcp_bic_cong_avoid(sk.tcp_sk().snd_cwnd(), sk.tcp_sk().snd_ssthresh(), false)
+        };
     }
 
     fn set_state(sk: &mut cong::Sock<'_, Self>, new_state: cong::State) {
         if matches!(new_state, cong::State::Loss) {
-            pr_info!(
-                // TODO: remove
-                "Retransmission timeout fired: time {}, start {}",
-                time::ktime_get_boot_fast_ns(),
-                sk.inet_csk_ca().start_time
-            );
-            sk.inet_csk_ca_mut().reset()
+            // SAFETY: `tcp_bic_loss` is a valid tracepoint.
+            unsafe { trace::tcp_bic_loss(sk.tcp_sk().snd_cwnd()) };
+            sk.inet_csk_ca_mut().reset();
+            <Self as hystart::HyStart>::reset(sk);
         }
     }
 
     fn undo_cwnd(sk: &mut cong::Sock<'_, Self>) -> u32 {
-        pr_info!(
-            // TODO: remove
-            "Undo cwnd reduction: time {}, start {}",
-            time::ktime_get_boot_fast_ns(),
-            sk.inet_csk_ca().start_time
-        );
-
         cong::reno::undo_cwnd(sk)
     }
 
     fn init(sk: &mut cong::Sock<'_, Self>) {
-        if let Some(ssthresh) = INITIAL_SSTHRESH {
+        if HYSTART {
+            <Self as hystart::HyStart>::reset(sk);
+        } else if let Some(ssthresh) = INITIAL_SSTHRESH {
             sk.tcp_sk_mut().set_snd_ssthresh(ssthresh);
         }
 
-        // TODO: remove
-        pr_info!("Socket created: start {}", sk.inet_csk_ca().start_time);
+        // SAFETY: `tcp_bic_init` is a valid tracepoint.
+        unsafe { trace::tcp_bic_init(sk.tcp_sk().snd_cwnd()) };
     }
 
-    // TODO: remove
     fn release(sk: &mut cong::Sock<'_, Self>) {
-        pr_info!(
-            "Socket destroyed: start {}, end {}",
-            sk.inet_csk_ca().start_time,
-            time::ktime_get_boot_fast_ns()
-        );
+        // SAFETY: `tcp_bic_release` is a valid tracepoint.
+        unsafe { trace::tcp_bic_release(sk.tcp_sk().snd_cwnd()) };
     }
 }
 
@@ -211,9 +259,18 @@ struct BicState {
     /// per packet when a receiver is sending a single ACK for multiple received
     /// packets.
     delayed_ack: u32,
-    /// Time when algorithm was initialised.
-    // TODO: remove
-    start_time: time::Nsecs,
+    /// State of the HyStart slow start algorithm.
+    hystart_state: hystart::HyStartState,
+}
+
+impl hystart::HasHyStartState for BicState {
+    fn hy(&self) -> &hystart::HyStartState {
+        &self.hystart_state
+    }
+
+    fn hy_mut(&mut self) -> &mut hystart::HyStartState {
+        &mut self.hystart_state
+    }
 }
 
 impl Default for BicState {
@@ -227,8 +284,7 @@ impl Default for BicState {
             last_time: 0,
             epoch_start: 0,
             delayed_ack: 2 << ACK_RATIO_SHIFT,
-            // TODO: remove
-            start_time: time::ktime_get_boot_fast_ns(),
+            hystart_state: hystart::HyStartState::default(),
         }
     }
 }
@@ -301,12 +357,6 @@ impl BicState {
     }
 
     fn reset(&mut self) {
-        // TODO: remove
-        let tmp = self.start_time;
-
         *self = Self::default();
-
-        // TODO: remove
-        self.start_time = tmp;
     }
 }
@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: GPL-2.0-only
+
+//! CAIA Delay-Gradient (CDG) congestion control algorithm.
+//!
+//! Based on:
+//!     David A. Hayes and Grenville Armitage,
+//!     Revisiting TCP Congestion Control Using Delay Gradients,
+//!     IFIP Networking 2011.
+//!     <https://doi.org/10.1007/978-3-642-20757-0_30>
+//!
+//! Unlike CUBIC and BIC, CDG does not wait for a loss event to back off: it
+//! watches the RTT gradient each round and, when delay looks like it is
+//! building up, probabilistically performs a multiplicative backoff. A
+//! "shadow window" records cwnd from just before a backoff so that it can be
+//! restored if the backoff turns out not to have drained the queue.
+
+use core::cmp::{max, min};
+use core::num::NonZeroU32;
+use kernel::bindings;
+use kernel::c_str;
+use kernel::net::tcp;
+use kernel::net::tcp::cong::{self, module_cca};
+use kernel::prelude::*;
+use kernel::time;
+
+/// Number of past per-round gradients averaged together to smooth `g_min`
+/// and `g_max`.
+const HISTORY_LEN: usize = 8;
+/// Scaling constant `G` used to normalize the gradient before it is squared.
+const GRADIENT_SCALE: i32 = 3;
+/// Multiplicative backoff factor, `BETA_NUM / BETA_DENOM` ~= 0.7.
+const BETA_NUM: u32 = 717;
+const BETA_DENOM: u32 = 1024;
+
+/// Fixed-point scale used for the backoff probability and for draws against
+/// it; `PSCALE` represents a probability of `1.0`.
+const PSCALE: u32 = 1 << 16;
+
+/// `exp(-k/4) * PSCALE` for `k` in `0..EXP_NEG_TABLE.len()`, i.e. `exp(-x)`
+/// at quarter-unit steps of `x` covering `0..=8`. `exp(-x)` is negligible
+/// past that, so an out-of-range index is treated as `0` (`P` saturates to
+/// `1`, i.e. certain backoff).
+const EXP_NEG_TABLE: [u32; 33] = [
+    65536, 51039, 39750, 30957, 24109, 18776, 14623, 11388, 8869, 6907, 5380, 4190, 3263, 2541,
+    1979, 1541, 1200, 935, 728, 567, 442, 344, 268, 209, 162, 127, 99, 77, 60, 47, 36, 28, 22,
+];
+
+/// Returns the probability (scaled by [`PSCALE`]) of backing off given a
+/// smoothed RTT gradient `g`, computed as `1 - exp(-(g/G)^2)`.
+fn backoff_probability(g: i32) -> u32 {
+    let g = g.unsigned_abs() as u64;
+    let g_scale = GRADIENT_SCALE as u64;
+    let index = (4 * g * g) / (g_scale * g_scale);
+    let exp_val = EXP_NEG_TABLE.get(index as usize).copied().unwrap_or(0);
+    PSCALE - exp_val
+}
+
+/// Draws against a backoff probability `p` (scaled by [`PSCALE`]).
+fn draw_backoff(p: u32) -> bool {
+    // SAFETY: `get_random_u32` may be called from any context.
+    let r = unsafe { bindings::get_random_u32() } % PSCALE;
+    r < p
+}
+
+/// A moving-average window of per-round gradients.
+#[derive(Default)]
+struct GradientWindow {
+    samples: [i32; HISTORY_LEN],
+    idx: usize,
+    filled: usize,
+}
+
+impl GradientWindow {
+    /// Pushes a new gradient sample and returns the updated average.
+    fn push(&mut self, sample: i32) -> i32 {
+        self.samples[self.idx] = sample;
+        self.idx = (self.idx + 1) % HISTORY_LEN;
+        self.filled = min(self.filled + 1, HISTORY_LEN);
+
+        let sum: i32 = self.samples[..self.filled].iter().sum();
+        sum / self.filled as i32
+    }
+}
+
+module_cca! {
+    type: Cdg,
+    name: "tcp_cdg_rust",
+    author: "Rust for Linux Contributors",
+    description: "CAIA Delay-Gradient (CDG) congestion control algorithm, Rust implementation",
+    license: "GPL v2",
+}
+
+struct Cdg {}
+
+#[vtable]
+impl cong::Algorithm for Cdg {
+    type Data = CdgState;
+
+    const NAME: &'static CStr = c_str!("cdg_rust");
+
+    fn init(sk: &mut cong::Sock<'_, Self>) {
+        let snd_nxt = sk.tcp_sk().snd_nxt();
+        sk.inet_csk_ca_mut().round_end = snd_nxt;
+    }
+
+    fn pkts_acked(sk: &mut cong::Sock<'_, Self>, sample: &cong::AckSample) {
+        let Some(rtt_us) = sample.rtt_us() else {
+            return;
+        };
+
+        let ca = sk.inet_csk_ca_mut();
+        ca.rtt_min = Some(match ca.rtt_min {
+            Some(m) => min(m, rtt_us),
+            None => rtt_us,
+        });
+        ca.rtt_max = Some(match ca.rtt_max {
+            Some(m) => max(m, rtt_us),
+            None => rtt_us,
+        });
+    }
+
+    fn cong_avoid(sk: &mut cong::Sock<'_, Self>, _ack: u32, mut acked: u32) {
+        if tcp::after(sk.tcp_sk().snd_una(), sk.inet_csk_ca().round_end) {
+            let snd_nxt = sk.tcp_sk().snd_nxt();
+            sk.inet_csk_ca_mut().end_round(snd_nxt);
+            maybe_backoff(sk);
+        }
+
+        if !sk.tcp_is_cwnd_limited() {
+            return;
+        }
+
+        let tp = sk.tcp_sk_mut();
+        if tp.in_slow_start() {
+            acked = tp.slow_start(acked);
+            if acked == 0 {
+                return;
+            }
+        }
+
+        let cwnd = tp.snd_cwnd();
+        let cnt = NonZeroU32::new(cwnd).unwrap_or(NonZeroU32::MIN);
+        tp.cong_avoid_ai(cnt, acked);
+    }
+
+    fn ssthresh(sk: &mut cong::Sock<'_, Self>) -> u32 {
+        // Fall back to Reno-like behavior on real loss.
+        max(sk.tcp_sk().snd_cwnd() / 2, 2)
+    }
+
+    fn undo_cwnd(sk: &mut cong::Sock<'_, Self>) -> u32 {
+        cong::reno::undo_cwnd(sk)
+    }
+
+    fn set_state(sk: &mut cong::Sock<'_, Self>, new_state: cong::State) {
+        if matches!(new_state, cong::State::Loss) {
+            sk.inet_csk_ca_mut().reset();
+        }
+    }
+}
+
+/// Applies (or undoes) a delay-gradient backoff for the round that just
+/// ended, based on the chosen smoothed gradient left in `ca.gradient`.
+fn maybe_backoff(sk: &mut cong::Sock<'_, Cdg>) {
+    let cwnd = sk.tcp_sk().snd_cwnd();
+    let ca = sk.inet_csk_ca_mut();
+    let g = ca.gradient;
+
+    if draw_backoff(backoff_probability(g)) {
+        ca.shadow_wnd.get_or_insert(cwnd);
+        let new_cwnd = max((cwnd * BETA_NUM) / BETA_DENOM, 2);
+        sk.tcp_sk_mut().set_snd_cwnd(new_cwnd);
+    } else if let Some(shadow) = sk.inet_csk_ca().shadow_wnd {
+        // The gradient is not falling: the backoff did not drain the queue,
+        // so recover the window given up to it.
+        if g >= 0 {
+            let restored = max(cwnd, shadow);
+            sk.tcp_sk_mut().set_snd_cwnd(restored);
+            sk.inet_csk_ca_mut().shadow_wnd = None;
+        }
+    }
+}
+
+/// Internal state of each instance of the algorithm.
+#[derive(Default)]
+struct CdgState {
+    /// Minimum RTT observed so far in the current round.
+    rtt_min: Option<time::Usecs32>,
+    /// Maximum RTT observed so far in the current round.
+    rtt_max: Option<time::Usecs32>,
+    /// `rtt_min` from the previous round, i.e. `RTTmin[n-1]`.
+    prev_min: Option<time::Usecs32>,
+    /// `rtt_max` from the previous round, i.e. `RTTmax[n-1]`.
+    prev_max: Option<time::Usecs32>,
+    /// Moving average of `g_min = RTTmin[n] - RTTmin[n-1]`.
+    gradient_min: GradientWindow,
+    /// Moving average of `g_max = RTTmax[n] - RTTmax[n-1]`.
+    gradient_max: GradientWindow,
+    /// The more conservative (larger) of the two smoothed gradients, chosen
+    /// at the end of the round that just ended.
+    gradient: i32,
+    /// Sequence number marking the end of the current round.
+    round_end: u32,
+    /// cwnd saved from just before a backoff, restored once the backoff is
+    /// found to have been ineffectual.
+    shadow_wnd: Option<u32>,
+}
+
+impl CdgState {
+    /// Ends the current round: folds this round's `rtt_min`/`rtt_max` into
+    /// the smoothed gradients, and starts the next round at `snd_nxt`.
+    fn end_round(&mut self, snd_nxt: u32) {
+        if let Some(rtt_min) = self.rtt_min.take() {
+            if let Some(prev_min) = self.prev_min {
+                let g = self.gradient_min.push(rtt_min as i32 - prev_min as i32);
+                self.gradient = g;
+            }
+            self.prev_min = Some(rtt_min);
+        }
+        if let Some(rtt_max) = self.rtt_max.take() {
+            if let Some(prev_max) = self.prev_max {
+                let g = self.gradient_max.push(rtt_max as i32 - prev_max as i32);
+                self.gradient = max(self.gradient, g);
+            }
+            self.prev_max = Some(rtt_max);
+        }
+
+        self.round_end = snd_nxt;
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -13,61 +13,120 @@
 use core::cmp::{max, min};
 use core::num::NonZeroU32;
 use kernel::c_str;
+use kernel::declare_params;
 use kernel::net::tcp;
-use kernel::net::tcp::cong::{self, hystart, hystart::HystartDetect, module_cca};
+use kernel::net::tcp::cong::{self, hystart, hystart::HystartDetect, module_cca, param::Param};
 use kernel::prelude::*;
 use kernel::time;
 
+/// Tracepoints for `tcp_cubic_rust`, replacing the ad-hoc `pr_info!`/
+/// `pr_debug!` calls that used to live next to the code below. The `TRACE_EVENT`
+/// definitions live in `include/trace/events/tcp.h`, alongside the ones the C
+/// implementation uses.
+mod trace {
+    use kernel::declare_trace;
+
+    declare_trace! {
+        /// A CUBIC socket was initialized.
+        pub(crate) fn tcp_cubic_init(cwnd: u32);
+        /// A CUBIC socket was destroyed.
+        pub(crate) fn tcp_cubic_release(cwnd: u32);
+        /// The sender entered the `Loss` state; CUBIC state was reset.
+        pub(crate) fn tcp_cubic_loss(cwnd: u32);
+        /// `ssthresh` was recalculated, e.g. on entering CWR/Recovery/Loss.
+        pub(crate) fn tcp_cubic_ssthresh(cwnd: u32, last_max_cwnd: u32, ssthresh: u32);
+        /// `cong_avoid` recalculated cwnd for this ACK.
+        pub(crate) fn tcp_cubic_cong_avoid(cwnd: u32, ssthresh: u32, slow_start: bool);
+        /// The cubic growth function was evaluated for the current epoch.
+        pub(crate) fn tcp_cubic_update(origin_point: u32, target: u32, cnt: u32);
+    }
+}
+
 const BICTCP_BETA_SCALE: u32 = 1024;
 
-// TODO: Convert to module parameters once they are available. Currently these
-// are the defaults from the C implementation.
-// TODO: Use `NonZeroU32` where appropriate.
-/// Whether to use fast convergence. This is a heuristic to increase the
-/// release of bandwidth by existing flows to speed up the convergence to a
-/// steady state when a new flow joins the link.
-const FAST_CONVERGENCE: bool = true;
-/// The factor for multiplicative decrease of cwnd upon a loss event. Will be
-/// divided by `BICTCP_BETA_SCALE`, approximately 0.7.
-const BETA: u32 = 717;
-/// The initial value of ssthresh for new connections. Setting this to `None`
-/// implies `i32::MAX`.
-const INITIAL_SSTHRESH: Option<u32> = None;
-/// The parameter `C` that scales the cubic term is defined as `BIC_SCALE/2^10`.
-/// (For C: Dimension: Time^-2, Unit: s^-2).
-const BIC_SCALE: u32 = 41;
-/// In environments where CUBIC grows cwnd less aggressively than normal TCP,
-/// enabling this option causes it to behave like normal TCP instead. This is
-/// the case in short RTT and/or low bandwidth delay product networks.
-const TCP_FRIENDLINESS: bool = true;
-/// Whether to use the [HyStart] slow start algorithm.
-///
-/// [HyStart]: hystart::HyStart
-const HYSTART: bool = true;
+declare_params! {
+    /// Whether to use fast convergence. This is a heuristic to increase the
+    /// release of bandwidth by existing flows to speed up the convergence to
+    /// a steady state when a new flow joins the link.
+    pub static FAST_CONVERGENCE: Param = Param::new(1), perm: 0o644;
+    /// The factor for multiplicative decrease of cwnd upon a loss event. Will
+    /// be divided by `BICTCP_BETA_SCALE`, approximately 0.7.
+    pub static BETA: Param = Param::new(717), perm: 0o644;
+    /// The initial value of ssthresh for new connections. `0` means
+    /// `i32::MAX`.
+    pub static INITIAL_SSTHRESH: Param = Param::new(0), perm: 0o644;
+    /// The parameter `C` that scales the cubic term is defined as
+    /// `BIC_SCALE/2^10`. (For C: Dimension: Time^-2, Unit: s^-2).
+    pub static BIC_SCALE: Param = Param::new(41), perm: 0o644;
+    /// In environments where CUBIC grows cwnd less aggressively than normal
+    /// TCP, enabling this option causes it to behave like normal TCP
+    /// instead. This is the case in short RTT and/or low bandwidth delay
+    /// product networks.
+    pub static TCP_FRIENDLINESS: Param = Param::new(1), perm: 0o644;
+    /// Whether to use the [HyStart] slow start algorithm.
+    ///
+    /// [HyStart]: hystart::HyStart
+    pub static HYSTART: Param = Param::new(1), perm: 0o644;
+}
+
+declare_params! {
+    /// Which heuristic the [HyStart] slow start algorithm should use to find
+    /// the exit point for slow start. Mirrors C's `hystart_detect`.
+    ///
+    /// [HyStart]: hystart::HyStart
+    pub static HYSTART_DETECT: Param = Param::new(HystartDetect::Both as u32), perm: 0o644;
+    /// Lower bound for cwnd during hybrid slow start. Mirrors C's
+    /// `hystart_low_window`.
+    pub static HYSTART_LOW_WINDOW: Param = Param::new(16), perm: 0o644;
+    /// Max spacing between ACKs in an ACK-train. Mirrors C's
+    /// `hystart_ack_delta_us`.
+    pub static HYSTART_ACK_DELTA: Param = Param::new(2000), perm: 0o644;
+}
 
 impl hystart::HyStart for Cubic {
-    /// Which mechanism to use for deciding when it is time to exit slow start.
-    const DETECT: HystartDetect = HystartDetect::Both;
-    /// Lower bound for cwnd during hybrid slow start.
-    const LOW_WINDOW: u32 = 16;
-    /// Spacing between ACKs indicating an ACK-train.
-    /// (Dimension: Time. Unit: us).
-    const ACK_DELTA: time::Usecs32 = 2000;
+    const DETECT: &'static Param = &HYSTART_DETECT;
+    const LOW_WINDOW: &'static Param = &HYSTART_LOW_WINDOW;
+    const ACK_DELTA: &'static Param = &HYSTART_ACK_DELTA;
+}
+
+/// Values derived from the module parameters above.
+///
+/// Mirrors the C implementation's `beta_scale`/`cube_rtt_scale`/`cube_factor`,
+/// which are likewise recomputed rather than hard-coded, since `BETA` and
+/// `BIC_SCALE` may be changed at runtime through `sysfs`. Recomputed per
+/// socket on [`Cubic::init`] and again whenever the socket's state is reset
+/// on entering `Loss`; in between, a parameter change does not affect the
+/// cached values of an existing connection.
+struct Config {
+    /// Factor of `8/3 * (1 + beta) / (1 - beta)` that is used in various
+    /// calculations. (Dimension: none)
+    beta_scale: u32,
+    /// Factor of `2^10*C/SRTT` where `SRTT = 100ms` that is used in various
+    /// calculations. (Dimension: Time^-3, Unit: s^-3).
+    cube_rtt_scale: u32,
+    /// Factor of `SRTT/C` where `SRTT = 100ms` and `C` from above.
+    /// (Dimension: Time^3. Unit: (ms)^3)
+    // Note: C uses a custom time unit of 2^-10 s called `BICTCP_HZ`. This
+    // implementation consistently uses milliseconds instead.
+    cube_factor: u64,
 }
 
-// TODO: Those are computed based on the module parameters in the init. Even
-// with module parameters available this will be a bit tricky to do in Rust.
-/// Factor of `8/3 * (1 + beta) / (1 - beta)` that is used in various
-/// calculations. (Dimension: none)
-const BETA_SCALE: u32 = ((8 * (BICTCP_BETA_SCALE + BETA)) / 3) / (BICTCP_BETA_SCALE - BETA);
-/// Factor of `2^10*C/SRTT` where `SRTT = 100ms` that is used in various
-/// calculations. (Dimension: Time^-3, Unit: s^-3).
-const CUBE_RTT_SCALE: u32 = BIC_SCALE * 10;
-/// Factor of `SRTT/C` where `SRTT = 100ms` and `C` from above.
-/// (Dimension: Time^3. Unit: (ms)^3)
-// Note: C uses a custom time unit of 2^-10 s called `BICTCP_HZ`. This
-// implementation consistently uses milliseconds instead.
-const CUBE_FACTOR: u64 = 1_000_000_000 * (1u64 << 10) / (CUBE_RTT_SCALE as u64);
+impl Config {
+    fn compute() -> Self {
+        let beta = BETA.get();
+        let bic_scale = BIC_SCALE.get();
+
+        let beta_scale = ((8 * (BICTCP_BETA_SCALE + beta)) / 3) / (BICTCP_BETA_SCALE - beta);
+        let cube_rtt_scale = bic_scale * 10;
+        let cube_factor = 1_000_000_000 * (1u64 << 10) / (cube_rtt_scale as u64);
+
+        Self {
+            beta_scale,
+            cube_rtt_scale,
+            cube_factor,
+        }
+    }
+}
 
 module_cca! {
     type: Cubic,
@@ -84,28 +143,27 @@ impl cong::Algorithm for Cubic {
     type Data = CubicState;
 
     const NAME: &'static CStr = c_str!("cubic_rust");
+    const FLAGS: cong::Flags = cong::Flags::NON_RESTRICTED;
 
     fn init(sk: &mut cong::Sock<'_, Self>) {
-        if HYSTART {
+        sk.inet_csk_ca_mut().cfg = Config::compute();
+
+        if HYSTART.get() != 0 {
             <Self as hystart::HyStart>::reset(sk)
-        } else if let Some(ssthresh) = INITIAL_SSTHRESH {
-            sk.tcp_sk_mut().set_snd_ssthresh(ssthresh);
+        } else {
+            let ssthresh = INITIAL_SSTHRESH.get();
+            if ssthresh != 0 {
+                sk.tcp_sk_mut().set_snd_ssthresh(ssthresh);
+            }
         }
 
-        // TODO: remove
-        pr_info!(
-            "init: socket created: start {}us",
-            sk.inet_csk_ca().hystart_state.start_time
-        );
+        // SAFETY: `tcp_cubic_init` is a valid tracepoint.
+        unsafe { trace::tcp_cubic_init(sk.tcp_sk().snd_cwnd()) };
     }
 
-    // TODO: remove
     fn release(sk: &mut cong::Sock<'_, Self>) {
-        pr_info!(
-            "release: socket destroyed: start {}us, end {}us",
-            sk.inet_csk_ca().hystart_state.start_time,
-            time::ktime_get_boot_fast_us32(),
-        );
+        // SAFETY: `tcp_cubic_release` is a valid tracepoint.
+        unsafe { trace::tcp_cubic_release(sk.tcp_sk().snd_cwnd()) };
     }
 
     fn cwnd_event(sk: &mut cong::Sock<'_, Self>, ev: cong::Event) {
@@ -123,8 +181,6 @@ impl cong::Algorithm for Cubic {
             // Ok, lets switch to SI units.
             let now = time::ktime_get_boot_fast_ms32();
             let delta = time::jiffies_to_msecs(delta as time::Jiffies);
-            // TODO: remove
-            pr_debug!("cwnd_event: TxStart, now {}ms, delta {}ms", now, delta);
             // We were application limited, i.e., idle, for a while. If we are
             // in congestion avoidance, shift `epoch_start` by the time we were
             // idle to keep cwnd growth to cubic curve.
@@ -140,12 +196,8 @@ impl cong::Algorithm for Cubic {
 
     fn set_state(sk: &mut cong::Sock<'_, Self>, new_state: cong::State) {
         if matches!(new_state, cong::State::Loss) {
-            pr_info!(
-                // TODO: remove
-                "set_state: Loss, time {}us, start {}us",
-                time::ktime_get_boot_fast_us32(),
-                sk.inet_csk_ca().hystart_state.start_time
-            );
+            // SAFETY: `tcp_cubic_loss` is a valid tracepoint.
+            unsafe { trace::tcp_cubic_loss(sk.tcp_sk().snd_cwnd()) };
             sk.inet_csk_ca_mut().reset();
             <Self as hystart::HyStart>::reset(sk);
         }
@@ -154,11 +206,6 @@ impl cong::Algorithm for Cubic {
     fn pkts_acked(sk: &mut cong::Sock<'_, Self>, sample: &cong::AckSample) {
         // Some samples do not include RTTs.
         let Some(rtt_us) = sample.rtt_us() else {
-            // TODO: remove
-            pr_debug!(
-                "pkts_acked: no RTT sample, start {}us",
-                sk.inet_csk_ca().hystart_state.start_time,
-            );
             return;
         };
 
@@ -168,12 +215,6 @@ impl cong::Algorithm for Cubic {
         if epoch_start.is_some_and(|epoch_start| {
             time::ktime_get_boot_fast_ms32().wrapping_sub(epoch_start) < time::MSEC_PER_SEC
         }) {
-            // TODO: remove
-            pr_debug!(
-                "pkts_acked: {}ms - {}ms < 1s, too close to epoch_start",
-                time::ktime_get_boot_fast_ms32(),
-                epoch_start.unwrap()
-            );
             return;
         }
 
@@ -182,14 +223,6 @@ impl cong::Algorithm for Cubic {
         let in_slow_start = sk.tcp_sk().in_slow_start();
         let ca = sk.inet_csk_ca_mut();
 
-        // TODO: remove
-        pr_debug!(
-            "pkts_acked: delay {}us, cwnd {}, ss {}",
-            delay,
-            cwnd,
-            in_slow_start
-        );
-
         // First call after reset or the delay decreased.
         if ca.hystart_state.delay_min.is_none()
             || ca
@@ -200,7 +233,7 @@ impl cong::Algorithm for Cubic {
             ca.hystart_state.delay_min = Some(delay);
         }
 
-        if in_slow_start && HYSTART && ca.hystart_state.in_hystart::<Self>(cwnd) {
+        if in_slow_start && HYSTART.get() != 0 && ca.hystart_state.in_hystart::<Self>(cwnd) {
             hystart::HyStart::update(sk, delay);
         }
     }
@@ -209,35 +242,52 @@ impl cong::Algorithm for Cubic {
         let cwnd = sk.tcp_sk().snd_cwnd();
         let ca = sk.inet_csk_ca_mut();
 
-        pr_info!(
-            // TODO: remove
-            "ssthresh: time {}us, start {}us",
-            time::ktime_get_boot_fast_us32(),
-            ca.hystart_state.start_time
-        );
-
         // Epoch has ended.
         ca.epoch_start = None;
-        ca.last_max_cwnd = if cwnd < ca.last_max_cwnd && FAST_CONVERGENCE {
-            (cwnd * (BICTCP_BETA_SCALE + BETA)) / (2 * BICTCP_BETA_SCALE)
+        ca.last_max_cwnd = if cwnd < ca.last_max_cwnd && FAST_CONVERGENCE.get() != 0 {
+            (cwnd * (BICTCP_BETA_SCALE + BETA.get())) / (2 * BICTCP_BETA_SCALE)
         } else {
             cwnd
         };
 
-        max((cwnd * BETA) / BICTCP_BETA_SCALE, 2)
+        let ssthresh = max((cwnd * BETA.get()) / BICTCP_BETA_SCALE, 2);
+        // SAFETY: `tcp_cubic_ssthresh` is a valid tracepoint.
+        unsafe { trace::tcp_cubic_ssthresh(cwnd, ca.last_max_cwnd, ssthresh) };
+        ssthresh
     }
 
     fn undo_cwnd(sk: &mut cong::Sock<'_, Self>) -> u32 {
-        pr_info!(
-            // TODO: remove
-            "undo_cwnd: time {}us, start {}us",
-            time::ktime_get_boot_fast_us32(),
-            sk.inet_csk_ca().hystart_state.start_time
-        );
-
         cong::reno::undo_cwnd(sk)
     }
 
+    fn get_info(
+        sk: &cong::Sock<'_, Self>,
+        attr: u32,
+        writer: &mut cong::InfoWriter<'_>,
+    ) -> Option<u32> {
+        if attr != kernel::bindings::INET_DIAG_VEGASINFO {
+            return None;
+        }
+
+        let ca = sk.inet_csk_ca();
+        let info = kernel::bindings::tcp_cubic_info {
+            cnt: ca.cnt.get(),
+            last_max_cwnd: ca.last_max_cwnd,
+            last_cwnd: ca.last_cwnd,
+            last_time: ca.last_time,
+            bic_origin_point: ca.origin_point,
+            bic_K: ca.K,
+            delay_min: ca.hystart_state.delay_min.unwrap_or(0),
+            epoch_start: ca.epoch_start.unwrap_or(0),
+            ack_cnt: ca.ack_cnt,
+            tcp_cwnd: ca.tcp_cwnd,
+            found: u8::from(ca.hystart_state.in_hystart::<Self>(sk.tcp_sk().snd_cwnd())).into(),
+        };
+
+        writer.write(&info)?;
+        Some(kernel::bindings::INET_DIAG_VEGASINFO)
+    }
+
     fn cong_avoid(sk: &mut cong::Sock<'_, Self>, _ack: u32, mut acked: u32) {
         if !sk.tcp_is_cwnd_limited() {
             return;
@@ -248,14 +298,14 @@ impl cong::Algorithm for Cubic {
         if tp.in_slow_start() {
             acked = tp.slow_start(acked);
             if acked == 0 {
-                pr_info!(
-                    // TODO: remove
-                    "cong_avoid: new cwnd {}, time {}us, ssthresh {}, start {}us, ss 1",
-                    sk.tcp_sk().snd_cwnd(),
-                    time::ktime_get_boot_fast_us32(),
-                    sk.tcp_sk().snd_ssthresh(),
-                    sk.inet_csk_ca().hystart_state.start_time
-                );
+                // SAFETY: `tcp_cubic_cong_avoid` is a valid tracepoint.
+                unsafe {
+                    trace::tcp_cubic_cong_avoid(
+                        sk.tcp_sk().snd_cwnd(),
+                        sk.tcp_sk().snd_ssthresh(),
+                        true,
+                    )
+                };
                 return;
             }
         }
@@ -264,14 +314,10 @@ impl cong::Algorithm for Cubic {
         let cnt = sk.inet_csk_ca_mut().update(cwnd, acked);
         sk.tcp_sk_mut().cong_avoid_ai(cnt, acked);
 
-        pr_info!(
-            // TODO: remove
-            "cong_avoid: new cwnd {}, time {}us, ssthresh {}, start {}us, ss 0",
-            sk.tcp_sk().snd_cwnd(),
-            time::ktime_get_boot_fast_us32(),
-            sk.tcp_sk().snd_ssthresh(),
-            sk.inet_csk_ca().hystart_state.start_time
-        );
+        // SAFETY: `tcp_cubic_cong_avoid` is a valid tracepoint.
+        unsafe {
+            trace::tcp_cubic_cong_avoid(sk.tcp_sk().snd_cwnd(), sk.tcp_sk().snd_ssthresh(), false)
+        };
     }
 }
 
@@ -299,6 +345,8 @@ struct CubicState {
     tcp_cwnd: u32,
     /// State of the HyStart slow start algorithm.
     hystart_state: hystart::HyStartState,
+    /// Values derived from the module parameters, recomputed on `init`.
+    cfg: Config,
 }
 
 impl hystart::HasHyStartState for CubicState {
@@ -326,6 +374,7 @@ impl Default for CubicState {
             ack_cnt: 0,
             tcp_cwnd: 0,
             hystart_state: hystart::HyStartState::default(),
+            cfg: Config::compute(),
         }
     }
 }
@@ -336,27 +385,19 @@ impl CubicState {
     /// increases at the speed of normal TCP.
     #[inline]
     fn tcp_friendliness(&mut self, cnt: u32, cwnd: u32) -> u32 {
-        if !TCP_FRIENDLINESS {
+        if TCP_FRIENDLINESS.get() == 0 {
             return cnt;
         }
 
         // Estimate cwnd of normal TCP.
         // cwnd/3 * (1 + BETA)/(1 - BETA)
-        let delta = (cwnd * BETA_SCALE) >> 3;
+        let delta = (cwnd * self.cfg.beta_scale) >> 3;
         // W__tcp(t) = W__tcp(t__0) + (acks(t) - acks(t__0)) / delta
         while self.ack_cnt > delta {
             self.ack_cnt -= delta;
             self.tcp_cwnd += 1;
         }
 
-        //TODO: remove
-        pr_info!(
-            "tcp_friendliness: tcp_cwnd {}, cwnd {}, start {}us",
-            self.tcp_cwnd,
-            cwnd,
-            self.hystart_state.start_time,
-        );
-
         // We are slower than normal TCP.
         if self.tcp_cwnd > cwnd {
             let delta = self.tcp_cwnd - cwnd;
@@ -401,7 +442,7 @@ impl CubicState {
                 self.origin_point = cwnd;
             } else {
                 // K = (SRTT/C * (W__max - cwnd))^1/3
-                self.K = cubic_root(CUBE_FACTOR * ((self.last_max_cwnd - cwnd) as u64));
+                self.K = cubic_root(self.cfg.cube_factor * ((self.last_max_cwnd - cwnd) as u64));
                 self.origin_point = self.last_max_cwnd;
             }
         }
@@ -422,7 +463,7 @@ impl CubicState {
         // Calculate c/rtt * (t-K)^3 and change units to seconds.
         // Widen type to prevent overflow.
         let offs = offs as u64;
-        let delta = (((CUBE_RTT_SCALE as u64 * offs * offs * offs) >> 10) / 1_000_000_000) as u32;
+        let delta = (((self.cfg.cube_rtt_scale as u64 * offs * offs * offs) >> 10) / 1_000_000_000) as u32;
         // Calculate the full cubic function c/rtt * (t - K)^3 + W__max.
         let target = if t < self.K {
             self.origin_point - delta
@@ -430,20 +471,6 @@ impl CubicState {
             self.origin_point + delta
         };
 
-        // TODO: remove
-        pr_info!(
-            "update: now {}ms, epoch_start {}ms, t {}ms, K {}ms, |t - K| {}ms, last_max_cwnd {}, origin_point {}, target {}, start {}us",
-            now,
-            epoch_start,
-            t,
-            self.K,
-            offs,
-            self.last_max_cwnd,
-            self.origin_point,
-            target,
-            self.hystart_state.start_time,
-        );
-
         let mut cnt = if target > cwnd {
             cwnd / (target - cwnd)
         } else {
@@ -459,6 +486,9 @@ impl CubicState {
         // SAFETY: 2 != 0. QED.
         self.cnt = unsafe { NonZeroU32::new_unchecked(max(2, self.tcp_friendliness(cnt, cwnd))) };
 
+        // SAFETY: `tcp_cubic_update` is a valid tracepoint.
+        unsafe { trace::tcp_cubic_update(self.origin_point, target, self.cnt.get()) };
+
         self.cnt
     }
 
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! ACPI abstractions.
+//!
+//! C header: [`include/linux/acpi.h`](../../../../include/linux/acpi.h)
+
+use crate::{
+    bindings, device_id,
+    str::{BStr, CStr},
+};
+
+/// An ACPI device id.
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct DeviceId(bindings::acpi_device_id);
+
+impl DeviceId {
+    /// Create an ACPI `DeviceId` from an ACPI Hardware ID (HID) string.
+    pub const fn new(hid: &CStr) -> Self {
+        let device_id = core::mem::MaybeUninit::<bindings::acpi_device_id>::zeroed();
+        let mut device_id = unsafe { device_id.assume_init() };
+
+        let hid = BStr::from_bytes(hid.as_bytes_with_nul());
+        assert!(hid.len() <= device_id.id.len());
+
+        let mut i = 0;
+        while i < hid.len() {
+            device_id.id[i] = hid.deref_const()[i] as _;
+            i += 1;
+        }
+
+        Self(device_id)
+    }
+}
+
+// SAFETY: `ZERO` is all zeroed-out and `to_rawid` stores `offset` in `acpi_device_id::driver_data`.
+unsafe impl device_id::RawDeviceId for DeviceId {
+    type RawType = bindings::acpi_device_id;
+    const DRIVER_DATA_OFFSET: usize = core::mem::offset_of!(bindings::acpi_device_id, driver_data);
+}
+
+/// Alias for `device_id::IdTable` containing ACPI's `DeviceId`
+pub type IdTable<T> = &'static dyn device_id::IdTable<DeviceId, T>;
+
+/// Create an ACPI `IdTable` with its alias for modpost.
+#[macro_export]
+macro_rules! acpi_device_table {
+    ($module_table_name:ident, $table_name:ident, $id_info_type: ty, $table_data: expr) => {
+        const $table_name: $crate::device_id::IdArray<
+            $crate::acpi::DeviceId,
+            $id_info_type,
+            { $table_data.len() },
+        > = $crate::device_id::IdArray::new($table_data);
+
+        $crate::module_device_table!("acpi", $module_table_name, $table_name);
+    };
+}
@@ -71,6 +71,109 @@ pub const fn is_aligned(val: usize, alignment: usize) -> bool {
     (val & (alignment - 1)) == 0
 }
 
+/// Like [`mask`], but returns `None` instead of silently wrapping when
+/// `val + mask` overflows `usize`.
+#[allow(dead_code)]
+#[inline]
+pub const fn checked_mask(val: usize, mask: usize) -> Option<usize> {
+    match val.checked_add(mask) {
+        Some(sum) => Some(sum & !mask),
+        None => None,
+    }
+}
+
+/// Like [`align`], but returns `None` instead of silently misbehaving when
+/// `alignment` is not a power of 2, or when rounding `val` up overflows
+/// `usize`, instead of leaving both as the caller's responsibility.
+#[allow(dead_code)]
+#[inline]
+pub const fn checked_align(val: usize, alignment: usize) -> Option<usize> {
+    if !alignment.is_power_of_two() {
+        return None;
+    }
+    checked_mask(val, alignment - 1)
+}
+
+/// Like [`align_down`], but returns `None` instead of silently underflowing
+/// when `alignment` is not a power of 2, or when `val` is smaller than
+/// `alignment - 1`, instead of leaving both as the caller's responsibility.
+#[allow(dead_code)]
+#[inline]
+pub const fn checked_align_down(val: usize, alignment: usize) -> Option<usize> {
+    if !alignment.is_power_of_two() {
+        return None;
+    }
+    match val.checked_sub(alignment - 1) {
+        Some(v) => Some(align(v, alignment)),
+        None => None,
+    }
+}
+
+/// A validated power-of-2 alignment.
+///
+/// Unlike the bare [`align`]/[`align_down`] functions, constructing an
+/// `Alignment` checks the power-of-2 requirement once, up front (and can do
+/// so at compile time for a constant alignment via [`Alignment::new`]).
+/// Code holding an `Alignment` can then call [`Alignment::align`]/
+/// [`Alignment::align_down`] and get the same branch-free codegen as the
+/// bare functions, without re-deriving the invariant at every call site.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alignment(usize);
+
+impl Alignment {
+    /// Validates that `alignment` is a power of 2, returning `None`
+    /// otherwise.
+    #[allow(dead_code)]
+    pub const fn new(alignment: usize) -> Option<Self> {
+        if alignment.is_power_of_two() {
+            Some(Self(alignment))
+        } else {
+            None
+        }
+    }
+
+    /// The validated alignment value, in bytes.
+    #[allow(dead_code)]
+    #[inline]
+    pub const fn get(self) -> usize {
+        self.0
+    }
+
+    /// Rounds `val` up to this alignment.
+    ///
+    /// `val` must be small enough that rounding up doesn't overflow
+    /// `usize`; use [`checked_align`](Self::checked_align) when that isn't
+    /// already known to hold.
+    #[allow(dead_code)]
+    #[inline]
+    pub const fn align(self, val: usize) -> usize {
+        align(val, self.0)
+    }
+
+    /// Rounds `val` down to this alignment.
+    #[allow(dead_code)]
+    #[inline]
+    pub const fn align_down(self, val: usize) -> usize {
+        align_down(val, self.0)
+    }
+
+    /// Like [`align`](Self::align), but returns `None` instead of silently
+    /// overflowing when rounding `val` up doesn't fit in a `usize`.
+    #[allow(dead_code)]
+    #[inline]
+    pub const fn checked_align(self, val: usize) -> Option<usize> {
+        checked_mask(val, self.0 - 1)
+    }
+
+    /// Tests if `val` is aligned to this alignment.
+    #[allow(dead_code)]
+    #[inline]
+    pub const fn is_aligned(self, val: usize) -> bool {
+        is_aligned(val, self.0)
+    }
+}
+
 /// An interface for dealing with alignment.
 trait Align {
     /// The type of alignment value. This type should be integer.
@@ -88,6 +191,25 @@ trait Align {
 
     /// Test if Aligned.
     fn is_aligned(&self, alignment: Self::Alignment) -> bool;
+
+    /// Like `mask`, but returns `None` instead of silently wrapping on
+    /// overflow.
+    fn checked_mask(&self, mask: Self::Alignment) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Like `align`, but returns `None` instead of silently misbehaving
+    /// when `alignment` is not a power of 2, or when rounding up overflows.
+    fn checked_align(&self, alignment: Self::Alignment) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Like `align_down`, but returns `None` instead of silently
+    /// underflowing when `alignment` is not a power of 2, or when `self` is
+    /// smaller than `alignment - 1`.
+    fn checked_align_down(&self, alignment: Self::Alignment) -> Option<Self>
+    where
+        Self: Sized;
 }
 
 /// A Helper macro for implementing `Align` trait for primitive integer types.
@@ -117,6 +239,27 @@ macro_rules! impl_align_for_integer {
             fn is_aligned(&self, alignment: Self) -> bool {
                 (self & (alignment - 1)) == 0
             }
+
+            #[inline]
+            fn checked_mask(&self, mask: Self) -> Option<Self> {
+                self.checked_add(mask).map(|sum| sum & !mask)
+            }
+
+            #[inline]
+            fn checked_align(&self, alignment: Self) -> Option<Self> {
+                if !(alignment > 0 && (alignment & (alignment - 1)) == 0) {
+                    return None;
+                }
+                self.checked_mask(alignment - 1)
+            }
+
+            #[inline]
+            fn checked_align_down(&self, alignment: Self) -> Option<Self> {
+                if !(alignment > 0 && (alignment & (alignment - 1)) == 0) {
+                    return None;
+                }
+                self.checked_sub(alignment - 1)?.checked_align(alignment)
+            }
         }
     };
 }
@@ -209,4 +352,54 @@ mod tests {
         test_for_integer!(usize, 0xf0);
         test_for_integer!(isize, 0xf0 as isize);
     }
+
+    #[test]
+    fn test_checked_fn() {
+        assert_eq!(checked_mask(PAGE_SIZE, 0x03), Some(mask(PAGE_SIZE, 0x03)));
+        assert_eq!(checked_align(PAGE_SIZE, 8), Some(align(PAGE_SIZE, 8)));
+        assert_eq!(
+            checked_align_down(PAGE_SIZE, 8),
+            Some(align_down(PAGE_SIZE, 8))
+        );
+
+        // Non-power-of-2 alignments are rejected instead of silently
+        // misbehaving.
+        assert_eq!(checked_align(PAGE_SIZE, 3), None);
+        assert_eq!(checked_align_down(PAGE_SIZE, 3), None);
+
+        // Rounding up near the top of the range overflows instead of
+        // wrapping around to a small value.
+        assert_eq!(checked_mask(usize::MAX, 0x03), None);
+        assert_eq!(checked_align(usize::MAX, PAGE_SIZE), None);
+        assert_eq!(checked_align(usize::MAX, 2), None);
+
+        // Aligning 0 down to a larger alignment has no valid answer, rather
+        // than silently underflowing to a huge value.
+        assert_eq!(checked_align_down(0, PAGE_SIZE), None);
+        assert_eq!(checked_align_down(0, 1), Some(0));
+    }
+
+    #[test]
+    fn test_alignment() {
+        assert_eq!(Alignment::new(0), None);
+        assert_eq!(Alignment::new(3), None);
+
+        let a = Alignment::new(PAGE_SIZE).unwrap();
+        assert_eq!(a.get(), PAGE_SIZE);
+        assert_eq!(a.align(PAGE_SIZE + 1), PAGE_SIZE * 2);
+        assert_eq!(a.align_down(PAGE_SIZE + 1), PAGE_SIZE);
+        assert!(a.is_aligned(PAGE_SIZE));
+        assert!(!a.is_aligned(PAGE_SIZE + 1));
+        assert_eq!(a.checked_align(usize::MAX), None);
+    }
+
+    #[test]
+    fn test_checked_integer_fn() {
+        assert_eq!(0xf0u8.checked_align(4), Some(0xf0));
+        assert_eq!((0xf0u8 - 1).checked_align_down(4), Some(0xf0 - 4));
+        assert_eq!(0xf0u8.checked_align(3), None);
+        assert_eq!(u8::MAX.checked_align(4), None);
+        assert_eq!(0u8.checked_align_down(4), None);
+        assert_eq!(0u8.checked_align_down(1), Some(0));
+    }
 }
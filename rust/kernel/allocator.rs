@@ -6,18 +6,103 @@ use core::alloc::{GlobalAlloc, Layout};
 use core::ptr;
 
 use crate::bindings;
+use crate::c_types;
+
+/// The alignment `krealloc()`/`kmalloc()` guarantee for any request, matching the kernel's
+/// `ARCH_KMALLOC_MINALIGN` on every architecture this crate supports. Bindgen doesn't expose the
+/// macro itself, so the value is hardcoded here instead.
+const KMALLOC_MIN_ALIGN: usize = 8;
+
+/// Returns whether `layout.size()` is a power of two that `krealloc()` naturally aligns to
+/// `layout.align()`, the same way `kmalloc()`'s slab buckets do for power-of-two sizes.
+fn naturally_aligned(layout: Layout) -> bool {
+    layout.size().is_power_of_two() && layout.size() >= layout.align()
+}
+
+/// Returns whether `layout` needs the over-allocate-and-align fallback below, i.e. whether
+/// `krealloc()`'s own guarantees aren't already enough to satisfy it.
+///
+/// Any [`core::alloc::Allocator`] built over `krealloc()`/`krealloc_node()`/`kvmalloc()` (not just
+/// the `#[global_allocator]` below) needs to route through [`aligned_alloc`] whenever this
+/// returns `true`, or over-aligned types silently get under-aligned memory.
+pub(crate) fn needs_aligned_alloc(layout: Layout) -> bool {
+    layout.align() > KMALLOC_MIN_ALIGN && !naturally_aligned(layout)
+}
+
+/// Over-allocates `layout` and hands back a pointer aligned to `layout.align()`, stashing the
+/// real `krealloc()`/`krealloc_node()` pointer in the word immediately before it so
+/// [`aligned_free`]/realloc can recover it later.
+///
+/// Allocates on `node` through `krealloc_node()` when given, or via plain `krealloc()` otherwise.
+///
+/// # Safety
+///
+/// `flags` must be valid flags to pass to `krealloc()`/`krealloc_node()`.
+pub(crate) unsafe fn aligned_alloc(
+    layout: Layout,
+    flags: bindings::gfp_t,
+    node: Option<c_types::c_int>,
+) -> *mut u8 {
+    let header = core::mem::size_of::<*mut u8>();
+    let total = layout.size() + layout.align() + header;
+
+    // SAFETY: calling C, `total` is non-zero since `layout.align()` is.
+    let raw = unsafe {
+        match node {
+            Some(node) => bindings::krealloc_node(ptr::null(), total, flags, node) as *mut u8,
+            None => bindings::krealloc(ptr::null(), total, flags) as *mut u8,
+        }
+    };
+    if raw.is_null() {
+        return ptr::null_mut();
+    }
+
+    let aligned = (raw as usize + header).next_multiple_of(layout.align());
+
+    // SAFETY: `aligned - header` lies within the `total`-byte allocation just made, and is
+    // suitably aligned for a `*mut u8`.
+    unsafe { (aligned as *mut *mut u8).sub(1).write(raw) };
+
+    aligned as *mut u8
+}
+
+/// Frees a pointer returned by [`aligned_alloc`].
+///
+/// # Safety
+///
+/// `ptr` must have been returned by [`aligned_alloc`] and not yet freed.
+pub(crate) unsafe fn aligned_free(ptr: *mut u8) {
+    // SAFETY: `ptr` was returned by `aligned_alloc`, which always leaves the original
+    // `krealloc()`/`krealloc_node()` pointer in the word right before it.
+    let raw = unsafe { (ptr as *mut *mut u8).sub(1).read() };
+    // SAFETY: `raw` is the `krealloc()`/`krealloc_node()` pointer `aligned_alloc` allocated `ptr`
+    // from.
+    unsafe { bindings::kfree(raw as *const core::ffi::c_void) };
+}
 
 struct KernelAllocator;
 
 unsafe impl GlobalAlloc for KernelAllocator {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if needs_aligned_alloc(layout) {
+            // SAFETY: `bindings::GFP_KERNEL` is always valid to pass to `krealloc()`.
+            return unsafe { aligned_alloc(layout, bindings::GFP_KERNEL, None) };
+        }
+
         // `krealloc()` is used instead of `kmalloc()` because the latter is
         // an inline function and cannot be bound to as a result.
         // SAFETY: calling C, layout is non zero as per function
         unsafe { bindings::krealloc(ptr::null(), layout.size(), bindings::GFP_KERNEL) as *mut u8 }
     }
 
-    unsafe fn dealloc(&self, ptr: *mut u8, _layout: Layout) {
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if needs_aligned_alloc(layout) {
+            // SAFETY: `ptr` was returned by `alloc`/`alloc_zeroed` for this same `layout`,
+            // which routed through `aligned_alloc` since `needs_aligned_alloc(layout)` held.
+            unsafe { aligned_free(ptr) };
+            return;
+        }
+
         // SAFETY: calling C, ptr is valid and from `krealloc` or `kmalloc`.
         unsafe {
             bindings::kfree(ptr as *const core::ffi::c_void);
@@ -25,6 +110,14 @@ unsafe impl GlobalAlloc for KernelAllocator {
     }
 
     unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if needs_aligned_alloc(layout) {
+            // SAFETY: `bindings::GFP_KERNEL | bindings::__GFP_ZERO` is always valid to pass
+            // to `krealloc()`.
+            return unsafe {
+                aligned_alloc(layout, bindings::GFP_KERNEL | bindings::__GFP_ZERO, None)
+            };
+        }
+
         // `krealloc()` is used instead of `kmalloc()` because the latter is
         // an inline function and cannot be bound to as a result.
         // SAFETY: calling C, layout is non zero as per function
@@ -37,7 +130,28 @@ unsafe impl GlobalAlloc for KernelAllocator {
         }
     }
 
-    unsafe fn realloc(&self, ptr: *mut u8, _layout: Layout, new_size: usize) -> *mut u8 {
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_layout = match Layout::from_size_align(new_size, layout.align()) {
+            Ok(new_layout) => new_layout,
+            Err(_) => return ptr::null_mut(),
+        };
+
+        if needs_aligned_alloc(layout) || needs_aligned_alloc(new_layout) {
+            // SAFETY: `new_layout.size() == new_size > 0`, as required by `GlobalAlloc::realloc`.
+            let new_ptr = unsafe { self.alloc(new_layout) };
+            if !new_ptr.is_null() {
+                // SAFETY: the caller guarantees `ptr` is valid for `layout.size()` bytes, and
+                // `new_ptr` was just allocated for at least `min(layout.size(), new_size)`
+                // bytes; the two don't overlap since `new_ptr` is a fresh allocation.
+                unsafe {
+                    ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+                }
+                // SAFETY: `ptr` was allocated for `layout` through this same allocator.
+                unsafe { self.dealloc(ptr, layout) };
+            }
+            return new_ptr;
+        }
+
         // SAFETY: calling C, new_size is non zero as per function and prt is valid.
         unsafe {
             bindings::krealloc(
@@ -2,9 +2,12 @@
 
 use alloc::alloc::{AllocError, Allocator, Layout};
 use alloc::boxed::Box;
+use alloc::vec::Vec;
 use core::ptr::{self, NonNull};
 
+use crate::allocator::{aligned_alloc, needs_aligned_alloc};
 use crate::bindings;
+use crate::c_types;
 
 /// Allocator extension to pass Flags to allocator.
 pub trait FlagAllocator: Allocator {
@@ -14,6 +17,18 @@ pub trait FlagAllocator: Allocator {
         layout: Layout,
         flags: bindings::gfp_t,
     ) -> Result<NonNull<[u8]>, AllocError>;
+
+    /// Allocates memory with the given flag on a specific NUMA node.
+    ///
+    /// Use this instead of [`Self::allocate_with_flag`] when the caller
+    /// knows which node the allocation will be used from, e.g. to place a
+    /// per-queue RX ring on the same node as the NAPI instance draining it.
+    fn allocate_with_flag_node(
+        &self,
+        layout: Layout,
+        flags: bindings::gfp_t,
+        node: c_types::c_int,
+    ) -> Result<NonNull<[u8]>, AllocError>;
 }
 
 #[cfg(not(test))]
@@ -24,6 +39,18 @@ impl FlagAllocator for alloc::alloc::Global {
         layout: Layout,
         flags: bindings::gfp_t,
     ) -> Result<NonNull<[u8]>, AllocError> {
+        if needs_aligned_alloc(layout) {
+            // SAFETY: `flags` are valid flags to pass to `krealloc()`.
+            let mem = unsafe { aligned_alloc(layout, flags, None) };
+            if mem.is_null() {
+                return Err(AllocError);
+            }
+            // SAFETY: `mem` was just allocated for at least `layout.size()` bytes.
+            let mem = unsafe { core::slice::from_raw_parts_mut(mem, layout.size()) };
+            // Safety: checked for non null above
+            return Ok(unsafe { NonNull::new_unchecked(mem) });
+        }
+
         // `krealloc()` is used instead of `kmalloc()` because the latter is
         // an inline function and cannot be bound to as a result.
         let mem = unsafe { bindings::krealloc(ptr::null(), layout.size(), flags) as *mut u8 };
@@ -34,6 +61,37 @@ impl FlagAllocator for alloc::alloc::Global {
         // Safety: checked for non null abpve
         Ok(unsafe { NonNull::new_unchecked(mem) })
     }
+
+    fn allocate_with_flag_node(
+        &self,
+        layout: Layout,
+        flags: bindings::gfp_t,
+        node: c_types::c_int,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        if needs_aligned_alloc(layout) {
+            // SAFETY: `flags` are valid flags to pass to `krealloc_node()`.
+            let mem = unsafe { aligned_alloc(layout, flags, Some(node)) };
+            if mem.is_null() {
+                return Err(AllocError);
+            }
+            // SAFETY: `mem` was just allocated for at least `layout.size()` bytes.
+            let mem = unsafe { core::slice::from_raw_parts_mut(mem, layout.size()) };
+            // Safety: checked for non null above
+            return Ok(unsafe { NonNull::new_unchecked(mem) });
+        }
+
+        // `krealloc_node()` is used instead of `kmalloc_node()` for the same
+        // reason `krealloc()` is used above: `kmalloc_node()` is an inline
+        // function and cannot be bound to directly.
+        let mem =
+            unsafe { bindings::krealloc_node(ptr::null(), layout.size(), flags, node) as *mut u8 };
+        if mem.is_null() {
+            return Err(AllocError);
+        }
+        let mem = unsafe { core::slice::from_raw_parts_mut(mem, bindings::ksize(mem as _)) };
+        // Safety: checked for non null abpve
+        Ok(unsafe { NonNull::new_unchecked(mem) })
+    }
 }
 
 #[cfg(test)]
@@ -46,6 +104,15 @@ impl FlagAllocator for alloc::alloc::Global {
     ) -> Result<NonNull<[u8]>, AllocError> {
         self.allocate(layout)
     }
+
+    fn allocate_with_flag_node(
+        &self,
+        layout: Layout,
+        _flags: bindings::gfp_t,
+        _node: c_types::c_int,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocate(layout)
+    }
 }
 
 // Box Ext
@@ -77,9 +144,15 @@ pub trait BoxAllocFlagExt<T: Sized>: BoxAllocFlagInExt<T, alloc::alloc::Global>
     }
 
     /// Allocated box with flags on Glabal Allocator.
-    fn tr_new_flag(x: T, flags: bindings::gfp_t) -> Result<Box<T>, AllocError> {
+    fn try_new_flag(x: T, flags: bindings::gfp_t) -> Result<Box<T>, AllocError> {
         Self::try_new_flag_in(x, flags, alloc::alloc::Global)
     }
+
+    /// Allocates a box with `GFP_ATOMIC`, for use from contexts that cannot sleep (irq handlers,
+    /// code holding a spinlock, ...), where `GFP_KERNEL`'s blocking allocation path is forbidden.
+    fn try_new_atomic(x: T) -> Result<Box<T>, AllocError> {
+        Self::try_new_flag(x, bindings::GFP_ATOMIC)
+    }
 }
 
 impl<T, A> BoxAllocFlagInExt<T, A> for Box<T, A>
@@ -98,3 +171,91 @@ where
 }
 
 impl<T: Sized> BoxAllocFlagExt<T> for Box<T> {}
+
+// Vec Ext
+/// `Vec` extension providing functions to pass GFP flags to the allocator.
+///
+/// Useful for growable buffers, such as packet batches, that must be sized
+/// up front with `GFP_ATOMIC` from a context (e.g. a NAPI poll) where
+/// sleeping allocation is forbidden.
+pub trait VecAllocFlagExt<T: Sized, A: FlagAllocator>: Sized {
+    /// Allocates a vec with room for `capacity` elements, using `flags`.
+    fn try_with_capacity_flag_in(
+        capacity: usize,
+        flags: bindings::gfp_t,
+        alloc: A,
+    ) -> Result<Self, AllocError>;
+
+    /// Reserves capacity for at least `additional` more elements, using
+    /// `flags`.
+    fn try_reserve_flag(
+        &mut self,
+        additional: usize,
+        flags: bindings::gfp_t,
+    ) -> Result<(), AllocError>
+    where
+        A: Clone;
+
+    /// Allocates a vec with room for `capacity` elements, using `GFP_ATOMIC`, for use from
+    /// contexts that cannot sleep (irq handlers, code holding a spinlock, ...).
+    fn try_with_capacity_atomic(capacity: usize) -> Result<Self, AllocError>
+    where
+        A: Default,
+    {
+        Self::try_with_capacity_flag_in(capacity, bindings::GFP_ATOMIC, A::default())
+    }
+}
+
+impl<T, A> VecAllocFlagExt<T, A> for Vec<T, A>
+where
+    A: FlagAllocator,
+{
+    fn try_with_capacity_flag_in(
+        capacity: usize,
+        flags: bindings::gfp_t,
+        alloc: A,
+    ) -> Result<Self, AllocError> {
+        if capacity == 0 {
+            return Ok(Vec::new_in(alloc));
+        }
+
+        let layout = Layout::array::<T>(capacity).map_err(|_| AllocError)?;
+        let ptr = alloc.allocate_with_flag(layout, flags)?.cast::<T>();
+        // SAFETY: `ptr` points at a fresh allocation from `alloc`, sized for
+        // at least `capacity` elements of `T`, none of which are
+        // initialized, matching a length of 0.
+        Ok(unsafe { Vec::from_raw_parts_in(ptr.as_ptr(), 0, capacity, alloc) })
+    }
+
+    fn try_reserve_flag(
+        &mut self,
+        additional: usize,
+        flags: bindings::gfp_t,
+    ) -> Result<(), AllocError>
+    where
+        A: Clone,
+    {
+        let len = self.len();
+        let needed = len.checked_add(additional).ok_or(AllocError)?;
+        if needed <= self.capacity() {
+            return Ok(());
+        }
+
+        let mut grown = Self::try_with_capacity_flag_in(needed, flags, self.allocator().clone())?;
+
+        // SAFETY: `self` has `len` initialized elements of `T` at
+        // `self.as_ptr()`, and `grown` has room for at least `len` elements
+        // at `grown.as_mut_ptr()`, coming from a fresh, non-overlapping
+        // allocation. Setting `self`'s length to 0 before it is overwritten
+        // below hands ownership of those `len` elements to `grown` without
+        // either vec running `T`'s destructor on them.
+        unsafe {
+            ptr::copy_nonoverlapping(self.as_ptr(), grown.as_mut_ptr(), len);
+            self.set_len(0);
+            grown.set_len(len);
+        }
+
+        *self = grown;
+        Ok(())
+    }
+}
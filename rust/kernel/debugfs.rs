@@ -7,17 +7,23 @@
 //!
 //! Reference: <https://www.kernel.org/doc/html/next/filesystems/debugfs.html>
 
-use crate::error::{from_err_ptr, Result};
+use crate::error::{from_err_ptr, Error, Result};
 use crate::file;
+use crate::init::{InPlaceInit, PinInit};
+use crate::macros::pin_project;
+use crate::pin_init;
 use crate::prelude::*;
 use crate::str::CStr;
 use crate::sync::Arc;
 use crate::types::Mode;
+use crate::Opaque;
 use core::ffi::c_void;
+use core::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicU64, AtomicU8, AtomicUsize};
 
 pub type PinnedRegistration<T = ()> = Pin<Box<Registration<T>>>;
 
 /// A registration of a debugfs directory or file
+#[pin_project]
 pub struct Registration<T> {
     open_data: T,
     dentry: *mut bindings::dentry,
@@ -79,9 +85,337 @@ impl Registration<()> {
             _parent: parent,
         })
     }
+
+    /// Creates a read/write debugfs file that reads and writes directly through to `value`,
+    /// without needing an [`file::Operations`] impl.
+    ///
+    /// Unlike [`Registration::register_symlink`] and [`Registration::register_dir`], the
+    /// underlying `debugfs_create_*` helpers this is built on do not report failure, so the
+    /// returned dentry is simply untracked (`null`); the file is reclaimed when `parent` (or an
+    /// ancestor of it) is removed. `Result` is kept here for symmetry with the rest of this type's
+    /// constructors and in case a future kernel version starts reporting errors.
+    pub fn register_u8(
+        name: &'static CStr,
+        mode: Mode,
+        parent: Option<Arc<Registration<()>>>,
+        value: &'static AtomicU8,
+    ) -> Result<Registration<()>> {
+        let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
+        // SAFETY: name.as_char_ptr() is non-null and nul-terminated, parent_dentry is either
+        // null or a dentry owned by `parent`, and `value` outlives the file by virtue of being
+        // `'static`.
+        unsafe {
+            bindings::debugfs_create_u8(
+                name.as_char_ptr(),
+                mode.as_int(),
+                parent_dentry,
+                value.as_ptr(),
+            )
+        };
+
+        Ok(Self {
+            dentry: core::ptr::null_mut(),
+            open_data: (),
+            _parent: parent,
+        })
+    }
+
+    /// Like [`Registration::register_u8`], but for a 16-bit value.
+    pub fn register_u16(
+        name: &'static CStr,
+        mode: Mode,
+        parent: Option<Arc<Registration<()>>>,
+        value: &'static AtomicU16,
+    ) -> Result<Registration<()>> {
+        let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
+        // SAFETY: see `register_u8`.
+        unsafe {
+            bindings::debugfs_create_u16(
+                name.as_char_ptr(),
+                mode.as_int(),
+                parent_dentry,
+                value.as_ptr(),
+            )
+        };
+
+        Ok(Self {
+            dentry: core::ptr::null_mut(),
+            open_data: (),
+            _parent: parent,
+        })
+    }
+
+    /// Like [`Registration::register_u8`], but for a 32-bit value.
+    pub fn register_u32(
+        name: &'static CStr,
+        mode: Mode,
+        parent: Option<Arc<Registration<()>>>,
+        value: &'static AtomicU32,
+    ) -> Result<Registration<()>> {
+        let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
+        // SAFETY: see `register_u8`.
+        unsafe {
+            bindings::debugfs_create_u32(
+                name.as_char_ptr(),
+                mode.as_int(),
+                parent_dentry,
+                value.as_ptr(),
+            )
+        };
+
+        Ok(Self {
+            dentry: core::ptr::null_mut(),
+            open_data: (),
+            _parent: parent,
+        })
+    }
+
+    /// Like [`Registration::register_u8`], but for a 64-bit value.
+    pub fn register_u64(
+        name: &'static CStr,
+        mode: Mode,
+        parent: Option<Arc<Registration<()>>>,
+        value: &'static AtomicU64,
+    ) -> Result<Registration<()>> {
+        let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
+        // SAFETY: see `register_u8`.
+        unsafe {
+            bindings::debugfs_create_u64(
+                name.as_char_ptr(),
+                mode.as_int(),
+                parent_dentry,
+                value.as_ptr(),
+            )
+        };
+
+        Ok(Self {
+            dentry: core::ptr::null_mut(),
+            open_data: (),
+            _parent: parent,
+        })
+    }
+
+    /// Like [`Registration::register_u8`], but renders `value` in hexadecimal.
+    pub fn register_x8(
+        name: &'static CStr,
+        mode: Mode,
+        parent: Option<Arc<Registration<()>>>,
+        value: &'static AtomicU8,
+    ) -> Result<Registration<()>> {
+        let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
+        // SAFETY: see `register_u8`.
+        unsafe {
+            bindings::debugfs_create_x8(
+                name.as_char_ptr(),
+                mode.as_int(),
+                parent_dentry,
+                value.as_ptr(),
+            )
+        };
+
+        Ok(Self {
+            dentry: core::ptr::null_mut(),
+            open_data: (),
+            _parent: parent,
+        })
+    }
+
+    /// Like [`Registration::register_x8`], but for a 16-bit value.
+    pub fn register_x16(
+        name: &'static CStr,
+        mode: Mode,
+        parent: Option<Arc<Registration<()>>>,
+        value: &'static AtomicU16,
+    ) -> Result<Registration<()>> {
+        let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
+        // SAFETY: see `register_u8`.
+        unsafe {
+            bindings::debugfs_create_x16(
+                name.as_char_ptr(),
+                mode.as_int(),
+                parent_dentry,
+                value.as_ptr(),
+            )
+        };
+
+        Ok(Self {
+            dentry: core::ptr::null_mut(),
+            open_data: (),
+            _parent: parent,
+        })
+    }
+
+    /// Like [`Registration::register_x8`], but for a 32-bit value.
+    pub fn register_x32(
+        name: &'static CStr,
+        mode: Mode,
+        parent: Option<Arc<Registration<()>>>,
+        value: &'static AtomicU32,
+    ) -> Result<Registration<()>> {
+        let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
+        // SAFETY: see `register_u8`.
+        unsafe {
+            bindings::debugfs_create_x32(
+                name.as_char_ptr(),
+                mode.as_int(),
+                parent_dentry,
+                value.as_ptr(),
+            )
+        };
+
+        Ok(Self {
+            dentry: core::ptr::null_mut(),
+            open_data: (),
+            _parent: parent,
+        })
+    }
+
+    /// Like [`Registration::register_x8`], but for a 64-bit value.
+    pub fn register_x64(
+        name: &'static CStr,
+        mode: Mode,
+        parent: Option<Arc<Registration<()>>>,
+        value: &'static AtomicU64,
+    ) -> Result<Registration<()>> {
+        let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
+        // SAFETY: see `register_u8`.
+        unsafe {
+            bindings::debugfs_create_x64(
+                name.as_char_ptr(),
+                mode.as_int(),
+                parent_dentry,
+                value.as_ptr(),
+            )
+        };
+
+        Ok(Self {
+            dentry: core::ptr::null_mut(),
+            open_data: (),
+            _parent: parent,
+        })
+    }
+
+    /// Like [`Registration::register_u8`], but for a boolean value.
+    pub fn register_bool(
+        name: &'static CStr,
+        mode: Mode,
+        parent: Option<Arc<Registration<()>>>,
+        value: &'static AtomicBool,
+    ) -> Result<Registration<()>> {
+        let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
+        // SAFETY: see `register_u8`.
+        unsafe {
+            bindings::debugfs_create_bool(
+                name.as_char_ptr(),
+                mode.as_int(),
+                parent_dentry,
+                value.as_ptr(),
+            )
+        };
+
+        Ok(Self {
+            dentry: core::ptr::null_mut(),
+            open_data: (),
+            _parent: parent,
+        })
+    }
+
+    /// Like [`Registration::register_u8`], but for a `size_t` value.
+    pub fn register_size_t(
+        name: &'static CStr,
+        mode: Mode,
+        parent: Option<Arc<Registration<()>>>,
+        value: &'static AtomicUsize,
+    ) -> Result<Registration<()>> {
+        let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
+        // SAFETY: see `register_u8`.
+        unsafe {
+            bindings::debugfs_create_size_t(
+                name.as_char_ptr(),
+                mode.as_int(),
+                parent_dentry,
+                value.as_ptr(),
+            )
+        };
+
+        Ok(Self {
+            dentry: core::ptr::null_mut(),
+            open_data: (),
+            _parent: parent,
+        })
+    }
+
+    /// Like [`Registration::register_u8`], but for a kernel `atomic_t`.
+    pub fn register_atomic(
+        name: &'static CStr,
+        mode: Mode,
+        parent: Option<Arc<Registration<()>>>,
+        value: &'static Opaque<bindings::atomic_t>,
+    ) -> Result<Registration<()>> {
+        let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
+        // SAFETY: `value` points to a valid `atomic_t` that outlives the file by virtue of
+        // being `'static`.
+        unsafe {
+            bindings::debugfs_create_atomic_t(
+                name.as_char_ptr(),
+                mode.as_int(),
+                parent_dentry,
+                value.get(),
+            )
+        };
+
+        Ok(Self {
+            dentry: core::ptr::null_mut(),
+            open_data: (),
+            _parent: parent,
+        })
+    }
 }
 
 impl<T: Sync> Registration<T> {
+    /// Returns a pin-initializer for a debugfs file bound to `open_data`.
+    ///
+    /// `Self` is built directly in its final, pinned location, and `debugfs_create_file` is only
+    /// called once that address is stable, via [`PinInit::chain`]. If `debugfs_create_file`
+    /// fails, `chain` drops the partially constructed `Registration` for us before propagating
+    /// the error: there is no window where `dentry` is null and reachable, and no leak on
+    /// failure. [`Registration::register_file`] boxes this initializer; using it directly also
+    /// lets a `Registration<T>` be embedded inside a larger `pin_init!`-initialized struct.
+    pub fn new_file<U>(
+        name: &'static CStr,
+        mode: Mode,
+        open_data: T,
+        parent: Option<Arc<Registration<()>>>,
+    ) -> impl PinInit<Self, Error>
+    where
+        Self: file::OpenAdapter<T>,
+        U: file::Operations<OpenData = T>,
+    {
+        let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
+
+        pin_init!(Self {
+            dentry: core::ptr::null_mut(),
+            open_data,
+            _parent: parent,
+        })
+        // The fields above can only ever fail to initialize with `Infallible`; widen that to
+        // `Error` so it can be chained with the fallible `debugfs_create_file` call below.
+        .map_err(|e: core::convert::Infallible| match e {})
+        .chain(move |this: &mut Self| {
+            let fops = unsafe { file::OperationsVtable::<Self, U>::build() };
+            this.dentry = from_err_ptr(unsafe {
+                bindings::debugfs_create_file(
+                    name.as_char_ptr(),
+                    mode.as_int(),
+                    parent_dentry,
+                    this as *mut _ as *mut c_void,
+                    fops,
+                )
+            })?;
+            Ok(())
+        })
+    }
+
     pub fn register_file<U>(
         name: &'static CStr,
         mode: Mode,
@@ -92,23 +426,53 @@ impl<T: Sync> Registration<T> {
         Self: file::OpenAdapter<T>,
         U: file::Operations<OpenData = T>,
     {
-        let fops = unsafe { file::OperationsVtable::<Self, U>::build() };
+        Box::pin_init(Self::new_file::<U>(name, mode, open_data, parent))
+    }
+}
+
+/// Owns the `struct debugfs_blob_wrapper` backing a [`Registration::register_blob`] file.
+///
+/// The blob wrapper only ever points at the `&'static [u8]` passed in at registration time, so
+/// sharing it across threads is as safe as sharing that slice would be.
+pub struct BlobWrapper(bindings::debugfs_blob_wrapper);
+
+// SAFETY: `BlobWrapper` only holds a pointer/length pair into a `&'static [u8]`, which is safe
+// to access from any thread.
+unsafe impl Send for BlobWrapper {}
+// SAFETY: see above.
+unsafe impl Sync for BlobWrapper {}
+
+impl Registration<BlobWrapper> {
+    /// Creates a read-only debugfs file that exposes `data` as a raw byte blob, via
+    /// `debugfs_create_blob`. This is the path for dumping firmware images, register dumps, or
+    /// other binary data that cannot be expressed through the scalar `register_*` helpers.
+    ///
+    /// The backing [`BlobWrapper`] is boxed and pinned alongside the `Registration` itself, and
+    /// the dentry is created only once that box is in place and then stored into it, so the
+    /// wrapper is guaranteed to outlive the dentry.
+    pub fn register_blob(
+        name: &'static CStr,
+        parent: Option<Arc<Registration<()>>>,
+        data: &'static [u8],
+    ) -> Result<PinnedRegistration<BlobWrapper>> {
         let parent_dentry = parent.as_ref().map_or(core::ptr::null_mut(), |r| r.dentry);
 
         let mut registration = Pin::from(Box::try_new(Self {
             dentry: core::ptr::null_mut(),
-            open_data,
+            open_data: BlobWrapper(bindings::debugfs_blob_wrapper {
+                data: data.as_ptr() as *mut c_void,
+                size: data.len() as _,
+            }),
             _parent: parent,
         })?);
         // SAFETY: The function never moves `this` hence the call is safe.
         let this = unsafe { registration.as_mut().get_unchecked_mut() };
         this.dentry = from_err_ptr(unsafe {
-            bindings::debugfs_create_file(
+            bindings::debugfs_create_blob(
                 name.as_char_ptr(),
-                mode.as_int(),
+                0o444,
                 parent_dentry,
-                this as *mut _ as *mut c_void,
-                fops,
+                &mut this.open_data.0,
             )
         })?;
 
@@ -273,6 +637,141 @@ pub mod attr {
     }
 }
 
+/// Support for multi-line, structured debugfs files, backed by the kernel's `seq_file` and the
+/// `single_open`/`single_release` helpers (i.e. the same model as the C `DEFINE_SHOW_ATTRIBUTE`
+/// macro: one `show` call renders the whole file, rather than a full `seq_operations` iterator).
+pub mod seq {
+    use crate::error::{to_result, Error, Result};
+    use crate::file;
+    use crate::prelude::*;
+    use core::ffi::{c_int, c_void};
+    use core::fmt;
+
+    /// Implemented by types that can render their state into a [`SeqPrinter`] for display
+    /// through a debugfs seq_file.
+    pub trait SeqOperations: Sync {
+        /// Writes this type's content into `m`.
+        fn show(&self, m: &mut SeqPrinter<'_>) -> Result;
+    }
+
+    /// A thin wrapper around the kernel's `struct seq_file`, letting a [`SeqOperations::show`]
+    /// implementation use `write!`/`writeln!` to produce its output.
+    pub struct SeqPrinter<'a>(&'a mut bindings::seq_file);
+
+    impl fmt::Write for SeqPrinter<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            // SAFETY: `self.0` is a valid, currently-open seq_file for the duration of `show`.
+            let ret = unsafe { bindings::seq_write(self.0, s.as_ptr() as *const c_void, s.len()) };
+            if ret != 0 {
+                Err(fmt::Error)
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    pub extern "C" fn show_callback<S: SeqOperations>(
+        m: *mut bindings::seq_file,
+        _v: *mut c_void,
+    ) -> c_int {
+        // SAFETY: `open` stashes a `*const S` that outlives the file in `seq_file::private`.
+        let data: &S = unsafe { &*((*m).private as *const S) };
+        // SAFETY: `m` is a valid, non-null seq_file for the duration of this callback.
+        let mut printer = SeqPrinter(unsafe { &mut *m });
+        match data.show(&mut printer) {
+            Ok(()) => 0,
+            Err(e) => e.to_errno(),
+        }
+    }
+
+    /// Opens `file` as a single-shot seq_file rendering `data` via [`SeqOperations::show`].
+    ///
+    /// `data` must outlive `file`; callers pass in a pointer borrowed from their
+    /// [`Registration`]'s own `open_data`, which is kept alive for as long as the dentry is.
+    pub fn open<S: SeqOperations>(file: &file::File, data: *const S) -> Result {
+        let file_ptr = file.as_ptr();
+        // SAFETY: `file_ptr` is a newly-opened file and `data` outlives it, per this function's
+        // safety requirements.
+        to_result(unsafe {
+            bindings::single_open(file_ptr, Some(show_callback::<S>), data as *mut c_void)
+        })
+    }
+
+    pub fn release(file: &file::File) -> Result {
+        let file_ptr = file.as_ptr();
+        // SAFETY: `file_ptr` was opened through `open`, above.
+        to_result(unsafe { bindings::single_release((*file_ptr).f_inode, file_ptr) })
+    }
+
+    pub fn read(
+        file: &file::File,
+        writer: &mut impl crate::io_buffer::IoBufferWriter,
+        offset: u64,
+    ) -> Result<usize> {
+        let mut ppos = offset as bindings::loff_t;
+        let file_ptr = file.as_ptr();
+        let buf = writer.buffer().unwrap() as *mut i8;
+
+        // SAFETY: `file_ptr` was opened through `open`, above.
+        let ret = unsafe { bindings::seq_read(file_ptr, buf, writer.len(), &mut ppos) };
+
+        if ret < 0 {
+            Err(Error::from_errno(ret as i32))
+        } else {
+            Ok(ret as usize)
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! register_seq_file {
+    ($seq_type:ty) => {
+        impl $seq_type {
+            fn register_seq_file(
+                self: $crate::sync::Arc<Self>,
+                name: &'static $crate::str::CStr,
+                mode: $crate::types::Mode,
+                parent: ::core::option::Option<
+                    $crate::sync::Arc<$crate::debugfs::Registration<()>>,
+                >,
+            ) -> $crate::error::Result<$crate::debugfs::PinnedRegistration<$crate::sync::Arc<Self>>>
+            {
+                $crate::debugfs::Registration::<$crate::sync::Arc<Self>>::register_file::<Self>(
+                    name, mode, self, parent,
+                )
+            }
+        }
+
+        #[vtable]
+        impl $crate::file::Operations for $seq_type {
+            type OpenData = $crate::sync::Arc<Self>;
+            type Data = ();
+
+            fn open(
+                data: &Self::OpenData,
+                file: &$crate::file::File,
+            ) -> $crate::error::Result<Self::Data> {
+                $crate::debugfs::seq::open(file, &**data as *const Self)
+            }
+
+            fn release(_data: Self::Data, file: &$crate::file::File) {
+                let _ = $crate::debugfs::seq::release(file);
+            }
+
+            fn read(
+                _data: (),
+                file: &$crate::file::File,
+                writer: &mut impl $crate::io_buffer::IoBufferWriter,
+                offset: u64,
+            ) -> $crate::error::Result<usize> {
+                $crate::debugfs::seq::read(file, writer, offset)
+            }
+        }
+    };
+}
+
+pub use register_seq_file;
+
 #[macro_export]
 macro_rules! attribute {
     ($attribute_type:ty, $fmt:literal, $is_signed:literal, $getter:expr, $setter:expr) => {
@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! DMA-coherent memory allocation.
+//!
+//! C header: [`include/linux/dma-mapping.h`](../../../../include/linux/dma-mapping.h)
+
+use crate::{
+    bindings,
+    device::RawDevice,
+    error::{code::*, Result},
+    gfp_t,
+};
+
+/// A block of DMA-coherent memory shared between the CPU and a device.
+///
+/// Unlike a bounce buffer allocated with [`alloc::vec::Vec`], the memory
+/// backing a `CoherentBuffer` is safe to hand directly to hardware as a DMA
+/// target: callers that previously filled an [`usb::Urb`](crate::usb::Urb)
+/// from a `Vec<u8>` and copied the result out on completion can instead pass
+/// a borrowed slice of this buffer as the transfer and let the device write
+/// into it in place.
+///
+/// # Invariants
+///
+/// `cpu_addr` is non-null and valid for reads and writes of `size` bytes for
+/// the lifetime of the object. `dma_handle` is the bus address corresponding
+/// to `cpu_addr`, as returned by `dma_alloc_coherent`.
+pub struct CoherentBuffer {
+    dev: *mut bindings::device,
+    cpu_addr: *mut core::ffi::c_void,
+    dma_handle: bindings::dma_addr_t,
+    size: usize,
+}
+
+impl CoherentBuffer {
+    /// Allocates a new DMA-coherent buffer of `size` bytes for `dev`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ENOMEM`] if the allocation fails.
+    pub fn try_new(dev: &impl RawDevice, size: usize, flags: gfp_t) -> Result<Self> {
+        let mut dma_handle: bindings::dma_addr_t = 0;
+        // SAFETY: `dev.raw_device()` is valid for the duration of this call, and
+        // `dma_handle` is a valid pointer to write the bus address into.
+        let cpu_addr = unsafe {
+            bindings::dma_alloc_coherent(dev.raw_device(), size, &mut dma_handle, flags)
+        };
+        if cpu_addr.is_null() {
+            return Err(ENOMEM);
+        }
+        Ok(Self {
+            dev: dev.raw_device(),
+            cpu_addr,
+            dma_handle,
+            size,
+        })
+    }
+
+    /// Returns the bus address of the buffer, as seen by the device.
+    #[inline]
+    pub fn dma_handle(&self) -> bindings::dma_addr_t {
+        self.dma_handle
+    }
+
+    /// Returns the size of the buffer in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns whether the buffer is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Borrows the buffer contents as a byte slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: By the type invariants, `self.cpu_addr` is valid for reads of
+        // `self.size` bytes.
+        unsafe { core::slice::from_raw_parts(self.cpu_addr.cast(), self.size) }
+    }
+
+    /// Mutably borrows the buffer contents as a byte slice.
+    #[inline]
+    pub fn as_slice_mut(&mut self) -> &mut [u8] {
+        // SAFETY: By the type invariants, `self.cpu_addr` is valid for writes of
+        // `self.size` bytes.
+        unsafe { core::slice::from_raw_parts_mut(self.cpu_addr.cast(), self.size) }
+    }
+
+    /// Returns the raw CPU-side pointer to the buffer.
+    ///
+    /// The underlying allocation does not move for the lifetime of `self`, so
+    /// this pointer stays valid even while `self` is itself moved around,
+    /// e.g. into a lock.
+    #[inline]
+    pub fn as_mut_ptr(&self) -> *mut u8 {
+        self.cpu_addr.cast()
+    }
+}
+
+impl Drop for CoherentBuffer {
+    fn drop(&mut self) {
+        // SAFETY: `self.dev`, `self.cpu_addr` and `self.dma_handle` were all
+        // returned together by a previous, matching call to `dma_alloc_coherent`.
+        unsafe {
+            bindings::dma_free_coherent(self.dev, self.size, self.cpu_addr, self.dma_handle);
+        }
+    }
+}
+
+// SAFETY: `CoherentBuffer` only holds a pointer to DMA-coherent memory and the
+// device it was allocated from; both are safe to access from any thread.
+unsafe impl Send for CoherentBuffer {}
+
+// SAFETY: The buffer's contents are only accessed through `&self`/`&mut self`
+// borrows, which Rust already serializes.
+unsafe impl Sync for CoherentBuffer {}
@@ -5,16 +5,24 @@
 //! C header: [`include/linux/i2c.h`](../../../../include/linux/i2c.h)
 
 use crate::{
-    bindings,
+    acpi, bindings,
     device::Device,
     device_id::{self, RawDeviceId},
     driver,
-    error::{from_result, to_result, Result},
+    error::{
+        code::{EINVAL, EIO},
+        from_kernel_result, from_result, to_result, Error, Result,
+    },
     of,
     str::{BStr, CStr},
     types::ForeignOwnable,
     ThisModule,
 };
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+use core::num::NonZeroU64;
+
+pub mod adapter;
 
 /// An I2C device id.
 #[repr(transparent)]
@@ -69,14 +77,18 @@ impl<T: Driver> driver::RegistrationOps for Adapter<T> {
         if let Some(t) = T::OF_DEVICE_ID_TABLE {
             i2cdrv.driver.of_match_table = t.as_ptr();
         }
+        if let Some(t) = T::ACPI_DEVICE_ID_TABLE {
+            i2cdrv.driver.acpi_match_table = t.as_ptr();
+        }
 
         // SAFETY:
         //   - `pdrv` lives at least until the call to `platform_driver_unregister()` returns.
         //   - `name` pointer has static lifetime.
         //   - `module.0` lives at least as long as the module.
         //   - `probe()` and `remove()` are static functions.
-        //   - `of_match_table` is either a raw pointer with static lifetime,
-        //      as guaranteed by the [`device_id::IdTable`] type, or null.
+        //   - `of_match_table` and `acpi_match_table` are each either a raw
+        //     pointer with static lifetime, as guaranteed by the
+        //     [`device_id::IdTable`] type, or null.
         to_result(unsafe { bindings::i2c_register_driver(module.0, i2cdrv) })
     }
 
@@ -92,7 +104,9 @@ impl<T: Driver> Adapter<T> {
     extern "C" fn probe_callback(i2c: *mut bindings::i2c_client) -> core::ffi::c_int {
         from_result(|| {
             let mut client = unsafe { Client::from_ptr(i2c) };
-            let data = T::probe(&mut client)?;
+            // SAFETY: `i2c` is guaranteed to be a valid, non-null pointer.
+            let id_info = unsafe { Self::find_id_info(i2c) };
+            let data = T::probe(&mut client, id_info)?;
 
             // SAFETY: `i2c` is guaranteed to be a valid, non-null pointer.
             unsafe { bindings::i2c_set_clientdata(i2c, data.into_foreign() as _) };
@@ -100,6 +114,56 @@ impl<T: Driver> Adapter<T> {
         })
     }
 
+    /// Finds the `IdInfo` of the entry that matched `i2c`, preferring the
+    /// legacy `i2c_device_id` table and falling back to the OF match, then
+    /// the ACPI match.
+    ///
+    /// # Safety
+    ///
+    /// `i2c` must be a valid, non-null pointer.
+    unsafe fn find_id_info(i2c: *mut bindings::i2c_client) -> Option<&'static T::IdInfo> {
+        if let Some(t) = T::I2C_DEVICE_ID_TABLE {
+            // SAFETY: `i2c` is valid by the safety requirements of this
+            // function, and `t` points to a static table.
+            let id = unsafe { bindings::i2c_match_id(t.as_ptr(), i2c) };
+            if !id.is_null() {
+                // SAFETY: `id` points within the static table `t`.
+                return unsafe {
+                    NonZeroU64::new((*id).driver_data)
+                        .map(|o| &*(id.cast::<u8>().offset(o.get() as _).cast::<T::IdInfo>()))
+                };
+            }
+        }
+
+        if let Some(t) = T::OF_DEVICE_ID_TABLE {
+            // SAFETY: `i2c` is valid by the safety requirements of this
+            // function, so `&(*i2c).dev` is a valid `struct device` reference.
+            let id = unsafe { bindings::of_match_device(t.as_ptr(), &(*i2c).dev) };
+            if !id.is_null() {
+                // SAFETY: `id` points within the static table `t`.
+                return unsafe {
+                    NonZeroU64::new((*id).data as u64)
+                        .map(|o| &*(id.cast::<u8>().offset(o.get() as _).cast::<T::IdInfo>()))
+                };
+            }
+        }
+
+        if let Some(t) = T::ACPI_DEVICE_ID_TABLE {
+            // SAFETY: `i2c` is valid by the safety requirements of this
+            // function, so `&(*i2c).dev` is a valid `struct device` reference.
+            let id = unsafe { bindings::acpi_match_device(t.as_ptr(), &(*i2c).dev) };
+            if !id.is_null() {
+                // SAFETY: `id` points within the static table `t`.
+                return unsafe {
+                    NonZeroU64::new((*id).driver_data)
+                        .map(|o| &*(id.cast::<u8>().offset(o.get() as _).cast::<T::IdInfo>()))
+                };
+            }
+        }
+
+        None
+    }
+
     extern "C" fn remove_callback(i2c: *mut bindings::i2c_client) {
         // SAFETY: `i2c` is guaranteed to be a valid, non-null pointer
         let ptr = unsafe { bindings::i2c_get_clientdata(i2c) };
@@ -134,11 +198,17 @@ pub trait Driver {
     /// The table of OF device ids supported by the driver.
     const OF_DEVICE_ID_TABLE: Option<of::IdTable<Self::IdInfo>> = None;
 
+    /// The table of ACPI device ids supported by the driver.
+    const ACPI_DEVICE_ID_TABLE: Option<acpi::IdTable<Self::IdInfo>> = None;
+
     /// I2C driver probe.
     ///
-    /// Called when a new i2c client is added or discovered.
+    /// Called when a new i2c client is added or discovered. `id_info` holds
+    /// the `IdInfo` of the entry in [`Self::I2C_DEVICE_ID_TABLE`],
+    /// [`Self::OF_DEVICE_ID_TABLE`], or [`Self::ACPI_DEVICE_ID_TABLE`] that
+    /// matched this client, if any.
     /// Implementers should attempt to initialize the client here.
-    fn probe(client: &mut Client) -> Result<Self::Data>;
+    fn probe(client: &mut Client, id_info: Option<&Self::IdInfo>) -> Result<Self::Data>;
 
     /// I2C driver remove.
     ///
@@ -171,6 +241,322 @@ impl Client {
     pub fn raw_client(&self) -> *mut bindings::i2c_client {
         self.ptr
     }
+
+    /// Returns the address this client was detected at.
+    pub fn addr(&self) -> u16 {
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants.
+        unsafe { (*self.ptr).addr }
+    }
+
+    /// Returns the `I2C_CLIENT_*` flags this client was registered with.
+    pub fn flags(&self) -> u16 {
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants.
+        unsafe { (*self.ptr).flags }
+    }
+
+    /// The longest block transfer the SMBus protocol allows.
+    pub const SMBUS_BLOCK_MAX: usize = 32;
+
+    /// Reads a single byte from the device, without addressing a register.
+    ///
+    /// Wraps `i2c_smbus_read_byte`.
+    pub fn read_byte(&self) -> Result<u8> {
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants.
+        let ret = unsafe { bindings::i2c_smbus_read_byte(self.ptr) };
+        if ret < 0 {
+            return Err(Error::from_errno(ret));
+        }
+        Ok(ret as u8)
+    }
+
+    /// Writes a single byte to the device, without addressing a register.
+    ///
+    /// Wraps `i2c_smbus_write_byte`.
+    pub fn write_byte(&self, value: u8) -> Result {
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants.
+        to_result(unsafe { bindings::i2c_smbus_write_byte(self.ptr, value) })
+    }
+
+    /// Reads the byte stored in register `cmd`.
+    ///
+    /// Wraps `i2c_smbus_read_byte_data`.
+    pub fn read_byte_data(&self, cmd: u8) -> Result<u8> {
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants.
+        let ret = unsafe { bindings::i2c_smbus_read_byte_data(self.ptr, cmd) };
+        if ret < 0 {
+            return Err(Error::from_errno(ret));
+        }
+        Ok(ret as u8)
+    }
+
+    /// Writes `value` to register `cmd`.
+    ///
+    /// Wraps `i2c_smbus_write_byte_data`.
+    pub fn write_byte_data(&self, cmd: u8, value: u8) -> Result {
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants.
+        to_result(unsafe { bindings::i2c_smbus_write_byte_data(self.ptr, cmd, value) })
+    }
+
+    /// Reads a little-endian 16-bit word from register `cmd`.
+    ///
+    /// Wraps `i2c_smbus_read_word_data`.
+    pub fn read_word_data(&self, cmd: u8) -> Result<u16> {
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants.
+        let ret = unsafe { bindings::i2c_smbus_read_word_data(self.ptr, cmd) };
+        if ret < 0 {
+            return Err(Error::from_errno(ret));
+        }
+        Ok(ret as u16)
+    }
+
+    /// Writes little-endian 16-bit `value` to register `cmd`.
+    ///
+    /// Wraps `i2c_smbus_write_word_data`.
+    pub fn write_word_data(&self, cmd: u8, value: u16) -> Result {
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants.
+        to_result(unsafe { bindings::i2c_smbus_write_word_data(self.ptr, cmd, value) })
+    }
+
+    /// Reads an SMBus block from register `cmd` into `data`, returning the
+    /// number of bytes read.
+    ///
+    /// `data` must be at least [`Self::SMBUS_BLOCK_MAX`] bytes long, the
+    /// maximum size the protocol allows; the device may return fewer.
+    ///
+    /// Wraps `i2c_smbus_read_i2c_block_data`.
+    pub fn read_block_data(&self, cmd: u8, data: &mut [u8]) -> Result<usize> {
+        assert!(data.len() >= Self::SMBUS_BLOCK_MAX);
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants,
+        // and `data` is valid for writes of `SMBUS_BLOCK_MAX` bytes, which
+        // `i2c_smbus_read_i2c_block_data` never exceeds.
+        let ret = unsafe {
+            bindings::i2c_smbus_read_i2c_block_data(
+                self.ptr,
+                cmd,
+                Self::SMBUS_BLOCK_MAX as u8,
+                data.as_mut_ptr(),
+            )
+        };
+        if ret < 0 {
+            return Err(Error::from_errno(ret));
+        }
+        Ok(ret as usize)
+    }
+
+    /// Writes `data` to register `cmd` as a single SMBus block.
+    ///
+    /// `data` must be at most [`Self::SMBUS_BLOCK_MAX`] bytes long.
+    ///
+    /// Wraps `i2c_smbus_write_i2c_block_data`.
+    pub fn write_block_data(&self, cmd: u8, data: &[u8]) -> Result {
+        assert!(data.len() <= Self::SMBUS_BLOCK_MAX);
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants,
+        // and `data` is valid for reads of `data.len()` bytes.
+        to_result(unsafe {
+            bindings::i2c_smbus_write_i2c_block_data(self.ptr, cmd, data.len() as u8, data.as_ptr())
+        })
+    }
+
+    /// Performs a raw transfer of `msgs`, e.g. a write-then-read combined
+    /// transaction with a repeated start, which the SMBus helpers above
+    /// cannot express.
+    ///
+    /// Returns the number of messages completed.
+    ///
+    /// Wraps `i2c_transfer`.
+    pub fn transfer(&self, msgs: &mut [Msg<'_>]) -> Result<usize> {
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants.
+        let adapter = unsafe { (*self.ptr).adapter };
+        // SAFETY: `Msg` is `repr(transparent)` over `bindings::i2c_msg`, so
+        // the cast is layout-compatible, and `msgs` is valid for
+        // `msgs.len()` messages for the duration of this call, which does
+        // not outlive `msgs`.
+        let ret = unsafe {
+            bindings::i2c_transfer(adapter, msgs.as_mut_ptr().cast(), msgs.len() as i32)
+        };
+        if ret < 0 {
+            return Err(Error::from_errno(ret));
+        }
+        let completed = ret as usize;
+        if completed < msgs.len() {
+            return Err(EIO);
+        }
+        Ok(completed)
+    }
+
+    /// Writes `buf` to the device in a single transfer.
+    ///
+    /// Wraps `i2c_master_send`.
+    pub fn master_send(&self, buf: &[u8]) -> Result<usize> {
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants,
+        // and `buf` is valid for reads of `buf.len()` bytes.
+        let ret =
+            unsafe { bindings::i2c_master_send(self.ptr, buf.as_ptr().cast(), buf.len() as i32) };
+        if ret < 0 {
+            return Err(Error::from_errno(ret));
+        }
+        Ok(ret as usize)
+    }
+
+    /// Reads up to `buf.len()` bytes from the device in a single transfer,
+    /// returning the number of bytes actually read.
+    ///
+    /// Wraps `i2c_master_recv`.
+    pub fn master_recv(&self, buf: &mut [u8]) -> Result<usize> {
+        // SAFETY: `self.ptr` is non-null and valid by the type invariants,
+        // and `buf` is valid for writes of `buf.len()` bytes.
+        let ret = unsafe {
+            bindings::i2c_master_recv(self.ptr, buf.as_mut_ptr().cast(), buf.len() as i32)
+        };
+        if ret < 0 {
+            return Err(Error::from_errno(ret));
+        }
+        Ok(ret as usize)
+    }
+}
+
+/// A single message within a [`Client::transfer`] transaction, wrapping
+/// `struct i2c_msg`.
+#[repr(transparent)]
+pub struct Msg<'a> {
+    msg: bindings::i2c_msg,
+    _buf: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> Msg<'a> {
+    /// Builds a message that writes `buf` to `addr`.
+    pub fn write(addr: u16, buf: &'a mut [u8]) -> Self {
+        Self::new(addr, 0, buf)
+    }
+
+    /// Builds a message that reads `buf.len()` bytes from `addr` into
+    /// `buf`.
+    pub fn read(addr: u16, buf: &'a mut [u8]) -> Self {
+        Self::new(addr, bindings::I2C_M_RD as u16, buf)
+    }
+
+    fn new(addr: u16, flags: u16, buf: &'a mut [u8]) -> Self {
+        Self {
+            msg: bindings::i2c_msg {
+                addr,
+                flags,
+                len: buf.len() as u16,
+                buf: buf.as_mut_ptr(),
+                ..Default::default()
+            },
+            _buf: PhantomData,
+        }
+    }
+
+    /// Marks this message as addressing a 10-bit I2C address, setting
+    /// `I2C_M_TEN`.
+    pub fn with_ten_bit_addr(mut self) -> Self {
+        self.msg.flags |= bindings::I2C_M_TEN as u16;
+        self
+    }
+}
+
+/// An event delivered to a [`SlaveBackend`] while acting as an I2C slave
+/// device, corresponding to `enum i2c_slave_event`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SlaveEvent {
+    /// A master is about to write to this device.
+    WriteRequested,
+    /// A master wrote the byte in `val` to this device.
+    WriteReceived,
+    /// A master is about to read from this device; the handler must store
+    /// the byte to send in `val`.
+    ReadRequested,
+    /// A master has read the byte in `val`; the handler may update `val`
+    /// with the next byte to send.
+    ReadProcessed,
+    /// The bus transaction has ended.
+    Stop,
+}
+
+impl SlaveEvent {
+    fn from_raw(event: bindings::i2c_slave_event) -> Result<Self> {
+        match event {
+            bindings::i2c_slave_event_I2C_SLAVE_WRITE_REQUESTED => Ok(Self::WriteRequested),
+            bindings::i2c_slave_event_I2C_SLAVE_WRITE_RECEIVED => Ok(Self::WriteReceived),
+            bindings::i2c_slave_event_I2C_SLAVE_READ_REQUESTED => Ok(Self::ReadRequested),
+            bindings::i2c_slave_event_I2C_SLAVE_READ_PROCESSED => Ok(Self::ReadProcessed),
+            bindings::i2c_slave_event_I2C_SLAVE_STOP => Ok(Self::Stop),
+            _ => Err(EINVAL),
+        }
+    }
+}
+
+/// A backend implementing I2C slave-mode device emulation, e.g. an
+/// EEPROM-emulation style device.
+pub trait SlaveBackend: Sync {
+    /// Handles a single slave-mode `event`.
+    ///
+    /// For [`SlaveEvent::ReadRequested`] and [`SlaveEvent::ReadProcessed`],
+    /// the implementation must write the next byte to send into `val`; for
+    /// the other events, `val` holds the byte received from the master (if
+    /// any) and is otherwise ignored.
+    fn slave_event(&self, event: SlaveEvent, val: &mut u8) -> Result;
+}
+
+/// The registration of a [`SlaveBackend`] on a [`Client`].
+///
+/// # Invariants
+///
+/// `self.client` has been successfully passed to `i2c_slave_register` and
+/// has not yet been unregistered.
+pub struct SlaveRegistration<T: SlaveBackend> {
+    client: *mut bindings::i2c_client,
+    backend: Box<T>,
+}
+
+impl<T: SlaveBackend> SlaveRegistration<T> {
+    /// Registers `backend` as the slave-mode event handler for `client`.
+    pub fn new(client: &Client, backend: T) -> Result<Self> {
+        let backend = Box::try_new(backend)?;
+
+        // SAFETY: `client.ptr` is non-null and valid by `Client`'s type
+        // invariants, and `backend` outlives the registration below.
+        unsafe {
+            bindings::i2c_set_clientdata(client.ptr, &*backend as *const T as *mut core::ffi::c_void)
+        };
+
+        // SAFETY: `client.ptr` is non-null and valid by `Client`'s type
+        // invariants, and `callback` is a static function.
+        to_result(unsafe { bindings::i2c_slave_register(client.ptr, Some(Self::callback)) })?;
+
+        // INVARIANT: `i2c_slave_register` just succeeded above.
+        Ok(Self {
+            client: client.ptr,
+            backend,
+        })
+    }
+
+    unsafe extern "C" fn callback(
+        client: *mut bindings::i2c_client,
+        event: bindings::i2c_slave_event,
+        val: *mut u8,
+    ) -> core::ffi::c_int {
+        from_kernel_result! {
+            // SAFETY: `client` is always a valid pointer passed from the
+            // caller, and its client data was set to a `T` pointer in `new`.
+            let backend = unsafe { &*(bindings::i2c_get_clientdata(client) as *const T) };
+            let event = SlaveEvent::from_raw(event)?;
+            // SAFETY: `val` is always a valid pointer to a single byte
+            // passed from the caller.
+            let val = unsafe { &mut *val };
+            backend.slave_event(event, val)?;
+            Ok(0)
+        }
+    }
+}
+
+impl<T: SlaveBackend> Drop for SlaveRegistration<T> {
+    fn drop(&mut self) {
+        // SAFETY: by the type invariants, `self.client` was successfully
+        // passed to `i2c_slave_register` and has not yet been unregistered.
+        unsafe { bindings::i2c_slave_unregister(self.client) };
+    }
 }
 
 impl AsRef<Device> for Client {
@@ -194,7 +580,7 @@ impl AsRef<Device> for Client {
 /// impl i2c::Driver for MyDriver {
 ///     kernel::driver_i2c_id_table!(I2C_CLIENT_I2C_ID_TABLE);
 ///     // [...]
-/// #   fn probe(_client: &mut i2c::Client) -> Result {
+/// #   fn probe(_client: &mut i2c::Client, _id_info: Option<&()>) -> Result {
 /// #       Ok(())
 /// #   }
 /// }
@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! I2C bus controller (adapter/algorithm) support.
+//!
+//! This lets a Rust driver implement an I2C *master*, as opposed to
+//! [`super::Driver`] which implements an I2C *client*.
+
+use crate::{
+    bindings,
+    device::RawDevice,
+    error::{from_kernel_result, to_result, Result},
+    str::CStr,
+};
+use alloc::boxed::Box;
+use core::marker::PhantomPinned;
+
+use super::Msg;
+
+/// The low-level operations of an I2C bus master.
+///
+/// Implementers provide the bus-specific transfer logic; [`Registration`]
+/// takes care of wiring it up to `struct i2c_adapter`/`struct i2c_algorithm`.
+pub trait Algorithm {
+    /// Carries out the transfer of `msgs` over the bus, returning the number
+    /// of messages that were transferred successfully.
+    fn master_xfer(&self, msgs: &mut [Msg<'_>]) -> Result<usize>;
+
+    /// Returns the bitmask of `I2C_FUNC_*` features this bus master
+    /// supports.
+    fn functionality(&self) -> u32;
+}
+
+/// The registration of an I2C bus master (`struct i2c_adapter`).
+///
+/// # Invariants
+///
+/// `self.adapter` has been successfully passed to `i2c_add_adapter` (or
+/// `i2c_add_numbered_adapter`) and has not yet been unregistered.
+pub struct Registration<T: Algorithm> {
+    adapter: Box<bindings::i2c_adapter>,
+    algo: Box<bindings::i2c_algorithm>,
+    algo_data: *mut T,
+    _pin: PhantomPinned,
+}
+
+impl<T: Algorithm> Registration<T> {
+    /// Registers an I2C bus master.
+    ///
+    /// If `num` is `Some`, the adapter is registered with that fixed bus
+    /// number via `i2c_add_numbered_adapter`; otherwise the next free
+    /// number is allocated by `i2c_add_adapter`.
+    pub fn new(
+        name: &CStr,
+        parent: &impl RawDevice,
+        algorithm: T,
+        num: Option<i32>,
+    ) -> Result<Self> {
+        let mut algo = Box::try_new(bindings::i2c_algorithm::default())?;
+        algo.master_xfer = Some(Self::master_xfer_callback);
+        algo.functionality = Some(Self::functionality_callback);
+
+        // `algo_data` outlives `adapter` for as long as `Self` is alive; it
+        // is reclaimed in `Drop`.
+        let algo_data = Box::into_raw(Box::try_new(algorithm)?);
+
+        let mut adapter = Box::try_new(bindings::i2c_adapter::default())?;
+        for (dst, src) in adapter.name.iter_mut().zip(name.as_bytes_with_nul()) {
+            *dst = *src as _;
+        }
+        adapter.owner = core::ptr::null_mut();
+        adapter.algo = &*algo;
+        adapter.algo_data = algo_data.cast();
+        // SAFETY: `parent` is valid for as long as `self` is, which the
+        // caller must uphold by keeping it alive across this call.
+        adapter.dev.parent = parent.raw_device();
+
+        if let Some(nr) = num {
+            adapter.nr = nr;
+            // SAFETY: `adapter` was just allocated and fully initialized
+            // above.
+            to_result(unsafe { bindings::i2c_add_numbered_adapter(&mut *adapter) })?;
+        } else {
+            // SAFETY: `adapter` was just allocated and fully initialized
+            // above.
+            to_result(unsafe { bindings::i2c_add_adapter(&mut *adapter) })?;
+        }
+
+        // INVARIANT: the adapter was just successfully added above.
+        Ok(Self {
+            adapter,
+            algo,
+            algo_data,
+            _pin: PhantomPinned,
+        })
+    }
+
+    unsafe extern "C" fn master_xfer_callback(
+        adap: *mut bindings::i2c_adapter,
+        msgs: *mut bindings::i2c_msg,
+        num: core::ffi::c_int,
+    ) -> core::ffi::c_int {
+        from_kernel_result! {
+            // SAFETY: `adap` is always a valid pointer passed from the
+            // caller, and `algo_data` was set to a `T` pointer in `new`.
+            let algorithm = unsafe { &*((*adap).algo_data as *const T) };
+            // SAFETY: `msgs` is valid for `num` messages for the duration
+            // of this call, and `Msg` is `repr(transparent)` over
+            // `bindings::i2c_msg`.
+            let msgs = unsafe { core::slice::from_raw_parts_mut(msgs.cast::<Msg<'_>>(), num as usize) };
+            let completed = algorithm.master_xfer(msgs)?;
+            Ok(completed as core::ffi::c_int)
+        }
+    }
+
+    unsafe extern "C" fn functionality_callback(adap: *mut bindings::i2c_adapter) -> u32 {
+        // SAFETY: `adap` is always a valid pointer passed from the caller,
+        // and `algo_data` was set to a `T` pointer in `new`.
+        let algorithm = unsafe { &*((*adap).algo_data as *const T) };
+        algorithm.functionality()
+    }
+}
+
+impl<T: Algorithm> Drop for Registration<T> {
+    fn drop(&mut self) {
+        // SAFETY: by the type invariants, `self.adapter` was successfully
+        // passed to `i2c_add_adapter`/`i2c_add_numbered_adapter` and has not
+        // yet been unregistered.
+        unsafe { bindings::i2c_del_adapter(&mut *self.adapter) };
+        // SAFETY: `self.algo_data` was created by `Box::into_raw` in `new`
+        // and is not used by the C side anymore once the adapter above has
+        // been unregistered.
+        drop(unsafe { Box::from_raw(self.algo_data) });
+    }
+}
+
+// SAFETY: `Registration` only gives out access to `T` through `&T`, so it
+// can be shared between threads as long as `T` can; the i2c core serializes
+// calls into `master_xfer` with its own bus lock.
+unsafe impl<T: Algorithm + Sync> Sync for Registration<T> {}
+
+// SAFETY: the drop implementation above does not touch thread-local state,
+// so a `Registration<T>` can be dropped from any thread as long as `T` can.
+unsafe impl<T: Algorithm + Send> Send for Registration<T> {}
@@ -105,7 +105,7 @@
 
 use crate::{
     error::{self, Error},
-    sync::UniqueArc,
+    sync::{Arc, UniqueArc},
 };
 use alloc::boxed::Box;
 use core::{
@@ -534,6 +534,131 @@ macro_rules! init {
     }}
 }
 
+/// Constructs an in-place initializer for an array `[T; N]`, calling
+/// `$make_elem` for every index in order.
+///
+/// Unlike [`init!`], this never needs stack space for the whole array at
+/// once: each element is written straight into its final slot before the
+/// next one is even built, which is what makes it usable for arrays far
+/// larger than the stack, e.g.
+///
+/// ```ignore
+/// let data = Box::init(init_array!(1_000_000, |_i| kernel::init::zeroed()))?;
+/// ```
+///
+/// If `$make_elem` (or the [`Init`] it returns) fails at index `k`, every
+/// element `0..k` already written is dropped before the error is
+/// propagated, leaving the slot fully uninitialized, as required by
+/// [`Init`]'s safety contract.
+///
+/// See [`pin_init_array!`] for the pinned variant, e.g. for `[Mutex<T>; N]`.
+#[macro_export]
+macro_rules! init_array {
+    ($n:expr, $make_elem:expr) => {
+        $crate::init::init_array_from_fn::<_, { $n }, _, _, _>($make_elem)
+    };
+}
+
+/// Pinned variant of [`init_array!`], for elements that must be pinned,
+/// e.g. `[Mutex<T>; N]`.
+#[macro_export]
+macro_rules! pin_init_array {
+    ($n:expr, $make_elem:expr) => {
+        $crate::init::pin_init_array_from_fn::<_, { $n }, _, _, _>($make_elem)
+    };
+}
+
+/// Backing function for [`init_array!`]; see its docs for details.
+#[inline]
+pub fn init_array_from_fn<T, const N: usize, E, F, I>(mut make_elem: F) -> impl Init<[T; N], E>
+where
+    F: FnMut(usize) -> I,
+    I: Init<T, E>,
+{
+    struct ArrayDropGuard<T> {
+        slot: *mut T,
+        init_count: usize,
+    }
+
+    impl<T> Drop for ArrayDropGuard<T> {
+        fn drop(&mut self) {
+            for i in 0..self.init_count {
+                // SAFETY: the loop in the closure below only ever advances
+                // `init_count` past `i` after `self.slot.add(i)` has been
+                // initialized, and neither it nor this destructor visits the
+                // same index twice.
+                unsafe { ptr::drop_in_place(self.slot.add(i)) };
+            }
+        }
+    }
+
+    // SAFETY: the closure below either initializes every one of the `N`
+    // elements of `slot` and returns `Ok(())`, or stops at the first error,
+    // in which case `guard` drops every element initialized so far (on
+    // either the early `?`-return or an unwind out of `elem_init.__init`)
+    // before we return `Err`, leaving `slot` fully uninitialized either way.
+    unsafe {
+        init_from_closure(move |slot: *mut [T; N]| {
+            let slot: *mut T = slot.cast();
+            let mut guard = ArrayDropGuard { slot, init_count: 0 };
+            for i in 0..N {
+                let elem_init = make_elem(i);
+                // SAFETY: `slot` points at an array of `N` elements of `T`
+                // and `i < N`, so `slot.add(i)` is a valid, uninitialized
+                // `T`; the caller does not touch it again if `Err` is
+                // returned here, only deallocates, which `guard` accounts
+                // for.
+                unsafe { elem_init.__init(slot.add(i))? };
+                guard.init_count = i + 1;
+            }
+            core::mem::forget(guard);
+            Ok(())
+        })
+    }
+}
+
+/// Backing function for [`pin_init_array!`]; see its docs for details.
+#[inline]
+pub fn pin_init_array_from_fn<T, const N: usize, E, F, I>(
+    mut make_elem: F,
+) -> impl PinInit<[T; N], E>
+where
+    F: FnMut(usize) -> I,
+    I: PinInit<T, E>,
+{
+    struct ArrayDropGuard<T> {
+        slot: *mut T,
+        init_count: usize,
+    }
+
+    impl<T> Drop for ArrayDropGuard<T> {
+        fn drop(&mut self) {
+            for i in 0..self.init_count {
+                // SAFETY: see `init_array_from_fn`'s `ArrayDropGuard` above.
+                unsafe { ptr::drop_in_place(self.slot.add(i)) };
+            }
+        }
+    }
+
+    // SAFETY: see `init_array_from_fn` above; additionally, none of the
+    // elements are moved after being written, satisfying `PinInit`'s
+    // stronger contract.
+    unsafe {
+        pin_init_from_closure(move |slot: *mut [T; N]| {
+            let slot: *mut T = slot.cast();
+            let mut guard = ArrayDropGuard { slot, init_count: 0 };
+            for i in 0..N {
+                let elem_init = make_elem(i);
+                // SAFETY: see `init_array_from_fn` above.
+                unsafe { elem_init.__pinned_init(slot.add(i))? };
+                guard.init_count = i + 1;
+            }
+            core::mem::forget(guard);
+            Ok(())
+        })
+    }
+}
+
 /// A pinned initializer for `T`.
 ///
 /// To use this initializer, you will need a suitable memory location that can hold a `T`. This can
@@ -568,6 +693,111 @@ pub unsafe trait PinInit<T, E = Infallible>: Sized {
     /// deallocate.
     /// The slot will not move, i.e. it will be pinned.
     unsafe fn __pinned_init(self, slot: *mut T) -> Result<(), E>;
+
+    /// Runs `f` on the initialized value before handing it back, e.g. to
+    /// link it into a C list or call a `_init` hook that needs the value's
+    /// final address.
+    ///
+    /// If `f` returns `Err`, the value is dropped before the error is
+    /// propagated, so this keeps `__pinned_init`'s cleanup contract.
+    #[inline]
+    fn chain<F>(self, f: F) -> ChainPinInit<Self, F, T, E>
+    where
+        F: FnOnce(&mut T) -> Result<(), E>,
+    {
+        ChainPinInit(self, f, PhantomData)
+    }
+
+    /// Converts the error type of this initializer using `g`, so
+    /// initializers with different error types can be composed with `?`.
+    #[inline]
+    fn map_err<E2, G>(self, g: G) -> MapErrPinInit<Self, G, T, E, E2>
+    where
+        G: FnOnce(E) -> E2,
+    {
+        MapErrPinInit(self, g, PhantomData)
+    }
+}
+
+/// The initializer returned by [`PinInit::chain`].
+pub struct ChainPinInit<I, F, T: ?Sized, E>(I, F, Invariant<(T, E)>);
+
+unsafe impl<I, F, T, E> PinInit<T, E> for ChainPinInit<I, F, T, E>
+where
+    I: PinInit<T, E>,
+    F: FnOnce(&mut T) -> Result<(), E>,
+{
+    #[inline]
+    unsafe fn __pinned_init(self, slot: *mut T) -> Result<(), E> {
+        // SAFETY: `slot` meets `__pinned_init`'s contract, which we forward
+        // unchanged to the inner initializer.
+        unsafe { self.0.__pinned_init(slot)? };
+        // SAFETY: the inner initializer above just finished writing every
+        // field of `slot`.
+        match (self.1)(unsafe { &mut *slot }) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                // SAFETY: `slot` was fully initialized above and has not
+                // been dropped yet, so we must clean it up ourselves before
+                // reporting `f`'s error, per `__pinned_init`'s contract.
+                unsafe { ptr::drop_in_place(slot) };
+                Err(e)
+            }
+        }
+    }
+}
+
+unsafe impl<I, F, T, E> Init<T, E> for ChainPinInit<I, F, T, E>
+where
+    I: Init<T, E>,
+    F: FnOnce(&mut T) -> Result<(), E>,
+{
+    #[inline]
+    unsafe fn __init(self, slot: *mut T) -> Result<(), E> {
+        // SAFETY: identical reasoning to `__pinned_init` above; `Init`
+        // additionally allows `slot` to move afterwards, which `f` taking a
+        // plain `&mut T` does not prevent.
+        unsafe { self.0.__init(slot)? };
+        match (self.1)(unsafe { &mut *slot }) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                // SAFETY: see `__pinned_init` above.
+                unsafe { ptr::drop_in_place(slot) };
+                Err(e)
+            }
+        }
+    }
+}
+
+/// The initializer returned by [`PinInit::map_err`].
+pub struct MapErrPinInit<I, G, T: ?Sized, E, E2>(I, G, Invariant<(T, E, E2)>);
+
+unsafe impl<I, G, T, E, E2> PinInit<T, E2> for MapErrPinInit<I, G, T, E, E2>
+where
+    I: PinInit<T, E>,
+    G: FnOnce(E) -> E2,
+{
+    #[inline]
+    unsafe fn __pinned_init(self, slot: *mut T) -> Result<(), E2> {
+        let Self(init, g, _) = self;
+        // SAFETY: forwarded directly to the inner initializer, which
+        // upholds the same contract on `slot`; only the error value is
+        // transformed.
+        unsafe { init.__pinned_init(slot) }.map_err(g)
+    }
+}
+
+unsafe impl<I, G, T, E, E2> Init<T, E2> for MapErrPinInit<I, G, T, E, E2>
+where
+    I: Init<T, E>,
+    G: FnOnce(E) -> E2,
+{
+    #[inline]
+    unsafe fn __init(self, slot: *mut T) -> Result<(), E2> {
+        let Self(init, g, _) = self;
+        // SAFETY: see `__pinned_init` above.
+        unsafe { init.__init(slot) }.map_err(g)
+    }
 }
 
 /// An initializer for `T`.
@@ -709,28 +939,111 @@ pub unsafe trait PinnedDrop {
     fn __ensure_no_unsafe_op_in_drop(self: Pin<&mut Self>);
 }
 
+/// An allocation that can be written into in-place and then turned into its
+/// fully-initialized counterpart, e.g. `Box<MaybeUninit<T>>` for `Box<T>`.
+///
+/// This is the uninitialized half of [`InPlaceInit`]: factoring it out lets
+/// [`InPlaceInit::pin_init`]/[`InPlaceInit::init`] share one body across
+/// every smart pointer, instead of each one hand-rolling the same
+/// allocate/initialize/assume_init sequence.
+///
+/// # Safety
+///
+/// [`Self::assume_init`] may only be called once every byte of the slot
+/// returned by [`Self::as_mut_ptr`] has been initialized.
+pub unsafe trait InitUninit<T> {
+    /// The fully-initialized counterpart produced by [`Self::assume_init`].
+    type Initialized;
+
+    /// Returns a pointer to the (possibly uninitialized) slot.
+    fn as_mut_ptr(&mut self) -> *mut T;
+
+    /// # Safety
+    ///
+    /// Every byte of the slot returned by [`Self::as_mut_ptr`] must already
+    /// be initialized.
+    unsafe fn assume_init(self) -> Self::Initialized;
+}
+
+// SAFETY: `Box::as_mut_ptr`/`Box::assume_init` operate on exactly the slot
+// this trait describes.
+unsafe impl<T> InitUninit<T> for Box<MaybeUninit<T>> {
+    type Initialized = Box<T>;
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T {
+        Box::as_mut_ptr(self).cast()
+    }
+
+    #[inline]
+    unsafe fn assume_init(self) -> Self::Initialized {
+        // SAFETY: forwarded to our own safety contract.
+        unsafe { Box::assume_init(self) }
+    }
+}
+
+// SAFETY: see above.
+unsafe impl<T> InitUninit<T> for UniqueArc<MaybeUninit<T>> {
+    type Initialized = UniqueArc<T>;
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T {
+        UniqueArc::as_mut_ptr(self).cast()
+    }
+
+    #[inline]
+    unsafe fn assume_init(self) -> Self::Initialized {
+        // SAFETY: see above.
+        unsafe { UniqueArc::assume_init(self) }
+    }
+}
+
+/// The uninitialized allocation backing [`Arc<T>`]'s [`InPlaceInit`] impl.
+///
+/// `Arc<T>` has no uninitialized form of its own: a refcounted slot can only
+/// be shared once it is fully initialized, so the allocation stays a
+/// [`UniqueArc`] throughout, and is converted to an [`Arc<T>`] only by
+/// [`Self::assume_init`] — a partially-initialized slot is never reachable
+/// through anything but a unique, pinned-in-place reference.
+///
+/// [`Arc<T>`]: crate::sync::Arc
+pub struct ArcUninit<T>(UniqueArc<MaybeUninit<T>>);
+
+// SAFETY: delegates to `UniqueArc<MaybeUninit<T>>`'s impl above, and only
+// converts to `Arc<T>` once that impl's own contract has been met.
+unsafe impl<T> InitUninit<T> for ArcUninit<T> {
+    type Initialized = Arc<T>;
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.0.as_mut_ptr()
+    }
+
+    #[inline]
+    unsafe fn assume_init(self) -> Self::Initialized {
+        // SAFETY: forwarded to our own safety contract.
+        unsafe { self.0.assume_init() }.into()
+    }
+}
+
 /// Smart pointer that can initialize memory in-place.
 pub trait InPlaceInit<T>: Sized {
+    /// The uninitialized allocation used while running the initializer; see
+    /// [`InitUninit`].
+    type Uninit: InitUninit<T, Initialized = Self>;
+
+    /// Allocates a new, uninitialized instance of [`Self::Uninit`].
+    fn try_new_uninit() -> error::Result<Self::Uninit>;
+
     /// Use the given initializer to in-place initialize a `T`.
     ///
     /// If `T: !Unpin` it will not be able to move afterwards.
-    fn pin_init<E>(init: impl PinInit<T, E>) -> error::Result<Pin<Self>>
-    where
-        Error: From<E>;
-
-    /// Use the given initializer to in-place initialize a `T`.
-    fn init<E>(init: impl Init<T, E>) -> error::Result<Self>
-    where
-        Error: From<E>;
-}
-
-impl<T> InPlaceInit<T> for Box<T> {
     #[inline]
     fn pin_init<E>(init: impl PinInit<T, E>) -> error::Result<Pin<Self>>
     where
         Error: From<E>,
     {
-        let mut this = Box::try_new_uninit()?;
+        let mut this = Self::try_new_uninit()?;
         let slot = this.as_mut_ptr();
         // SAFETY: when init errors/panics, slot will get deallocated but not dropped,
         // slot is valid and will not be moved because of the `Pin::new_unchecked`
@@ -739,12 +1052,13 @@ impl<T> InPlaceInit<T> for Box<T> {
         Ok(unsafe { Pin::new_unchecked(this.assume_init()) })
     }
 
+    /// Use the given initializer to in-place initialize a `T`.
     #[inline]
     fn init<E>(init: impl Init<T, E>) -> error::Result<Self>
     where
         Error: From<E>,
     {
-        let mut this = Box::try_new_uninit()?;
+        let mut this = Self::try_new_uninit()?;
         let slot = this.as_mut_ptr();
         // SAFETY: when init errors/panics, slot will get deallocated but not dropped,
         // slot is valid
@@ -754,33 +1068,33 @@ impl<T> InPlaceInit<T> for Box<T> {
     }
 }
 
+impl<T> InPlaceInit<T> for Box<T> {
+    type Uninit = Box<MaybeUninit<T>>;
+
+    #[inline]
+    fn try_new_uninit() -> error::Result<Self::Uninit> {
+        Ok(Box::try_new_uninit()?)
+    }
+}
+
 impl<T> InPlaceInit<T> for UniqueArc<T> {
+    type Uninit = UniqueArc<MaybeUninit<T>>;
+
     #[inline]
-    fn pin_init<E>(init: impl PinInit<T, E>) -> error::Result<Pin<Self>>
-    where
-        Error: From<E>,
-    {
-        let mut this = UniqueArc::try_new_uninit()?;
-        let slot = this.as_mut_ptr();
-        // SAFETY: when init errors/panics, slot will get deallocated but not dropped,
-        // slot is valid and will not be moved because of the `Pin::new_unchecked`
-        unsafe { init.__pinned_init(slot)? };
-        // SAFETY: all fields have been initialized
-        Ok(unsafe { Pin::new_unchecked(this.assume_init()) })
+    fn try_new_uninit() -> error::Result<Self::Uninit> {
+        Ok(UniqueArc::try_new_uninit()?)
     }
+}
+
+/// Allows building an [`Arc<T>`](crate::sync::Arc) in-place via
+/// [`InPlaceInit::pin_init`]/[`InPlaceInit::init`], without first routing
+/// through a separate [`UniqueArc`] value at the call site.
+impl<T> InPlaceInit<T> for Arc<T> {
+    type Uninit = ArcUninit<T>;
 
     #[inline]
-    fn init<E>(init: impl Init<T, E>) -> error::Result<Self>
-    where
-        Error: From<E>,
-    {
-        let mut this = UniqueArc::try_new_uninit()?;
-        let slot = this.as_mut_ptr();
-        // SAFETY: when init errors/panics, slot will get deallocated but not dropped,
-        // slot is valid
-        unsafe { init.__init(slot)? };
-        // SAFETY: all fields have been initialized
-        Ok(unsafe { this.assume_init() })
+    fn try_new_uninit() -> error::Result<Self::Uninit> {
+        Ok(ArcUninit(UniqueArc::try_new_uninit()?))
     }
 }
 
@@ -851,6 +1165,10 @@ unsafe impl<T: ?Sized> Zeroable for PhantomData<T> {}
 unsafe impl<T: ?Sized> Zeroable for *mut T {}
 unsafe impl<T: ?Sized> Zeroable for *const T {}
 
+// `MaybeUninit<T>` does not need `T` to be `Zeroable`: it is valid for any
+// bit pattern, all-zeroes included, regardless of `T`.
+unsafe impl<T> Zeroable for MaybeUninit<T> {}
+
 macro_rules! impl_tuple_zeroable {
     ($(,)?) => {};
     ($first:ident, $($t:ident),* $(,)?) => {
@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! Support for reporting Rust self tests through KUnit.
+//!
+//! Ad-hoc test runners (like `samples/rust/rust_selftests.rs`'s old `do_tests!` macro) print
+//! pass/fail/error lines with `pr_info!` and tally them by hand, so their results are invisible to
+//! the kernel's test harness and CI tooling. [`kunit_tests!`] instead registers each listed
+//! function as a `struct kunit_case` in a `struct kunit_suite`, so failures are reported through
+//! KUnit's own assertion machinery and show up as machine-readable KTAP output consumable by
+//! `kunit.py` and kernel CI.
+//!
+//! C header: [`include/kunit/test.h`](../../../include/kunit/test.h)
+
+use crate::{bindings, c_str, str::CStr};
+
+/// The outcome of a test function registered with [`kunit_tests!`], once any `Result` has been
+/// unwrapped.
+///
+/// This mirrors the `Pass`/`Fail` distinction ad-hoc self test runners already make: a test can
+/// complete and report that its assertions held ([`Outcome::Pass`]) or didn't
+/// ([`Outcome::Fail`]), or it can be interrupted before reaching a verdict, which is reported
+/// through the surrounding `Result` instead, with the errno attached.
+pub enum Outcome {
+    /// The test's assertions all held.
+    Pass,
+    /// The test ran to completion but one or more assertions failed.
+    Fail,
+}
+
+/// Implemented by the success type of the `Result` returned by a function registered with
+/// [`kunit_tests!`].
+///
+/// A blanket `impl` exists for `()`, so a test may simply return `Result<()>`. Types with their
+/// own notion of pass/fail without erroring, such as the `TestSummary` enum used by
+/// `samples/rust/rust_selftests.rs`, can implement this directly instead.
+pub trait TestOutcome {
+    /// Reduces `self` to a plain pass/fail verdict.
+    fn into_outcome(self) -> Outcome;
+}
+
+impl TestOutcome for Outcome {
+    fn into_outcome(self) -> Outcome {
+        self
+    }
+}
+
+impl TestOutcome for () {
+    fn into_outcome(self) -> Outcome {
+        Outcome::Pass
+    }
+}
+
+/// Reports `test` as failed because the registered function returned an error, attaching the
+/// errno to the KTAP output.
+///
+/// # Safety
+///
+/// `test` must be a valid, non-null pointer to the `struct kunit` passed into the currently
+/// running `run_case` callback.
+pub unsafe fn fail_with_errno(test: *mut bindings::kunit, name: &CStr, errno: i32) {
+    // SAFETY: `test` is valid per the caller; the format string and its `%s`/`%d` arguments match.
+    unsafe {
+        bindings::kunit_do_failed_assertion(
+            test,
+            core::ptr::null(),
+            0,
+            bindings::kunit_assert_type_KUNIT_FAIL_ASSERTION,
+            core::ptr::null(),
+            None,
+            c_str!("%s returned error %d").as_char_ptr(),
+            name.as_char_ptr(),
+            errno,
+        );
+    }
+}
+
+/// Reports `test` as failed because the registered function completed but returned
+/// [`Outcome::Fail`].
+///
+/// # Safety
+///
+/// `test` must be a valid, non-null pointer to the `struct kunit` passed into the currently
+/// running `run_case` callback.
+pub unsafe fn fail_outcome(test: *mut bindings::kunit, name: &CStr) {
+    // SAFETY: `test` is valid per the caller; the format string and its `%s` argument match.
+    unsafe {
+        bindings::kunit_do_failed_assertion(
+            test,
+            core::ptr::null(),
+            0,
+            bindings::kunit_assert_type_KUNIT_FAIL_ASSERTION,
+            core::ptr::null(),
+            None,
+            c_str!("%s failed").as_char_ptr(),
+            name.as_char_ptr(),
+        );
+    }
+}
+
+/// Builds a `kunit_suite::name`-shaped buffer (a fixed-size, NUL-padded `char` array) from a
+/// `&CStr`, the same way [`crate::i2c::DeviceId::new`] fills in a fixed-size device ID name.
+const fn suite_name(name: &CStr) -> [core::ffi::c_char; 256] {
+    let bytes = name.as_bytes_with_nul();
+    assert!(bytes.len() <= 256);
+
+    let mut buf = [0; 256];
+    let mut i = 0;
+    while i < bytes.len() {
+        buf[i] = bytes[i] as _;
+        i += 1;
+    }
+    buf
+}
+
+#[doc(hidden)]
+pub mod private {
+    // Re-exported for `kunit_tests!`, which expands in the caller's crate and has no other way
+    // to name these without requiring every caller to `use` them individually.
+    pub use super::{fail_outcome, fail_with_errno, suite_name, Outcome, TestOutcome};
+    pub use crate::bindings;
+}
+
+/// Collects functions returning `Result<T>` (for any `T: TestOutcome`) into a KUnit suite.
+///
+/// Each listed function is wrapped in an `extern "C"` `run_case` trampoline and registered as a
+/// `struct kunit_case` in a `struct kunit_suite` named `$suite_name`. The suite is placed in the
+/// `.kunit_test_suites` linker section, the same way the C `kunit_test_suite()` macro does, so it
+/// is picked up by the kernel's KUnit runner without any further registration step.
+///
+/// # Examples
+///
+/// ```ignore
+/// fn test_addition() -> Result<()> {
+///     assert_eq!(2 + 2, 4);
+///     Ok(())
+/// }
+///
+/// kernel::kunit_tests!("rust_selftests", [test_addition]);
+/// ```
+#[macro_export]
+macro_rules! kunit_tests {
+    ($suite_name:literal, [$($test:ident),* $(,)?]) => {
+        const _: () = {
+            use $crate::kunit::private::{bindings, fail_outcome, fail_with_errno, TestOutcome};
+
+            $(
+                #[allow(non_snake_case)]
+                unsafe extern "C" fn $test(test: *mut bindings::kunit) {
+                    // `self::` names the caller's test function, not this trampoline, which
+                    // shadows it inside the block.
+                    match self::$test().map(TestOutcome::into_outcome) {
+                        Ok($crate::kunit::Outcome::Pass) => {}
+                        Ok($crate::kunit::Outcome::Fail) => {
+                            // SAFETY: `test` is the live `struct kunit` passed in by KUnit.
+                            unsafe { fail_outcome(test, $crate::c_str!(stringify!($test))) };
+                        }
+                        Err(e) => {
+                            // SAFETY: `test` is the live `struct kunit` passed in by KUnit.
+                            unsafe {
+                                fail_with_errno(
+                                    test,
+                                    $crate::c_str!(stringify!($test)),
+                                    e.to_errno(),
+                                )
+                            };
+                        }
+                    }
+                }
+            )*
+
+            const NUM_CASES: usize = $crate::kunit_tests!(@count $($test),*);
+
+            static mut TEST_CASES: [bindings::kunit_case; NUM_CASES + 1] = [
+                $(
+                    {
+                        // SAFETY: zero-initializing a `kunit_case` and then filling in `name` and
+                        // `run_case` matches how the rest of this crate builds opaque C structs
+                        // it only partially populates from Rust.
+                        let mut case: bindings::kunit_case = unsafe { core::mem::zeroed() };
+                        case.name = $crate::c_str!(stringify!($test)).as_char_ptr();
+                        case.run_case = Some($test);
+                        case
+                    },
+                )*
+                // SAFETY: a zeroed `kunit_case` (null `name`) is KUnit's own sentinel marking the
+                // end of the array.
+                unsafe { core::mem::zeroed() },
+            ];
+
+            static SUITE: bindings::kunit_suite = {
+                // SAFETY: the remaining fields (init/exit hooks, statistics, ...) are correctly
+                // left zeroed/unset for a suite with no setup/teardown.
+                let mut suite: bindings::kunit_suite = unsafe { core::mem::zeroed() };
+                suite.name = $crate::kunit::private::suite_name($crate::c_str!($suite_name));
+                // SAFETY: `TEST_CASES` is only ever read by KUnit after this suite is published
+                // through the `.kunit_test_suites` section below, and is never mutated again.
+                suite.test_cases = unsafe { TEST_CASES.as_mut_ptr() };
+                suite
+            };
+
+            #[used]
+            #[link_section = ".kunit_test_suites"]
+            static SUITE_PTR: &bindings::kunit_suite = &SUITE;
+        };
+    };
+    (@count) => { 0 };
+    (@count $head:ident $(, $tail:ident)*) => { 1 + $crate::kunit_tests!(@count $($tail),*) };
+}
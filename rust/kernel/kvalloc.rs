@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! `kvmalloc()`-backed allocation, for large buffers that may not fit in a physically contiguous
+//! run of pages once memory gets fragmented.
+//!
+//! [`KVAllocator`] lets [`Box`]/[`Vec`] route through the kernel's own `kvmalloc()`/`kvfree()`:
+//! `kvmalloc()` tries the slab allocator first (`kmalloc()` with `__GFP_NORETRY | __GFP_NOWARN`)
+//! and falls back to a `vmalloc()`-backed, virtually-but-not-physically-contiguous allocation if
+//! that fails, and `kvfree()` already knows which of the two backs a given pointer (via
+//! `is_vmalloc_addr()`), so callers on this side never need to track the backend themselves.
+//! Useful for firmware blobs and large ring buffers that would otherwise cause OOM churn once
+//! physical memory is fragmented.
+
+use alloc::alloc::{AllocError, Allocator, Layout};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ptr::NonNull;
+
+use crate::allocator::needs_aligned_alloc;
+use crate::bindings;
+
+/// Over-allocates `layout` through `kvmalloc()` and hands back a pointer aligned to
+/// `layout.align()`, stashing the real `kvmalloc()` pointer in the word immediately before it so
+/// [`aligned_kvfree`] can recover it later.
+///
+/// The same over-allocate-and-stash-pointer trick as `allocator::aligned_alloc`, just built on
+/// `kvmalloc()`/`kvfree()` instead of `krealloc()`/`kfree()`, since `kvfree()` needs the exact
+/// pointer `kvmalloc()` returned to tell a `vmalloc()` backend apart from a slab one.
+fn aligned_kvalloc(layout: Layout) -> *mut u8 {
+    let header = core::mem::size_of::<*mut u8>();
+    let total = layout.size() + layout.align() + header;
+
+    // SAFETY: calling C, `total` is non-zero since `layout.align()` is.
+    let raw = unsafe { bindings::kvmalloc(total, bindings::GFP_KERNEL) as *mut u8 };
+    if raw.is_null() {
+        return core::ptr::null_mut();
+    }
+
+    let aligned = (raw as usize + header).next_multiple_of(layout.align());
+
+    // SAFETY: `aligned - header` lies within the `total`-byte allocation just made, and is
+    // suitably aligned for a `*mut u8`.
+    unsafe { (aligned as *mut *mut u8).sub(1).write(raw) };
+
+    aligned as *mut u8
+}
+
+/// Frees a pointer returned by [`aligned_kvalloc`].
+///
+/// # Safety
+///
+/// `ptr` must have been returned by [`aligned_kvalloc`] and not yet freed.
+unsafe fn aligned_kvfree(ptr: *mut u8) {
+    // SAFETY: `ptr` was returned by `aligned_kvalloc`, which always leaves the original
+    // `kvmalloc()` pointer in the word right before it.
+    let raw = unsafe { (ptr as *mut *mut u8).sub(1).read() };
+    // SAFETY: `raw` is the `kvmalloc()` pointer `aligned_kvalloc` allocated `ptr` from.
+    unsafe { bindings::kvfree(raw as *const core::ffi::c_void) };
+}
+
+/// A growable buffer allocated through [`KVAllocator`].
+pub type KVVec<T> = Vec<T, KVAllocator>;
+
+/// A heap allocation made through [`KVAllocator`].
+pub type KVBox<T> = Box<T, KVAllocator>;
+
+/// An [`Allocator`] backed by `kvmalloc()`/`kvfree()` rather than `krealloc()`/`kfree()`.
+///
+/// Reach for this instead of the default allocator when the size isn't bounded by the caller
+/// (e.g. a ring buffer or firmware image sized at runtime) and a multi-page, physically
+/// contiguous `kmalloc()` allocation failing under fragmentation would otherwise be fatal.
+#[derive(Clone, Copy, Default)]
+pub struct KVAllocator;
+
+unsafe impl Allocator for KVAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            return Ok(NonNull::slice_from_raw_parts(layout.dangling(), 0));
+        }
+
+        if needs_aligned_alloc(layout) {
+            let mem = aligned_kvalloc(layout);
+            let mem = NonNull::new(mem).ok_or(AllocError)?;
+            return Ok(NonNull::slice_from_raw_parts(mem, layout.size()));
+        }
+
+        // SAFETY: calling C, `layout.size()` was just checked to be non-zero.
+        let mem = unsafe { bindings::kvmalloc(layout.size(), bindings::GFP_KERNEL) as *mut u8 };
+        let mem = NonNull::new(mem).ok_or(AllocError)?;
+        Ok(NonNull::slice_from_raw_parts(mem, layout.size()))
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() == 0 {
+            return;
+        }
+
+        if needs_aligned_alloc(layout) {
+            // SAFETY: `ptr` was returned by `allocate` above for this same `layout`, which
+            // routed through `aligned_kvalloc` since `needs_aligned_alloc(layout)` held.
+            unsafe { aligned_kvfree(ptr.as_ptr()) };
+            return;
+        }
+
+        // SAFETY: `ptr` was returned by `allocate` above, which always hands out memory from
+        // `kvmalloc()`; `kvfree()` is the matching free for whichever backend it chose.
+        unsafe { bindings::kvfree(ptr.as_ptr() as *const core::ffi::c_void) };
+    }
+}
@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! Ethernet hardware addresses.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::bindings;
+use crate::error::{Error, Result};
+
+/// A 6-byte Ethernet hardware address.
+///
+/// Used in place of the raw `*mut c_void`/`struct sockaddr` the core passes
+/// to `ndo_set_mac_address`, so drivers never have to reinterpret a pointer
+/// themselves.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MacAddress([u8; 6]);
+
+impl MacAddress {
+    /// Wraps a raw 6-byte address.
+    pub const fn new(bytes: [u8; 6]) -> Self {
+        Self(bytes)
+    }
+
+    /// The all-zero address.
+    pub const fn zero() -> Self {
+        Self([0; 6])
+    }
+
+    /// Reads the address out of `(*ptr).sa_data`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point at a valid `struct sockaddr`.
+    pub unsafe fn from_sockaddr(ptr: *const bindings::sockaddr) -> Self {
+        // SAFETY: guaranteed valid by the caller.
+        let sa_data = unsafe { (*ptr).sa_data };
+
+        let mut bytes = [0u8; 6];
+        for (b, d) in bytes.iter_mut().zip(sa_data.iter()) {
+            *b = *d as u8;
+        }
+
+        Self(bytes)
+    }
+
+    /// Builds a `struct sockaddr` carrying this address in `sa_data`.
+    ///
+    /// `sa_family` is left zeroed; callers that need a particular address
+    /// family must set it themselves.
+    pub fn to_sockaddr(self) -> bindings::sockaddr {
+        // SAFETY: `sockaddr` is a plain C struct; zero is a valid bit pattern.
+        let mut sa: bindings::sockaddr = unsafe { core::mem::zeroed() };
+
+        for (d, b) in sa.sa_data.iter_mut().zip(self.0.iter()) {
+            *d = *b as _;
+        }
+
+        sa
+    }
+
+    /// The raw 6 bytes, in network order.
+    pub const fn as_bytes(&self) -> &[u8; 6] {
+        &self.0
+    }
+
+    /// Whether this is the all-zero address.
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 6]
+    }
+
+    /// Whether this is a multicast address (the low bit of the first octet
+    /// is set).
+    pub fn is_multicast(&self) -> bool {
+        self.0[0] & 0x01 != 0
+    }
+
+    /// Whether this is the broadcast address (`ff:ff:ff:ff:ff:ff`).
+    pub fn is_broadcast(&self) -> bool {
+        self.0 == [0xff; 6]
+    }
+
+    /// Whether this address could be assigned to a device: not all-zero and
+    /// not multicast.
+    ///
+    /// Mirrors `is_valid_ether_addr`.
+    pub fn is_valid(&self) -> bool {
+        !self.is_multicast() && !self.is_zero()
+    }
+}
+
+impl fmt::Display for MacAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5]
+        )
+    }
+}
+
+impl FromStr for MacAddress {
+    type Err = Error;
+
+    /// Parses the `aa:bb:cc:dd:ee:ff` form (an `ether_aton` equivalent).
+    fn from_str(s: &str) -> Result<Self> {
+        let mut bytes = [0u8; 6];
+        let mut parts = s.split(':');
+
+        for b in bytes.iter_mut() {
+            let part = parts.next().ok_or(Error::EINVAL)?;
+            *b = u8::from_str_radix(part, 16).map_err(|_| Error::EINVAL)?;
+        }
+
+        if parts.next().is_some() {
+            return Err(Error::EINVAL);
+        }
+
+        Ok(Self(bytes))
+    }
+}
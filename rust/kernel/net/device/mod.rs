@@ -6,8 +6,18 @@
 /// Flags.
 mod flags;
 
+/// Ethernet hardware addresses.
+mod mac;
+
+/// NAPI integration.
+mod napi;
+
 #[doc(inline)]
 pub use flags::{Features, Flag, PrivFlag};
+#[doc(inline)]
+pub use mac::MacAddress;
+#[doc(inline)]
+pub use napi::{Napi, NapiPoll};
 
 use core::{marker, mem, ptr};
 
@@ -34,6 +44,17 @@ extern "C" {
 
     #[allow(improper_ctypes)]
     fn rust_helper_dev_lstats_add(dev: *mut bindings::net_device, len: u32);
+
+    #[allow(improper_ctypes)]
+    fn rust_helper_netdev_hw_addr_list_first(
+        list: *const bindings::netdev_hw_addr_list,
+    ) -> *const bindings::netdev_hw_addr;
+
+    #[allow(improper_ctypes)]
+    fn rust_helper_netdev_hw_addr_next(
+        list: *const bindings::netdev_hw_addr_list,
+        ha: *const bindings::netdev_hw_addr,
+    ) -> *const bindings::netdev_hw_addr;
 }
 
 /// interface name assignment types (sysfs name_assign_type attribute).
@@ -162,6 +183,34 @@ impl<T: NetDeviceAdapter> NetDevice<T> {
         unsafe { rust_helper_eth_hw_addr_random(self.ptr) };
     }
 
+    /// Sets the device's hardware address ahead of [`Self::register`]/
+    /// [`Self::register_locked`].
+    ///
+    /// Doing this before registering avoids the window in which userspace
+    /// could otherwise observe the interface appear with an all-zero
+    /// `dev_addr`. Rejects an all-zero `addr`; a driver with no fixed
+    /// address to assign should call [`Self::hw_addr_random`] instead, which
+    /// [`Self::register`] also falls back to if `dev_addr` is still unset.
+    pub fn set_mac_addr_pre_register(&mut self, addr: &MacAddress) -> Result {
+        if addr.is_zero() {
+            return Err(Error::EINVAL);
+        }
+
+        let dev = self.get_internal_mut();
+        dev.addr_len = 6;
+        dev.dev_addr[..6].copy_from_slice(addr.as_bytes());
+
+        Ok(())
+    }
+
+    /// Reads back the currently-set `dev_addr`.
+    fn current_mac_addr(&self) -> MacAddress {
+        let mut bytes = [0u8; 6];
+        bytes.copy_from_slice(&self.get_internal().dev_addr[..6]);
+
+        MacAddress::new(bytes)
+    }
+
     /// Register a network device.
     ///
     /// Take a completed network device structure and add it to the kernel
@@ -171,7 +220,16 @@ impl<T: NetDeviceAdapter> NetDevice<T> {
     /// This is a wrapper around register_netdevice that takes the rtnl semaphore
     /// and expands the device name if you passed a format string to
     /// alloc_netdev.
-    pub fn register(&self) -> Result {
+    ///
+    /// If neither [`Self::set_mac_addr_pre_register`] nor
+    /// [`Self::hw_addr_random`] has been called yet, a random address is
+    /// assigned automatically so the device is never published with a null
+    /// hardware address.
+    pub fn register(&mut self) -> Result {
+        if self.current_mac_addr().is_zero() {
+            self.hw_addr_random();
+        }
+
         // SAFETY: self.ptr is valid if self is valid.
         // FIXME: where is the lock hold?
         let err = unsafe { bindings::register_netdev(self.ptr) };
@@ -197,10 +255,19 @@ impl<T: NetDeviceAdapter> NetDevice<T> {
     /// The locking appears insufficient to guarantee two parallel registers
     /// will not get the same name.
     ///
+    /// If neither [`Self::set_mac_addr_pre_register`] nor
+    /// [`Self::hw_addr_random`] has been called yet, a random address is
+    /// assigned automatically so the device is never published with a null
+    /// hardware address.
+    ///
     /// # Safety
     ///
     /// caller must hold the [`RtnlLock`] and semaphore
-    pub unsafe fn register_locked(&self) -> Result {
+    pub unsafe fn register_locked(&mut self) -> Result {
+        if self.current_mac_addr().is_zero() {
+            self.hw_addr_random();
+        }
+
         let err = unsafe { bindings::register_netdevice(self.ptr) };
 
         if err != 0 {
@@ -315,6 +382,28 @@ impl<T: NetDeviceAdapter> NetDevice<T> {
         dev.max_mtu = max;
     }
 
+    /// Set the current MTU of the [`NetDevice`].
+    ///
+    /// Called from [`NetDeviceOps::change_mtu`] once the driver has
+    /// reconfigured its hardware for `new_mtu`. The core `dev_set_mtu` path
+    /// has already range-checked `new_mtu` against [`Self::set_mtu`]'s
+    /// `min`/`max` bounds before invoking the callback.
+    pub fn set_current_mtu(&mut self, new_mtu: u32) {
+        let mut dev = self.get_internal_mut();
+
+        dev.mtu = new_mtu;
+    }
+
+    /// Set the watchdog timeout, in jiffies.
+    ///
+    /// The core `dev_watchdog` timer fires [`NetDeviceOps::tx_timeout`] once a
+    /// TX queue has been stopped for longer than this.
+    pub fn set_watchdog_timeo(&mut self, jiffies: u64) {
+        let mut dev = self.get_internal_mut();
+
+        dev.watchdog_timeo = jiffies as _;
+    }
+
     /// Create a new `pcpu_lstats` struct and assing it to the [`NetDevice`].
     // This is more or less a workaround, as I did not find a way to create a pcpu marco
     // and assing some value to the anonymous union.
@@ -354,6 +443,230 @@ impl<T: NetDeviceAdapter> NetDevice<T> {
         }
     }
 
+    /// Resume transmission on the device's (single) TX queue.
+    ///
+    /// Wraps `netif_start_queue`. Typically called from [`NetDeviceOps::open`]
+    /// once the driver is ready to accept packets.
+    pub fn start_queue(&mut self) {
+        // SAFETY: self.ptr is valid if self is valid.
+        unsafe { bindings::netif_start_queue(self.ptr) }
+    }
+
+    /// Stop transmission on the device's (single) TX queue.
+    ///
+    /// Wraps `netif_stop_queue`. Typically called from
+    /// [`NetDeviceOps::start_xmit`] once the driver's TX ring is full, to
+    /// avoid returning [`NetdevTX::TX_BUSY`].
+    pub fn stop_queue(&mut self) {
+        // SAFETY: self.ptr is valid if self is valid.
+        unsafe { bindings::netif_stop_queue(self.ptr) }
+    }
+
+    /// Resume transmission on the device's (single) TX queue.
+    ///
+    /// Wraps `netif_wake_queue`. Typically called from the driver's TX
+    /// completion path once room has been freed in the TX ring.
+    pub fn wake_queue(&mut self) {
+        // SAFETY: self.ptr is valid if self is valid.
+        unsafe { bindings::netif_wake_queue(self.ptr) }
+    }
+
+    /// Test if the device's (single) TX queue is stopped.
+    ///
+    /// Wraps `netif_queue_stopped`.
+    pub fn queue_stopped(&self) -> bool {
+        // SAFETY: self.ptr is valid if self is valid.
+        unsafe { bindings::netif_queue_stopped(self.ptr) }
+    }
+
+    /// Stop transmission on TX sub-queue `idx`.
+    ///
+    /// Wraps `netif_stop_subqueue`. Typically called from the driver's
+    /// [`NetDeviceOps::select_queue`]-chosen [`NetDeviceOps::start_xmit`] path
+    /// once the corresponding ring is full, instead of stalling every queue.
+    pub fn stop_subqueue(&mut self, idx: u16) {
+        // SAFETY: self.ptr is valid if self is valid.
+        unsafe { bindings::netif_stop_subqueue(self.ptr, idx) }
+    }
+
+    /// Resume transmission on TX sub-queue `idx`.
+    ///
+    /// Wraps `netif_wake_subqueue`. Typically called from the driver's TX
+    /// completion path once room has been freed in the corresponding ring.
+    pub fn wake_subqueue(&mut self, idx: u16) {
+        // SAFETY: self.ptr is valid if self is valid.
+        unsafe { bindings::netif_wake_subqueue(self.ptr, idx) }
+    }
+
+    /// Test if TX sub-queue `idx` is stopped.
+    ///
+    /// Wraps `__netif_subqueue_stopped`.
+    pub fn subqueue_stopped(&self, idx: u16) -> bool {
+        // SAFETY: self.ptr is valid if self is valid.
+        unsafe { bindings::__netif_subqueue_stopped(self.ptr, idx) }
+    }
+
+    /// Sets the number of active TX sub-queues.
+    ///
+    /// Wraps `netif_set_real_num_tx_queues`. Must be no more than the `txqs`
+    /// count the device was allocated with.
+    pub fn set_real_num_tx_queues(&mut self, txq: u32) -> Result {
+        // SAFETY: self.ptr is valid if self is valid.
+        let ret = unsafe { bindings::netif_set_real_num_tx_queues(self.ptr, txq) };
+
+        if ret != 0 {
+            Err(Error::from_kernel_errno(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sets the number of active RX sub-queues.
+    ///
+    /// Wraps `netif_set_real_num_rx_queues`. Must be no more than the `rxqs`
+    /// count the device was allocated with.
+    pub fn set_real_num_rx_queues(&mut self, rxq: u32) -> Result {
+        // SAFETY: self.ptr is valid if self is valid.
+        let ret = unsafe { bindings::netif_set_real_num_rx_queues(self.ptr, rxq) };
+
+        if ret != 0 {
+            Err(Error::from_kernel_errno(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Requests that the core re-run feature resolution (`ndo_fix_features`
+    /// followed by `ndo_set_features` as needed) for this device.
+    ///
+    /// Wraps `netdev_update_features`. Typically called after a link event
+    /// changes what the hardware is able to offer.
+    pub fn trigger_features_update(&mut self) {
+        // SAFETY: self.ptr is valid if self is valid.
+        unsafe { bindings::netdev_update_features(self.ptr) }
+    }
+
+    /// Iterate over the device's multicast address list (`dev->mc`).
+    ///
+    /// Intended for use from [`NetDeviceOps::set_rx_mode`], to program the
+    /// hardware's multicast filter.
+    pub fn mc_addresses(&self) -> HwAddrIter<'_> {
+        let dev = self.get_internal();
+        let list: *const bindings::netdev_hw_addr_list = &dev.mc;
+
+        HwAddrIter {
+            list,
+            // SAFETY: `list` is valid for as long as `self` is.
+            cur: unsafe { rust_helper_netdev_hw_addr_list_first(list) },
+            _p: marker::PhantomData,
+        }
+    }
+
+    /// The number of entries in the device's multicast address list
+    /// (`dev->mc`).
+    pub fn mc_count(&self) -> u32 {
+        self.get_internal().mc.count
+    }
+
+    /// Iterate over the device's unicast address list (`dev->uc`).
+    ///
+    /// Intended for use from [`NetDeviceOps::set_rx_mode`], for drivers that
+    /// support unicast address filtering (see [`PrivFlag::UNICAST_FLT`]).
+    pub fn uc_addresses(&self) -> HwAddrIter<'_> {
+        let dev = self.get_internal();
+        let list: *const bindings::netdev_hw_addr_list = &dev.uc;
+
+        HwAddrIter {
+            list,
+            // SAFETY: `list` is valid for as long as `self` is.
+            cur: unsafe { rust_helper_netdev_hw_addr_list_first(list) },
+            _p: marker::PhantomData,
+        }
+    }
+
+    /// The number of entries in the device's unicast address list
+    /// (`dev->uc`).
+    pub fn uc_count(&self) -> u32 {
+        self.get_internal().uc.count
+    }
+
+    /// Adds `addr` as a secondary entry in the device's unicast address
+    /// filter table.
+    ///
+    /// Wraps `dev_uc_add`. Intended for drivers with multiple hardware
+    /// filter slots, to build up a filter table from inside
+    /// [`NetDeviceOps::set_rx_mode`]. Drivers that advertise this support
+    /// should set [`PrivFlag::UNICAST_FLT`] (see [`Self::set_unicast_filtering`]).
+    pub fn add_uc_addr(&mut self, addr: &MacAddress) -> Result {
+        // SAFETY: self.ptr is valid if self is valid, and `addr` points at
+        // 6 valid bytes for the duration of the call.
+        let ret = unsafe { bindings::dev_uc_add(self.ptr, addr.as_bytes().as_ptr()) };
+
+        if ret != 0 {
+            Err(Error::from_kernel_errno(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Removes `addr` from the device's unicast address filter table.
+    ///
+    /// Wraps `dev_uc_del`.
+    pub fn del_uc_addr(&mut self, addr: &MacAddress) -> Result {
+        // SAFETY: self.ptr is valid if self is valid, and `addr` points at
+        // 6 valid bytes for the duration of the call.
+        let ret = unsafe { bindings::dev_uc_del(self.ptr, addr.as_bytes().as_ptr()) };
+
+        if ret != 0 {
+            Err(Error::from_kernel_errno(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Advertises (or withdraws) hardware unicast address filtering by
+    /// setting or clearing [`PrivFlag::UNICAST_FLT`] in `priv_flags`.
+    pub fn set_unicast_filtering(&mut self, enabled: bool) {
+        if enabled {
+            self.add_private_flag(PrivFlag::UNICAST_FLT);
+        } else {
+            self.remove_private_flag(PrivFlag::UNICAST_FLT);
+        }
+    }
+
+    /// Tests whether the device is currently in promiscuous mode (`IFF_PROMISC`).
+    pub fn is_promiscuous(&self) -> bool {
+        let dev = self.get_internal();
+
+        dev.flags & Flag::PROMISC as u32 != 0
+    }
+
+    /// Tests whether the device is currently receiving all multicast traffic
+    /// (`IFF_ALLMULTI`).
+    pub fn is_allmulti(&self) -> bool {
+        let dev = self.get_internal();
+
+        dev.flags & Flag::ALLMULTI as u32 != 0
+    }
+
+    /// Adjusts the device's promiscuity reference count by `inc`, enabling
+    /// or disabling `IFF_PROMISC` as the count crosses zero.
+    ///
+    /// Wraps `dev_set_promiscuity`.
+    pub fn set_promiscuity(&mut self, inc: i32) {
+        // SAFETY: self.ptr is valid if self is valid.
+        unsafe { bindings::dev_set_promiscuity(self.ptr, inc) }
+    }
+
+    /// Adjusts the device's all-multicast reference count by `inc`, enabling
+    /// or disabling `IFF_ALLMULTI` as the count crosses zero.
+    ///
+    /// Wraps `dev_set_allmulti`.
+    pub fn set_allmulti(&mut self, inc: i32) {
+        // SAFETY: self.ptr is valid if self is valid.
+        unsafe { bindings::dev_set_allmulti(self.ptr, inc) }
+    }
+
     /// Set carrier.
     pub fn carrier_set(&mut self, status: bool) {
         // SAFETY: self.ptr is valid if self is valid.
@@ -377,6 +690,36 @@ impl<T: NetDeviceAdapter> NetDevice<T> {
     }
 }
 
+/// An iterator over the hardware addresses of a [`NetDevice::mc_addresses`]
+/// or [`NetDevice::uc_addresses`] list.
+pub struct HwAddrIter<'a> {
+    list: *const bindings::netdev_hw_addr_list,
+    cur: *const bindings::netdev_hw_addr,
+    _p: marker::PhantomData<&'a ()>,
+}
+
+impl Iterator for HwAddrIter<'_> {
+    type Item = MacAddress;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cur.is_null() {
+            return None;
+        }
+
+        // SAFETY: `self.cur` is non-null, so by the invariant established
+        // where it was last assigned, it points at a valid `netdev_hw_addr`
+        // belonging to `self.list`.
+        let mut addr = [0u8; 6];
+        addr.copy_from_slice(unsafe { &(*self.cur).addr[..6] });
+
+        // SAFETY: `self.list` and `self.cur` are both valid; this returns
+        // the next entry, or null once the list is exhausted.
+        self.cur = unsafe { rust_helper_netdev_hw_addr_next(self.list, self.cur) };
+
+        Some(MacAddress::new(addr))
+    }
+}
+
 unsafe impl<T: NetDeviceAdapter> Sync for NetDevice<T> {}
 
 impl<I: NetDeviceAdapter> SavedAsPointer for NetDevice<I> {
@@ -423,6 +766,40 @@ pub trait NetDeviceAdapter: Sized {
     fn setup(dev: &mut NetDevice<Self>);
 }
 
+/// A [`NetDeviceAdapter`] for a [`NetDevice`] observed through a channel that
+/// isn't specific to any one driver, such as [`super::notifier::NetDeviceNotifier`].
+///
+/// Never used to register a device; only to view one.
+pub struct Unknown;
+
+#[doc(hidden)]
+pub struct UnknownOps;
+
+impl NetDeviceOps<Unknown> for UnknownOps {
+    declare_net_device_ops!();
+
+    fn init(_dev: &mut NetDevice<Unknown>) -> Result {
+        Ok(())
+    }
+
+    fn uninit(_dev: &mut NetDevice<Unknown>) {}
+}
+
+#[doc(hidden)]
+pub struct UnknownEthOps;
+
+impl EthToolOps<Unknown> for UnknownEthOps {
+    declare_eth_tool_ops!();
+}
+
+impl NetDeviceAdapter for Unknown {
+    type Inner = Self;
+    type Ops = UnknownOps;
+    type EthOps = UnknownEthOps;
+
+    fn setup(_dev: &mut NetDevice<Self>) {}
+}
+
 #[repr(i32)]
 #[allow(non_camel_case_types)]
 /// Maps to [`bindings::netdev_tx`] from the kernel.
@@ -449,6 +826,28 @@ unsafe extern "C" fn ndo_uninit_callback<T: NetDeviceAdapter>(dev: *mut bindings
     T::Ops::uninit(unsafe { &mut NetDevice::<T>::from_pointer_mut(dev) });
 }
 
+unsafe extern "C" fn ndo_open_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+) -> c_types::c_int {
+    from_kernel_result! {
+        T::Ops::open(
+            unsafe { &mut NetDevice::<T>::from_pointer_mut(dev) }
+        )?;
+        Ok(0)
+    }
+}
+
+unsafe extern "C" fn ndo_stop_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+) -> c_types::c_int {
+    from_kernel_result! {
+        T::Ops::stop(
+            unsafe { &mut NetDevice::<T>::from_pointer_mut(dev) }
+        )?;
+        Ok(0)
+    }
+}
+
 unsafe extern "C" fn ndo_start_xmit_callback<T: NetDeviceAdapter>(
     skb: *mut bindings::sk_buff,
     dev: *mut bindings::net_device,
@@ -486,7 +885,8 @@ unsafe extern "C" fn ndo_validate_addr_callback<T: NetDeviceAdapter>(
 ) -> c_types::c_int {
     from_kernel_result! {
         T::Ops::validate_addr(
-            unsafe { &mut NetDevice::<T>::from_pointer_mut(dev) }
+            unsafe { &mut NetDevice::<T>::from_pointer_mut(dev) },
+            None,
         )?;
         Ok(0)
     }
@@ -497,10 +897,17 @@ unsafe extern "C" fn ndo_set_mac_address_callback<T: NetDeviceAdapter>(
     p: *mut c_types::c_void,
 ) -> c_types::c_int {
     from_kernel_result! {
-        T::Ops::set_mac_addr(
-            unsafe { &mut NetDevice::<T>::from_pointer_mut(dev) },
-            p
-        )?;
+        let mut dev = unsafe { NetDevice::<T>::from_pointer_mut(dev) };
+        let sa = p.cast::<bindings::sockaddr>();
+
+        // SAFETY: `p` is a valid `struct sockaddr *` for the duration of
+        // this call, as guaranteed by the core before invoking
+        // `ndo_set_mac_address`.
+        T::Ops::validate_addr(&mut dev, Some(unsafe { &*sa }))?;
+        // SAFETY: same as above.
+        let addr = unsafe { MacAddress::from_sockaddr(sa) };
+
+        T::Ops::set_mac_addr(&mut dev, &addr)?;
         Ok(0)
     }
 }
@@ -509,17 +916,88 @@ unsafe extern "C" fn ndo_set_rx_mode_callback<T: NetDeviceAdapter>(dev: *mut bin
     T::Ops::set_rx_mode(unsafe { &mut NetDevice::<T>::from_pointer_mut(dev) })
 }
 
+unsafe extern "C" fn ndo_change_mtu_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    new_mtu: c_types::c_int,
+) -> c_types::c_int {
+    from_kernel_result! {
+        T::Ops::change_mtu(
+            unsafe { &mut NetDevice::<T>::from_pointer_mut(dev) },
+            new_mtu
+        )?;
+        Ok(0)
+    }
+}
+
+unsafe extern "C" fn ndo_fix_features_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    features: bindings::netdev_features_t,
+) -> bindings::netdev_features_t {
+    // `Features::try_from` never actually fails; the `Result` is only there
+    // to share the conversion with other, genuinely fallible, call sites.
+    let requested = Features::try_from(features).unwrap();
+
+    T::Ops::fix_features(unsafe { &mut NetDevice::<T>::from_pointer_mut(dev) }, requested).into()
+}
+
+unsafe extern "C" fn ndo_set_features_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    features: bindings::netdev_features_t,
+) -> c_types::c_int {
+    from_kernel_result! {
+        let features = Features::try_from(features)?;
+        T::Ops::set_features(
+            unsafe { &mut NetDevice::<T>::from_pointer_mut(dev) },
+            features
+        )?;
+        Ok(0)
+    }
+}
+
+unsafe extern "C" fn ndo_tx_timeout_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    txqueue: u32,
+) {
+    T::Ops::tx_timeout(
+        unsafe { &mut NetDevice::<T>::from_pointer_mut(dev) },
+        txqueue,
+    )
+}
+
+unsafe extern "C" fn ndo_select_queue_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    skb: *mut bindings::sk_buff,
+    _sb_dev: *mut bindings::net_device,
+) -> u16 {
+    T::Ops::select_queue(
+        unsafe { &mut NetDevice::<T>::from_pointer_mut(dev) },
+        &unsafe { SkBuff::from_pointer(skb) },
+    )
+}
+
 pub(crate) struct NetDeviceOperationsVtable<T: NetDeviceAdapter>(marker::PhantomData<T>);
 
 impl<T: NetDeviceAdapter> NetDeviceOperationsVtable<T> {
     const VTABLE: bindings::net_device_ops = bindings::net_device_ops {
         ndo_init: Some(ndo_init_callback::<T>),
         ndo_uninit: Some(ndo_uninit_callback::<T>),
-        ndo_open: None,
-        ndo_stop: None,
+        ndo_open: if T::Ops::TO_USE.open {
+            Some(ndo_open_callback::<T>)
+        } else {
+            None
+        },
+        ndo_stop: if T::Ops::TO_USE.stop {
+            Some(ndo_stop_callback::<T>)
+        } else {
+            None
+        },
         ndo_start_xmit: Some(ndo_start_xmit_callback::<T>),
         ndo_features_check: None,
-        ndo_select_queue: None,
+        ndo_select_queue: if T::Ops::TO_USE.select_queue {
+            Some(ndo_select_queue_callback::<T>)
+        } else {
+            None
+        },
         ndo_change_rx_flags: None,
         ndo_set_rx_mode: if T::Ops::TO_USE.set_rx_mode {
             Some(ndo_set_rx_mode_callback::<T>)
@@ -538,9 +1016,17 @@ impl<T: NetDeviceAdapter> NetDeviceOperationsVtable<T> {
         },
         ndo_do_ioctl: None,
         ndo_set_config: None,
-        ndo_change_mtu: None,
+        ndo_change_mtu: if T::Ops::TO_USE.change_mtu {
+            Some(ndo_change_mtu_callback::<T>)
+        } else {
+            None
+        },
         ndo_neigh_setup: None,
-        ndo_tx_timeout: None,
+        ndo_tx_timeout: if T::Ops::TO_USE.tx_timeout {
+            Some(ndo_tx_timeout_callback::<T>)
+        } else {
+            None
+        },
         ndo_get_stats64: if T::Ops::TO_USE.get_stats64 {
             Some(ndo_get_stats64_callback::<T>)
         } else {
@@ -597,8 +1083,16 @@ impl<T: NetDeviceAdapter> NetDeviceOperationsVtable<T> {
         ndo_del_slave: None,
         ndo_get_xmit_slave: None,
         ndo_sk_get_lower_dev: None,
-        ndo_fix_features: None,
-        ndo_set_features: None,
+        ndo_fix_features: if T::Ops::TO_USE.fix_features {
+            Some(ndo_fix_features_callback::<T>)
+        } else {
+            None
+        },
+        ndo_set_features: if T::Ops::TO_USE.set_features {
+            Some(ndo_set_features_callback::<T>)
+        } else {
+            None
+        },
         ndo_neigh_construct: None,
         ndo_neigh_destroy: None,
         ndo_fdb_add: None,
@@ -644,6 +1138,12 @@ impl<T: NetDeviceAdapter> NetDeviceOperationsVtable<T> {
 
 /// Represents which fields of [`struct net_device_ops`] should pe populated with pointers for the trait [`NetDeviceOps`].
 pub struct ToUse {
+    /// Trait defines a `ndo_open` function.
+    pub open: bool,
+
+    /// Trait defines a `ndo_stop` function.
+    pub stop: bool,
+
     /// Trait defines a `ndo_change_carrier` function.
     pub change_carrier: bool,
 
@@ -658,16 +1158,38 @@ pub struct ToUse {
 
     /// Trait defines a `ndo_set_rx_mode` function.
     pub set_rx_mode: bool,
+
+    /// Trait defines a `ndo_change_mtu` function.
+    pub change_mtu: bool,
+
+    /// Trait defines a `ndo_tx_timeout` function.
+    pub tx_timeout: bool,
+
+    /// Trait defines a `ndo_fix_features` function.
+    pub fix_features: bool,
+
+    /// Trait defines a `ndo_set_features` function.
+    pub set_features: bool,
+
+    /// Trait defines a `ndo_select_queue` function.
+    pub select_queue: bool,
 }
 
 /// This trait does not include any functions exept [`init`] and [`uninit`].
 #[doc(hidden)]
 pub const USE_NONE: ToUse = ToUse {
+    open: false,
+    stop: false,
     change_carrier: false,
     get_stats64: false,
     validate_addr: false,
     set_mac_addr: false,
     set_rx_mode: false,
+    change_mtu: false,
+    tx_timeout: false,
+    fix_features: false,
+    set_features: false,
+    select_queue: false,
 };
 
 /// Defines the [`NetDeviceOps::TO_USE`] field based on a list of fields to be populated.
@@ -703,6 +1225,21 @@ pub trait NetDeviceOps<T: NetDeviceAdapter>: Send + Sync + Sized {
     /// fails. It is not called if init fails.
     fn uninit(dev: &mut NetDevice<T>);
 
+    /// Called when the network device transitions from administratively
+    /// down to up (e.g. `ip link set up`). Drivers typically use this to
+    /// bring up the hardware and call [`NetDevice::start_queue`].
+    #[allow(unused_variables)]
+    fn open(dev: &mut NetDevice<T>) -> Result {
+        Err(Error::EINVAL)
+    }
+
+    /// Called when the network device transitions from administratively
+    /// up to down. Drivers typically use this to quiesce the hardware.
+    #[allow(unused_variables)]
+    fn stop(dev: &mut NetDevice<T>) -> Result {
+        Err(Error::EINVAL)
+    }
+
     /// Called when a packet needs to be transmitted.
     /// `Ok(())` returns NETDEV_TX_OK, Error maps to `NETDEV_TX_BUSY`
     /// Returns NETDEV_TX_OK.  Can return NETDEV_TX_BUSY, but you should stop
@@ -734,17 +1271,26 @@ pub trait NetDeviceOps<T: NetDeviceAdapter>: Send + Sync + Sized {
         Err(Error::EINVAL)
     }
 
-    /// Test if Media Access Control address is valid for the device.
+    /// Test if a Media Access Control address is valid for the device.
+    ///
+    /// `addr` is `None` to validate the currently-installed `dev_addr`, or
+    /// `Some` with a candidate address the core is about to hand to
+    /// [`Self::set_mac_addr`]. Validating the candidate here lets the core
+    /// reject it up front with `EADDRNOTAVAIL`, so implementations of
+    /// `set_mac_addr` no longer need to re-check it themselves.
     #[allow(unused_variables)]
-    fn validate_addr(dev: &mut NetDevice<T>) -> Result {
+    fn validate_addr(dev: &mut NetDevice<T>, addr: Option<&bindings::sockaddr>) -> Result {
         Err(Error::EINVAL)
     }
 
-    /// This function  is called when the Media Access Control address
+    /// This function is called when the Media Access Control address
     /// needs to be changed. If this interface is not defined, the
     /// MAC address can not be changed.
+    ///
+    /// The core has already run `addr` through [`Self::validate_addr`]
+    /// before calling this, so implementations don't need to re-validate it.
     #[allow(unused_variables)]
-    fn set_mac_addr(dev: &mut NetDevice<T>, p: *mut c_types::c_void) -> Result {
+    fn set_mac_addr(dev: &mut NetDevice<T>, addr: &MacAddress) -> Result {
         Err(Error::EINVAL)
     }
 
@@ -753,16 +1299,72 @@ pub trait NetDeviceOps<T: NetDeviceAdapter>: Send + Sync + Sized {
     /// IFF_UNICAST_FLT in its priv_flags.
     #[allow(unused_variables)]
     fn set_rx_mode(dev: &mut NetDevice<T>) {}
+
+    /// Called to change the MTU of the device.
+    ///
+    /// The core has already validated `new_mtu` against the `min_mtu`/`max_mtu`
+    /// bounds set via [`NetDevice::set_mtu`], so the implementation only
+    /// needs to reconfigure hardware for the new size (e.g. ring buffers,
+    /// jumbo-frame enable) and then call [`NetDevice::set_current_mtu`].
+    #[allow(unused_variables)]
+    fn change_mtu(dev: &mut NetDevice<T>, new_mtu: i32) -> Result {
+        Err(Error::EINVAL)
+    }
+
+    /// Called by the core `dev_watchdog` timer when transmission on
+    /// `txqueue` has been stalled for longer than the device's
+    /// [`NetDevice::set_watchdog_timeo`]. The typical implementation
+    /// records the event, resets the adapter, and calls
+    /// [`NetDevice::wake_queue`] to resume transmission.
+    #[allow(unused_variables)]
+    fn tx_timeout(dev: &mut NetDevice<T>, txqueue: u32) {}
+
+    /// Called by `netdev_fix_features`/`__netdev_update_features` to clamp a
+    /// proposed feature set to what the hardware can actually support, e.g.
+    /// disabling TSO when checksum offload is off.
+    #[allow(unused_variables)]
+    fn fix_features(dev: &mut NetDevice<T>, features: Features) -> Features {
+        features
+    }
+
+    /// Called by `__netdev_update_features` to program a resolved feature
+    /// set into hardware, after [`Self::fix_features`] has clamped it.
+    #[allow(unused_variables)]
+    fn set_features(dev: &mut NetDevice<T>, features: Features) -> Result {
+        Err(Error::EINVAL)
+    }
+
+    /// Picks which TX sub-queue `skb` should be transmitted on.
+    ///
+    /// The returned index is clamped by the core to `real_num_tx_queues`, so
+    /// an RSS-style driver can hash `skb` onto one of its queues without
+    /// worrying about the device's queue count changing underneath it.
+    #[allow(unused_variables)]
+    fn select_queue(dev: &mut NetDevice<T>, skb: &SkBuff) -> u16 {
+        0
+    }
 }
 
 /// Helper functions for NetDevices.
 pub mod helpers {
     use super::*;
 
-    /// Validate the eth addres for the [`NetDevice`] `dev`.
-    pub fn eth_validate_addr<T: NetDeviceAdapter>(dev: &mut NetDevice<T>) -> Result {
-        // SAFETY: Calling a C function.
-        let ret = unsafe { bindings::eth_validate_addr(dev.get_pointer_mut()) };
+    /// Validate the eth address for the [`NetDevice`] `dev`.
+    ///
+    /// `addr` is `None` to validate `dev`'s currently-installed `dev_addr`,
+    /// or `Some` to validate a candidate address instead.
+    pub fn eth_validate_addr<T: NetDeviceAdapter>(
+        dev: &mut NetDevice<T>,
+        addr: Option<&bindings::sockaddr>,
+    ) -> Result {
+        let sa = match addr {
+            Some(sa) => sa as *const bindings::sockaddr,
+            None => ptr::null(),
+        };
+
+        // SAFETY: Calling a C function. `sa` is either a valid `sockaddr`
+        // pointer or null, both of which `eth_validate_addr` accepts.
+        let ret = unsafe { bindings::eth_validate_addr(dev.get_pointer_mut(), sa) };
         if ret != 0 {
             Err(Error::from_kernel_errno(ret))
         } else {
@@ -774,16 +1376,17 @@ pub mod helpers {
     ///
     /// This doesn't change hardware matching, so needs to be overridden
     /// for most real devices.
-    ///
-    /// # Safety
-    ///
-    /// `socket_addr` has to be a valid socket address pointer.
-    pub unsafe fn eth_mac_addr<T: NetDeviceAdapter>(
-        dev: &mut NetDevice<T>,
-        socket_addr: *mut c_types::c_void,
-    ) -> Result {
-        // SAFETY: Calling a C function .
-        let ret = unsafe { bindings::eth_mac_addr(dev.get_pointer_mut(), socket_addr) };
+    pub fn eth_mac_addr<T: NetDeviceAdapter>(dev: &mut NetDevice<T>, addr: &MacAddress) -> Result {
+        let mut sa = addr.to_sockaddr();
+
+        // SAFETY: Calling a C function. `&mut sa` is a valid `sockaddr` for
+        // the duration of the call.
+        let ret = unsafe {
+            bindings::eth_mac_addr(
+                dev.get_pointer_mut(),
+                (&mut sa as *mut bindings::sockaddr).cast::<c_types::c_void>(),
+            )
+        };
 
         if ret != 0 {
             Err(Error::from_kernel_errno(ret))
@@ -791,4 +1394,45 @@ pub mod helpers {
             Ok(())
         }
     }
+
+    /// Builds and transmits a gratuitous ARP announcement for `dev`.
+    ///
+    /// Broadcasts an ARP request whose sender hardware address is `dev`'s
+    /// current `dev_addr`, and whose sender/target protocol addresses are
+    /// both `ip`, so neighbors refresh their ARP caches immediately after
+    /// the device's MAC address changes. Drivers typically call this from
+    /// the tail of their [`NetDeviceOps::set_mac_addr`] implementation.
+    ///
+    /// Wraps `arp_send`.
+    pub fn send_gratuitous_arp<T: NetDeviceAdapter>(
+        dev: &mut NetDevice<T>,
+        ip: core::net::Ipv4Addr,
+    ) -> Result {
+        let hw_addr = dev.current_mac_addr();
+        if hw_addr.is_zero() {
+            return Err(Error::EINVAL);
+        }
+
+        let be_ip = u32::from_ne_bytes(ip.octets());
+
+        // SAFETY: `dev.get_pointer_mut()` is valid if `dev` is valid, and
+        // `hw_addr` points at 6 valid bytes for the duration of the call.
+        // Passing null for `dest_hw`/`target_hw` makes `arp_send` announce
+        // to the broadcast address without caring about the target's own
+        // hardware address.
+        unsafe {
+            bindings::arp_send(
+                bindings::ARPOP_REQUEST as i32,
+                bindings::ETH_P_ARP as i32,
+                be_ip,
+                dev.get_pointer_mut(),
+                be_ip,
+                ptr::null(),
+                hw_addr.as_bytes().as_ptr(),
+                ptr::null(),
+            );
+        }
+
+        Ok(())
+    }
 }
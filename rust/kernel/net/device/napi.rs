@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! NAPI (softirq-driven RX polling) integration for [`NetDevice`].
+//!
+//! C header: [`include/linux/netdevice.h`](../../../../../include/linux/netdevice.h)
+
+use core::marker;
+
+use crate::bindings;
+
+use super::{NetDevice, NetDeviceAdapter};
+
+/// A NAPI instance attached to a [`NetDevice`].
+///
+/// Created by [`NetDevice::add_napi`]. Dropping the instance unregisters it
+/// via `netif_napi_del`, tying its lifetime to however long the driver keeps
+/// it around (typically for as long as the owning [`NetDevice`] is
+/// registered).
+pub struct Napi<T: NetDeviceAdapter> {
+    napi: bindings::napi_struct,
+    _p: marker::PhantomData<T>,
+}
+
+/// The poll routine of a [`Napi`] instance.
+///
+/// You implement this trait for the type driving the NAPI polling of a
+/// [`NetDeviceAdapter`] `T`.
+pub trait NapiPoll<T: NetDeviceAdapter> {
+    /// Called by the core NAPI loop to retrieve at most `budget` packets.
+    ///
+    /// Must return the number of packets actually retrieved. [`Napi::complete_done`]
+    /// must only be called with the returned value, and only when it is
+    /// strictly less than `budget`; a poll routine that consumes its whole
+    /// budget must leave the instance scheduled so the core calls it again.
+    fn poll(dev: &mut NetDevice<T>, budget: i32) -> i32;
+}
+
+unsafe extern "C" fn napi_poll_callback<T: NetDeviceAdapter, P: NapiPoll<T>>(
+    napi: *mut bindings::napi_struct,
+    budget: i32,
+) -> i32 {
+    // SAFETY: `napi` was registered against this device's `T` by
+    // `NetDevice::add_napi`, so `napi->dev` is the owning `net_device`.
+    let dev_ptr = unsafe { (*napi).dev };
+
+    // SAFETY: `dev_ptr` is non-null and valid for as long as `napi` can be
+    // polled on it.
+    let mut dev = unsafe { NetDevice::<T>::from_pointer_mut(dev_ptr) };
+
+    P::poll(&mut dev, budget)
+}
+
+impl<T: NetDeviceAdapter> NetDevice<T> {
+    /// Creates and registers a [`Napi`] instance for this device, dispatching
+    /// polling to `P`.
+    ///
+    /// Wraps `netif_napi_add`. The returned [`Napi`] must not be moved after
+    /// this call; drivers typically store it inside their private data.
+    pub fn add_napi<P: NapiPoll<T>>(&mut self, weight: i32) -> Napi<T> {
+        let mut napi = Napi {
+            napi: bindings::napi_struct::default(),
+            _p: marker::PhantomData,
+        };
+
+        // SAFETY: `self.ptr` is valid if `self` is valid, and `napi.napi` is
+        // a freshly zero-initialised `napi_struct` that outlives this call.
+        unsafe {
+            bindings::netif_napi_add(
+                self.ptr,
+                &mut napi.napi,
+                Some(napi_poll_callback::<T, P>),
+                weight,
+            );
+        }
+
+        napi
+    }
+}
+
+impl<T: NetDeviceAdapter> Napi<T> {
+    /// Schedules this NAPI instance to run on the current CPU.
+    ///
+    /// Wraps `napi_schedule`.
+    pub fn schedule(&mut self) {
+        // SAFETY: `self.napi` was registered by `netif_napi_add` and is
+        // valid for as long as `self` is.
+        unsafe { bindings::napi_schedule(&mut self.napi) };
+    }
+
+    /// Checks whether this NAPI instance is ready to be scheduled, without
+    /// actually scheduling it.
+    ///
+    /// Returns `false` if the instance is already scheduled or disabled.
+    ///
+    /// Wraps `napi_schedule_prep`.
+    pub fn schedule_prep(&mut self) -> bool {
+        // SAFETY: `self.napi` was registered by `netif_napi_add` and is
+        // valid for as long as `self` is.
+        unsafe { bindings::napi_schedule_prep(&mut self.napi) }
+    }
+
+    /// Marks polling as done for now, with `work_done` packets retrieved.
+    ///
+    /// Must only be called from [`NapiPoll::poll`], and only when
+    /// `work_done` is strictly less than the budget it was given; the core
+    /// uses this to decide whether the instance needs immediate
+    /// rescheduling.
+    ///
+    /// Wraps `napi_complete_done`.
+    pub fn complete_done(&mut self, work_done: i32) -> bool {
+        // SAFETY: `self.napi` was registered by `netif_napi_add` and is
+        // valid for as long as `self` is.
+        unsafe { bindings::napi_complete_done(&mut self.napi, work_done) }
+    }
+}
+
+impl<T: NetDeviceAdapter> Drop for Napi<T> {
+    fn drop(&mut self) {
+        // SAFETY: `self.napi` was registered by `netif_napi_add` exactly
+        // once, and is torn down here exactly once.
+        unsafe { bindings::netif_napi_del(&mut self.napi) };
+    }
+}
@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: GPL-2.0-only
+
+//! A minimal DNS resolver built on top of [`udp::UdpSocket`](super::udp::UdpSocket).
+//!
+//! Only what is needed to turn a hostname into A/AAAA records is implemented:
+//! a single question is sent and the answer section is scanned for address
+//! records. There is no caching, retry, or support for other record types.
+
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4};
+use core::net::IpAddr;
+
+use alloc::vec::Vec;
+
+use crate::error::{code::*, Result};
+use crate::net::udp::UdpSocket;
+
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+const CLASS_IN: u16 = 1;
+
+/// Resolves `name` against the DNS server at `server`, returning every A and
+/// AAAA address found in the reply.
+///
+/// # Errors
+///
+/// Returns an error if the query could not be sent, no reply was received, or
+/// the reply could not be parsed.
+pub fn resolve(name: &str, server: SocketAddrV4) -> Result<Vec<IpAddr>> {
+    let mut sock = UdpSocket::try_new()?;
+    let query = build_query(name)?;
+    sock.send_to(&query, server)?;
+
+    let mut buf = [0u8; 512];
+    let (len, _from) = sock.recv_from(&mut buf)?;
+    parse_answers(&buf[..len], query[0], query[1])
+}
+
+fn build_query(name: &str) -> Result<Vec<u8>> {
+    if name.is_empty() || !name.is_ascii() {
+        return Err(EINVAL);
+    }
+
+    let mut pkt = Vec::new();
+
+    // Header: id, flags (standard query, recursion desired), 1 question.
+    try_extend(&mut pkt, &[
+        0x13, 0x37, // id
+        0x01, 0x00, // flags: standard query, recursion desired
+        0x00, 0x01, // qdcount
+        0x00, 0x00, // ancount
+        0x00, 0x00, // nscount
+        0x00, 0x00, // arcount
+    ])?;
+
+    // Question: name encoded as length-prefixed labels, then QTYPE/QCLASS.
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(EINVAL);
+        }
+        pkt.try_push(label.len() as u8).map_err(|_| ENOMEM)?;
+        try_extend(&mut pkt, label.as_bytes())?;
+    }
+    pkt.try_push(0).map_err(|_| ENOMEM)?;
+    try_extend(&mut pkt, &TYPE_A.to_be_bytes())?;
+    try_extend(&mut pkt, &CLASS_IN.to_be_bytes())?;
+
+    Ok(pkt)
+}
+
+/// Appends `bytes` to `v`, reporting allocation failure instead of aborting.
+fn try_extend(v: &mut Vec<u8>, bytes: &[u8]) -> Result {
+    for &b in bytes {
+        v.try_push(b).map_err(|_| ENOMEM)?;
+    }
+    Ok(())
+}
+
+/// Parses the answer section of a reply whose id matches `id_hi`/`id_lo`.
+fn parse_answers(reply: &[u8], id_hi: u8, id_lo: u8) -> Result<Vec<IpAddr>> {
+    if reply.len() < 12 || reply[0] != id_hi || reply[1] != id_lo {
+        return Err(EINVAL);
+    }
+
+    let qdcount = u16::from_be_bytes([reply[4], reply[5]]) as usize;
+    let ancount = u16::from_be_bytes([reply[6], reply[7]]) as usize;
+
+    let mut off = 12;
+    for _ in 0..qdcount {
+        off = skip_name(reply, off)? + 4; // + QTYPE + QCLASS
+    }
+
+    let mut out = Vec::new();
+    for _ in 0..ancount {
+        off = skip_name(reply, off)?;
+        if off + 10 > reply.len() {
+            return Err(EINVAL);
+        }
+        let rtype = u16::from_be_bytes([reply[off], reply[off + 1]]);
+        let rdlength = u16::from_be_bytes([reply[off + 8], reply[off + 9]]) as usize;
+        off += 10;
+        if off + rdlength > reply.len() {
+            return Err(EINVAL);
+        }
+        match (rtype, rdlength) {
+            (TYPE_A, 4) => {
+                let b = &reply[off..off + 4];
+                out.try_push(IpAddr::V4(Ipv4Addr::new(b[0], b[1], b[2], b[3])))
+                    .map_err(|_| ENOMEM)?;
+            }
+            (TYPE_AAAA, 16) => {
+                let b: [u8; 16] = reply[off..off + 16].try_into().unwrap();
+                out.try_push(IpAddr::V6(Ipv6Addr::from(b)))
+                    .map_err(|_| ENOMEM)?;
+            }
+            _ => {}
+        }
+        off += rdlength;
+    }
+
+    Ok(out)
+}
+
+/// Skips a (possibly compressed) DNS name starting at `off`, returning the
+/// offset of the byte following it.
+fn skip_name(buf: &[u8], mut off: usize) -> Result<usize> {
+    loop {
+        if off >= buf.len() {
+            return Err(EINVAL);
+        }
+        let len = buf[off];
+        if len == 0 {
+            return Ok(off + 1);
+        } else if len & 0xc0 == 0xc0 {
+            // Compression pointer: two bytes, doesn't recurse further here.
+            if off + 1 >= buf.len() {
+                return Err(EINVAL);
+            }
+            return Ok(off + 2);
+        } else {
+            off += 1 + len as usize;
+        }
+    }
+}
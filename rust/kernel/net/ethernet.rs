@@ -5,6 +5,11 @@
 //! C headers: [`include/uapi/linux/if_ether.h`](../../../../include/uapi/linux/if_ether.h)
 
 use crate::bindings;
+use crate::error::{
+    code::{EINVAL, ENOMEM},
+    Result,
+};
+use alloc::vec::Vec;
 
 // IEEE 802.3 Ethernet magic constants
 //
@@ -39,6 +44,31 @@ pub const MIN_MTU: usize = bindings::ETH_MIN_MTU as usize;
 /// Maximal MTU
 pub const MAX_MTU: usize = bindings::ETH_MAX_MTU as usize;
 
+/// Maximal octet count of a jumbo frame, including the FCS
+///
+/// `MAX_MTU` does not express jumbo-capable hardware's limits, so this is
+/// given explicitly.
+pub const JUMBO_FRAME_LEN: usize = 9018;
+
+/// Returns the minimum legal frame length, the 802.3 zero-pad floor.
+pub const fn min_frame_len() -> usize {
+    ZLEN
+}
+
+/// Returns the maximum legal frame length for a frame carrying `mtu` bytes
+/// of payload, optionally VLAN-tagged and/or including the FCS, the way
+/// BSD's `ETHER_MAX_FRAME` does.
+pub const fn max_frame_len(mtu: usize, vlan_tagged: bool, with_fcs: bool) -> usize {
+    mtu + HLEN + (vlan_tagged as usize * 4) + (with_fcs as usize * FCS_LEN)
+}
+
+/// Returns whether `len` is a legal frame length for a frame carrying `mtu`
+/// bytes of payload, optionally VLAN-tagged and/or including the FCS.
+pub const fn is_valid_frame_len(len: usize, mtu: usize, vlan_tagged: bool, with_fcs: bool) -> bool {
+    len >= min_frame_len() + (with_fcs as usize * FCS_LEN)
+        && len <= max_frame_len(mtu, vlan_tagged, with_fcs)
+}
+
 /// Ethernet Protocol Identifiers
 ///
 /// These were taken from the original tree at `include/uapi/linux/if_ether.h`.
@@ -403,6 +433,145 @@ impl Address {
     pub const fn broadcast() -> Self {
         Self([0xff; ALEN])
     }
+
+    /// Returns whether this is the broadcast address (`ff:ff:ff:ff:ff:ff`).
+    pub fn is_broadcast(&self) -> bool {
+        self.0 == [0xff; ALEN]
+    }
+
+    /// Returns whether this is a multicast address, i.e. the I/G bit of the
+    /// first octet is set.
+    pub fn is_multicast(&self) -> bool {
+        self.0[0] & 0x01 != 0
+    }
+
+    /// Returns whether this is a unicast address.
+    pub fn is_unicast(&self) -> bool {
+        !self.is_multicast()
+    }
+
+    /// Returns whether this is the unspecified (all-zero) address.
+    pub fn is_unspecified(&self) -> bool {
+        self.0 == [0x00; ALEN]
+    }
+
+    /// Returns whether this address is locally administered, i.e. the U/L
+    /// bit of the first octet is set, rather than assigned by the
+    /// manufacturer.
+    pub fn is_locally_administered(&self) -> bool {
+        self.0[0] & 0x02 != 0
+    }
+
+    /// Parses a canonical colon- or dash-separated hex string, e.g.
+    /// `"ab:cd:ef:01:02:03"`, into an [`Address`].
+    pub fn parse(s: &str) -> core::result::Result<Self, AddressParseError> {
+        let mut addr = [0u8; ALEN];
+        let mut n = 0;
+        for octet in s.split(['-', ':']) {
+            if n >= ALEN || octet.len() != 2 {
+                return Err(AddressParseError);
+            }
+            addr[n] = u8::from_str_radix(octet, 16).map_err(|_| AddressParseError)?;
+            n += 1;
+        }
+        if n != ALEN {
+            return Err(AddressParseError);
+        }
+        Ok(Self(addr))
+    }
+}
+
+/// Error returned when parsing a string as an Ethernet [`Address`] fails.
+#[derive(Clone, Copy, Debug)]
+pub struct AddressParseError;
+
+impl core::str::FromStr for Address {
+    type Err = AddressParseError;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl core::fmt::Display for Address {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5]
+        )
+    }
+}
+
+/// An 802.1Q/802.1ad VLAN tag: the Tag Protocol Identifier (TPID) and Tag
+/// Control Information (TCI) inserted between the source address and
+/// ethertype of a tagged Ethernet II frame.
+#[derive(Clone, Copy, Debug)]
+pub struct VlanTag {
+    tpid: u16,
+    tci: u16,
+}
+
+impl VlanTag {
+    /// TPID of a customer (802.1Q) VLAN tag.
+    pub const TPID_CVLAN: u16 = bindings::ETH_P_8021Q as u16;
+    /// TPID of a service (802.1ad) VLAN tag.
+    pub const TPID_SVLAN: u16 = bindings::ETH_P_8021AD as u16;
+
+    /// Builds a new VLAN tag from its constituent fields.
+    ///
+    /// `vid` is masked to its valid 12-bit range; 0 and 4095 are reserved,
+    /// but are still accepted here since their meaning is context-dependent.
+    pub fn new(tpid: u16, pcp: u8, dei: bool, vid: u16) -> Self {
+        let tci = (u16::from(pcp & 0x7) << 13) | (u16::from(dei) << 12) | (vid & 0x0fff);
+        Self { tpid, tci }
+    }
+
+    /// Returns the Tag Protocol Identifier.
+    pub fn tpid(&self) -> u16 {
+        self.tpid
+    }
+
+    /// Returns the 3-bit Priority Code Point.
+    pub fn pcp(&self) -> u8 {
+        (self.tci >> 13) as u8
+    }
+
+    /// Returns the Drop Eligible Indicator.
+    pub fn dei(&self) -> bool {
+        self.tci & 0x1000 != 0
+    }
+
+    /// Returns the 12-bit VLAN ID.
+    pub fn vid(&self) -> u16 {
+        self.tci & 0x0fff
+    }
+
+    /// Returns this tag's 4 network-endian bytes, as they appear on the
+    /// wire, TPID followed by TCI.
+    pub fn to_be_bytes(&self) -> [u8; 4] {
+        let mut out = [0u8; 4];
+        out[..2].copy_from_slice(&self.tpid.to_be_bytes());
+        out[2..].copy_from_slice(&self.tci.to_be_bytes());
+        out
+    }
+
+    fn is_tpid(tpid: u16) -> bool {
+        tpid == Self::TPID_CVLAN || tpid == Self::TPID_SVLAN
+    }
+}
+
+/// The interpretation of a frame's 16-bit type/length field: either an
+/// 802.3 frame length, a resolved Ethernet II ethertype, or an
+/// unrecognized one.
+#[derive(Clone, Copy, Debug)]
+pub enum TypeOrLength {
+    /// The field is a frame length (`<= ETH_DATA_LEN`), as used by 802.3.
+    Length(u16),
+    /// The field is a resolved Ethernet II ethertype.
+    Type(Proto),
+    /// The field is an ethertype this module does not recognize.
+    UnknownType(u16),
 }
 
 /// Ethernet II protocol header
@@ -438,4 +607,89 @@ impl Header {
     pub fn proto_number(&self) -> u16 {
         self.0.h_proto
     }
+
+    /// Interprets the leading [`HLEN`] bytes of `buf` as a `Header`,
+    /// returning it alongside the remaining payload, without copying.
+    pub fn from_bytes(buf: &[u8]) -> Result<(&Header, &[u8])> {
+        if buf.len() < HLEN {
+            return Err(EINVAL);
+        }
+        // SAFETY: `Header` is `repr(transparent)` over `bindings::ethhdr`,
+        // which is `packed` and therefore has no alignment requirement
+        // beyond 1, and `buf` is at least `HLEN` bytes long, so the cast
+        // yields a valid reference for the lifetime of `buf`.
+        let header = unsafe { &*buf.as_ptr().cast::<Header>() };
+        Ok((header, &buf[HLEN..]))
+    }
+
+    /// Returns this header's ethertype, converting it from network to host
+    /// endian and resolving it through [`Proto`].
+    pub fn proto(&self) -> core::result::Result<Proto, UnknownProtoError> {
+        Proto::try_from(u16::from_be(self.0.h_proto))
+    }
+
+    /// Classifies this header's type/length field per the classic 802.3
+    /// rule that a value of `ETH_DATA_LEN` (1500) or below is a frame
+    /// length rather than an Ethernet II ethertype.
+    pub fn type_or_length(&self) -> TypeOrLength {
+        let value = u16::from_be(self.0.h_proto);
+        if value <= DATA_LEN as u16 {
+            TypeOrLength::Length(value)
+        } else {
+            match Proto::try_from(value) {
+                Ok(proto) => TypeOrLength::Type(proto),
+                Err(_) => TypeOrLength::UnknownType(value),
+            }
+        }
+    }
+
+    /// Returns this header's raw, network-endian bytes, ready to be written
+    /// onto the wire.
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: `Header` is `repr(transparent)` over `bindings::ethhdr`,
+        // which is a packed, pointer-free plain-old-data struct, so
+        // reinterpreting it as a byte slice of its own size is always
+        // valid.
+        unsafe {
+            core::slice::from_raw_parts(
+                (self as *const Self).cast::<u8>(),
+                core::mem::size_of::<Self>(),
+            )
+        }
+    }
+
+    /// Walks `payload` (the bytes immediately following this header)
+    /// collecting a stack of VLAN tags until a non-VLAN ethertype is
+    /// reached, to support stacked (QinQ) tagging.
+    ///
+    /// Returns the collected tags, outermost first, the inner ethertype in
+    /// host-endian form, and the offset into `payload` at which the real
+    /// payload begins.
+    pub fn vlan_stack(&self, payload: &[u8]) -> Result<(Vec<VlanTag>, u16, usize)> {
+        let mut tags = Vec::new();
+        let mut ethertype = u16::from_be(self.0.h_proto);
+        let mut offset = 0;
+
+        while VlanTag::is_tpid(ethertype) {
+            let tci = payload
+                .get(offset..offset + 2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                .ok_or(EINVAL)?;
+            let next = payload
+                .get(offset + 2..offset + 4)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                .ok_or(EINVAL)?;
+
+            tags.try_push(VlanTag {
+                tpid: ethertype,
+                tci,
+            })
+            .map_err(|_| ENOMEM)?;
+
+            offset += 4;
+            ethertype = next;
+        }
+
+        Ok((tags, ethertype, offset))
+    }
 }
@@ -39,6 +39,202 @@ unsafe extern "C" fn get_ts_info_callback<T: NetDeviceAdapter>(
     }
 }
 
+unsafe extern "C" fn get_ringparam_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    param: *mut bindings::ethtool_ringparam,
+) {
+    T::EthOps::get_ringparam(
+        // SAFETY: dev is valid, as this is a callback
+        unsafe { &NetDevice::<T>::from_pointer(dev) },
+        // SAFETY: param is valid, as this is a callback
+        unsafe { &mut EthtoolRingParam::from_pointer(param) },
+    );
+}
+
+unsafe extern "C" fn set_ringparam_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    param: *const bindings::ethtool_ringparam,
+) -> c_types::c_int {
+    from_kernel_result! {
+        T::EthOps::set_ringparam(
+            // SAFETY: dev is valid, as this is a callback
+            unsafe { &NetDevice::<T>::from_pointer(dev) },
+            // SAFETY: param is valid, as this is a callback
+            unsafe { &EthtoolRingParam::from_pointer(param) }
+        )?;
+        Ok(0)
+    }
+}
+
+unsafe extern "C" fn get_coalesce_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    coalesce: *mut bindings::ethtool_coalesce,
+) -> c_types::c_int {
+    from_kernel_result! {
+        T::EthOps::get_coalesce(
+            // SAFETY: dev is valid, as this is a callback
+            unsafe { &NetDevice::<T>::from_pointer(dev) },
+            // SAFETY: coalesce is valid, as this is a callback
+            unsafe { &mut EthtoolCoalesce::from_pointer(coalesce) }
+        )?;
+        Ok(0)
+    }
+}
+
+unsafe extern "C" fn set_coalesce_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    coalesce: *const bindings::ethtool_coalesce,
+) -> c_types::c_int {
+    from_kernel_result! {
+        T::EthOps::set_coalesce(
+            // SAFETY: dev is valid, as this is a callback
+            unsafe { &NetDevice::<T>::from_pointer(dev) },
+            // SAFETY: coalesce is valid, as this is a callback
+            unsafe { &EthtoolCoalesce::from_pointer(coalesce) }
+        )?;
+        Ok(0)
+    }
+}
+
+unsafe extern "C" fn get_channels_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    channels: *mut bindings::ethtool_channels,
+) {
+    T::EthOps::get_channels(
+        // SAFETY: dev is valid, as this is a callback
+        unsafe { &NetDevice::<T>::from_pointer(dev) },
+        // SAFETY: channels is valid, as this is a callback
+        unsafe { &mut EthtoolChannels::from_pointer(channels) },
+    );
+}
+
+unsafe extern "C" fn set_channels_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    channels: *const bindings::ethtool_channels,
+) -> c_types::c_int {
+    from_kernel_result! {
+        T::EthOps::set_channels(
+            // SAFETY: dev is valid, as this is a callback
+            unsafe { &NetDevice::<T>::from_pointer(dev) },
+            // SAFETY: channels is valid, as this is a callback
+            unsafe { &EthtoolChannels::from_pointer(channels) }
+        )?;
+        Ok(0)
+    }
+}
+
+unsafe extern "C" fn get_pauseparam_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    pause: *mut bindings::ethtool_pauseparam,
+) {
+    T::EthOps::get_pauseparam(
+        // SAFETY: dev is valid, as this is a callback
+        unsafe { &NetDevice::<T>::from_pointer(dev) },
+        // SAFETY: pause is valid, as this is a callback
+        unsafe { &mut EthtoolPauseParam::from_pointer(pause) },
+    );
+}
+
+unsafe extern "C" fn set_pauseparam_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    pause: *const bindings::ethtool_pauseparam,
+) -> c_types::c_int {
+    from_kernel_result! {
+        T::EthOps::set_pauseparam(
+            // SAFETY: dev is valid, as this is a callback
+            unsafe { &NetDevice::<T>::from_pointer(dev) },
+            // SAFETY: pause is valid, as this is a callback
+            unsafe { &EthtoolPauseParam::from_pointer(pause) }
+        )?;
+        Ok(0)
+    }
+}
+
+unsafe extern "C" fn get_link_ksettings_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    cmd: *mut bindings::ethtool_link_ksettings,
+) -> c_types::c_int {
+    from_kernel_result! {
+        T::EthOps::get_link_ksettings(
+            // SAFETY: dev is valid, as this is a callback
+            unsafe { &NetDevice::<T>::from_pointer(dev) },
+            // SAFETY: cmd is valid, as this is a callback
+            unsafe { &mut EthtoolLinkKsettings::from_pointer(cmd) }
+        )?;
+        Ok(0)
+    }
+}
+
+unsafe extern "C" fn set_link_ksettings_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    cmd: *const bindings::ethtool_link_ksettings,
+) -> c_types::c_int {
+    from_kernel_result! {
+        T::EthOps::set_link_ksettings(
+            // SAFETY: dev is valid, as this is a callback
+            unsafe { &NetDevice::<T>::from_pointer(dev) },
+            // SAFETY: cmd is valid, as this is a callback
+            unsafe { &EthtoolLinkKsettings::from_pointer(cmd) }
+        )?;
+        Ok(0)
+    }
+}
+
+unsafe extern "C" fn get_sset_count_callback<T: NetDeviceAdapter>(
+    _dev: *mut bindings::net_device,
+    sset: c_types::c_int,
+) -> c_types::c_int {
+    from_kernel_result! {
+        if sset as u32 != bindings::ETH_SS_STATS {
+            Err(Error::EOPNOTSUPP)
+        } else {
+            Ok(T::EthOps::stats().len() as c_types::c_int)
+        }
+    }
+}
+
+unsafe extern "C" fn get_strings_callback<T: NetDeviceAdapter>(
+    _dev: *mut bindings::net_device,
+    stringset: u32,
+    data: *mut u8,
+) {
+    if stringset != bindings::ETH_SS_STATS {
+        return;
+    }
+
+    let stats = T::EthOps::stats();
+    // SAFETY: the core only calls this after `get_sset_count` reported `stats.len()` entries
+    // for `ETH_SS_STATS`, so `data` has room for that many `ETH_GSTRING_LEN`-byte strings.
+    let buf = unsafe {
+        core::slice::from_raw_parts_mut(data, stats.len() * bindings::ETH_GSTRING_LEN as usize)
+    };
+
+    // SAFETY: `buf` is the destination buffer passed to this callback.
+    let mut writer = unsafe { EthtoolStringWriter::new(buf) };
+    for stat in stats {
+        writer.write(stat.name);
+    }
+}
+
+unsafe extern "C" fn get_ethtool_stats_callback<T: NetDeviceAdapter>(
+    dev: *mut bindings::net_device,
+    _stats: *mut bindings::ethtool_stats,
+    data: *mut u64,
+) {
+    let stats = T::EthOps::stats();
+    // SAFETY: the core only calls this after `get_sset_count` reported `stats.len()` entries
+    // for `ETH_SS_STATS`, so `data` has room for that many `u64`s.
+    let buf = unsafe { core::slice::from_raw_parts_mut(data, stats.len()) };
+    // SAFETY: dev is valid, as this is a callback
+    let dev = unsafe { NetDevice::<T>::from_pointer(dev) };
+
+    // SAFETY: `buf` is the destination buffer passed to this callback.
+    let mut writer = unsafe { EthtoolStatsWriter::new(buf) };
+    for stat in stats {
+        writer.write((stat.get)(&dev));
+    }
+}
+
 pub(crate) struct EthToolOperationsVtable<T: NetDeviceAdapter>(marker::PhantomData<T>);
 
 impl<T: NetDeviceAdapter> EthToolOperationsVtable<T> {
@@ -63,22 +259,46 @@ impl<T: NetDeviceAdapter> EthToolOperationsVtable<T> {
         get_eeprom_len: None,
         get_eeprom: None,
         set_eeprom: None,
-        get_coalesce: None,
-        set_coalesce: None,
-        get_ringparam: None,
-        set_ringparam: None,
+        get_coalesce: if T::EthOps::TO_USE.get_coalesce {
+            Some(get_coalesce_callback::<T>)
+        } else {
+            None
+        },
+        set_coalesce: if T::EthOps::TO_USE.set_coalesce {
+            Some(set_coalesce_callback::<T>)
+        } else {
+            None
+        },
+        get_ringparam: if T::EthOps::TO_USE.get_ringparam {
+            Some(get_ringparam_callback::<T>)
+        } else {
+            None
+        },
+        set_ringparam: if T::EthOps::TO_USE.set_ringparam {
+            Some(set_ringparam_callback::<T>)
+        } else {
+            None
+        },
         get_pause_stats: None,
-        get_pauseparam: None,
-        set_pauseparam: None,
+        get_pauseparam: if T::EthOps::TO_USE.get_pauseparam {
+            Some(get_pauseparam_callback::<T>)
+        } else {
+            None
+        },
+        set_pauseparam: if T::EthOps::TO_USE.set_pauseparam {
+            Some(set_pauseparam_callback::<T>)
+        } else {
+            None
+        },
         self_test: None,
-        get_strings: None,
+        get_strings: Some(get_strings_callback::<T>),
         set_phys_id: None,
-        get_ethtool_stats: None,
+        get_ethtool_stats: Some(get_ethtool_stats_callback::<T>),
         begin: None,
         complete: None,
         get_priv_flags: None,
         set_priv_flags: None,
-        get_sset_count: None,
+        get_sset_count: Some(get_sset_count_callback::<T>),
         get_rxnfc: None,
         set_rxnfc: None,
         flash_device: None,
@@ -89,8 +309,16 @@ impl<T: NetDeviceAdapter> EthToolOperationsVtable<T> {
         set_rxfh: None,
         get_rxfh_context: None,
         set_rxfh_context: None,
-        get_channels: None,
-        set_channels: None,
+        get_channels: if T::EthOps::TO_USE.get_channels {
+            Some(get_channels_callback::<T>)
+        } else {
+            None
+        },
+        set_channels: if T::EthOps::TO_USE.set_channels {
+            Some(set_channels_callback::<T>)
+        } else {
+            None
+        },
         get_dump_flag: None,
         get_dump_data: None,
         set_dump: None,
@@ -107,8 +335,16 @@ impl<T: NetDeviceAdapter> EthToolOperationsVtable<T> {
         set_tunable: None,
         get_per_queue_coalesce: None,
         set_per_queue_coalesce: None,
-        get_link_ksettings: None,
-        set_link_ksettings: None,
+        get_link_ksettings: if T::EthOps::TO_USE.get_link_ksettings {
+            Some(get_link_ksettings_callback::<T>)
+        } else {
+            None
+        },
+        set_link_ksettings: if T::EthOps::TO_USE.set_link_ksettings {
+            Some(set_link_ksettings_callback::<T>)
+        } else {
+            None
+        },
         get_fecparam: None,
         set_fecparam: None,
         get_ethtool_phy_stats: None,
@@ -133,6 +369,36 @@ pub struct EthToolToUse {
 
     /// Trait defines a `get_ts_info` function.
     pub get_ts_info: bool,
+
+    /// Trait defines a `get_ringparam` function.
+    pub get_ringparam: bool,
+
+    /// Trait defines a `set_ringparam` function.
+    pub set_ringparam: bool,
+
+    /// Trait defines a `get_coalesce` function.
+    pub get_coalesce: bool,
+
+    /// Trait defines a `set_coalesce` function.
+    pub set_coalesce: bool,
+
+    /// Trait defines a `get_channels` function.
+    pub get_channels: bool,
+
+    /// Trait defines a `set_channels` function.
+    pub set_channels: bool,
+
+    /// Trait defines a `get_pauseparam` function.
+    pub get_pauseparam: bool,
+
+    /// Trait defines a `set_pauseparam` function.
+    pub set_pauseparam: bool,
+
+    /// Trait defines a `get_link_ksettings` function.
+    pub get_link_ksettings: bool,
+
+    /// Trait defines a `set_link_ksettings` function.
+    pub set_link_ksettings: bool,
 }
 
 /// This trait does not include any functions.
@@ -140,6 +406,16 @@ pub struct EthToolToUse {
 pub const ETH_TOOL_USE_NONE: EthToolToUse = EthToolToUse {
     get_drvinfo: false,
     get_ts_info: false,
+    get_ringparam: false,
+    set_ringparam: false,
+    get_coalesce: false,
+    set_coalesce: false,
+    get_channels: false,
+    set_channels: false,
+    get_pauseparam: false,
+    set_pauseparam: false,
+    get_link_ksettings: false,
+    set_link_ksettings: false,
 };
 
 /// Defines the [`EthToolOps::TO_USE`] field based on a list of fields to be populated.
@@ -175,6 +451,71 @@ pub trait EthToolOps<T: NetDeviceAdapter>: Send + Sync + Sized {
     fn get_ts_info(_dev: &NetDevice<T>, _info: &mut EthToolTsInfo) -> Result {
         Err(Error::EINVAL)
     }
+
+    /// Report rx/tx ring sizes.
+    fn get_ringparam(_dev: &NetDevice<T>, _param: &mut EthtoolRingParam) {}
+
+    /// Set rx/tx ring sizes.  Returns a negative error code or zero.
+    fn set_ringparam(_dev: &NetDevice<T>, _param: &EthtoolRingParam) -> Result {
+        Err(Error::EINVAL)
+    }
+
+    /// Get interrupt coalescing parameters.  Returns a negative error code or zero.
+    fn get_coalesce(_dev: &NetDevice<T>, _coalesce: &mut EthtoolCoalesce) -> Result {
+        Err(Error::EINVAL)
+    }
+
+    /// Set interrupt coalescing parameters.  Returns a negative error code or zero.
+    fn set_coalesce(_dev: &NetDevice<T>, _coalesce: &EthtoolCoalesce) -> Result {
+        Err(Error::EINVAL)
+    }
+
+    /// Report number of available rx/tx/other/combined channels.
+    fn get_channels(_dev: &NetDevice<T>, _channels: &mut EthtoolChannels) {}
+
+    /// Set number of rx/tx/other/combined channels.  Returns a negative error code or zero.
+    fn set_channels(_dev: &NetDevice<T>, _channels: &EthtoolChannels) -> Result {
+        Err(Error::EINVAL)
+    }
+
+    /// Report pause parameters.
+    fn get_pauseparam(_dev: &NetDevice<T>, _pause: &mut EthtoolPauseParam) {}
+
+    /// Set pause parameters.  Returns a negative error code or zero.
+    fn set_pauseparam(_dev: &NetDevice<T>, _pause: &EthtoolPauseParam) -> Result {
+        Err(Error::EINVAL)
+    }
+
+    /// Get various link settings.  Should report a complete set of settings
+    /// that are supported.  Returns a negative error code or zero.
+    fn get_link_ksettings(_dev: &NetDevice<T>, _cmd: &mut EthtoolLinkKsettings) -> Result {
+        Err(Error::EINVAL)
+    }
+
+    /// Set various link settings.  Should validate the settings prior to
+    /// applying them.  Returns a negative error code or zero.
+    fn set_link_ksettings(_dev: &NetDevice<T>, _cmd: &EthtoolLinkKsettings) -> Result {
+        Err(Error::EINVAL)
+    }
+
+    /// The device's custom statistics, reported under `ethtool -S`.
+    ///
+    /// `get_sset_count`, `get_strings` and `get_ethtool_stats` are all derived from this list,
+    /// so implementing it is enough to support `ethtool -S` without touching those three ops
+    /// directly. Empty by default, meaning the device reports no custom statistics.
+    fn stats() -> &'static [EthtoolStat<T>] {
+        &[]
+    }
+}
+
+/// One custom statistic reported under `ethtool -S`, pairing the name `ethtool` prints with the
+/// accessor that reads its current value off the device.
+pub struct EthtoolStat<T: NetDeviceAdapter> {
+    /// The name shown by `ethtool -S`, truncated to `ETH_GSTRING_LEN - 1` bytes.
+    pub name: &'static str,
+
+    /// Reads the current value of this statistic from the device.
+    pub get: fn(&NetDevice<T>) -> u64,
 }
 
 /// Wrappes the [`bindings::ethtool_ts_info`] struct.
@@ -216,6 +557,165 @@ impl SavedAsPointer for EthtoolDrvinfo {
 
 impl SavedAsPointerMut for EthtoolDrvinfo {}
 
+/// Wrappes the [`bindings::ethtool_ringparam`] struct.
+pub struct EthtoolRingParam {
+    ptr: *const bindings::ethtool_ringparam,
+}
+
+impl SavedAsPointer for EthtoolRingParam {
+    type InternalType = bindings::ethtool_ringparam;
+
+    unsafe fn from_pointer(ptr: *const Self::InternalType) -> Self {
+        Self { ptr }
+    }
+
+    fn get_pointer(&self) -> *const Self::InternalType {
+        self.ptr
+    }
+}
+
+impl SavedAsPointerMut for EthtoolRingParam {}
+
+/// Wrappes the [`bindings::ethtool_coalesce`] struct.
+pub struct EthtoolCoalesce {
+    ptr: *const bindings::ethtool_coalesce,
+}
+
+impl SavedAsPointer for EthtoolCoalesce {
+    type InternalType = bindings::ethtool_coalesce;
+
+    unsafe fn from_pointer(ptr: *const Self::InternalType) -> Self {
+        Self { ptr }
+    }
+
+    fn get_pointer(&self) -> *const Self::InternalType {
+        self.ptr
+    }
+}
+
+impl SavedAsPointerMut for EthtoolCoalesce {}
+
+/// Wrappes the [`bindings::ethtool_channels`] struct.
+pub struct EthtoolChannels {
+    ptr: *const bindings::ethtool_channels,
+}
+
+impl SavedAsPointer for EthtoolChannels {
+    type InternalType = bindings::ethtool_channels;
+
+    unsafe fn from_pointer(ptr: *const Self::InternalType) -> Self {
+        Self { ptr }
+    }
+
+    fn get_pointer(&self) -> *const Self::InternalType {
+        self.ptr
+    }
+}
+
+impl SavedAsPointerMut for EthtoolChannels {}
+
+/// Wrappes the [`bindings::ethtool_pauseparam`] struct.
+pub struct EthtoolPauseParam {
+    ptr: *const bindings::ethtool_pauseparam,
+}
+
+impl SavedAsPointer for EthtoolPauseParam {
+    type InternalType = bindings::ethtool_pauseparam;
+
+    unsafe fn from_pointer(ptr: *const Self::InternalType) -> Self {
+        Self { ptr }
+    }
+
+    fn get_pointer(&self) -> *const Self::InternalType {
+        self.ptr
+    }
+}
+
+impl SavedAsPointerMut for EthtoolPauseParam {}
+
+/// Wrappes the [`bindings::ethtool_link_ksettings`] struct.
+pub struct EthtoolLinkKsettings {
+    ptr: *const bindings::ethtool_link_ksettings,
+}
+
+impl SavedAsPointer for EthtoolLinkKsettings {
+    type InternalType = bindings::ethtool_link_ksettings;
+
+    unsafe fn from_pointer(ptr: *const Self::InternalType) -> Self {
+        Self { ptr }
+    }
+
+    fn get_pointer(&self) -> *const Self::InternalType {
+        self.ptr
+    }
+}
+
+impl SavedAsPointerMut for EthtoolLinkKsettings {}
+
+/// A bounds-checked destination for [`EthToolOps::stats`]' names, standing in for the `u8 *data`
+/// buffer `get_strings` is handed.
+pub struct EthtoolStringWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> EthtoolStringWriter<'a> {
+    /// Creates a new `EthtoolStringWriter` over `buf`.
+    ///
+    /// # Safety
+    ///
+    /// `buf` must have been obtained as the destination buffer passed to the `get_strings`
+    /// callback.
+    unsafe fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Writes the next `ETH_GSTRING_LEN`-byte slot, truncating or zero-padding `name` to fit.
+    ///
+    /// Does nothing once every slot the kernel provided has been written.
+    fn write(&mut self, name: &str) {
+        let len = bindings::ETH_GSTRING_LEN as usize;
+        let Some(slot) = self.buf.get_mut(self.pos * len..(self.pos + 1) * len) else {
+            return;
+        };
+
+        let bytes = name.as_bytes();
+        let copy = bytes.len().min(len - 1);
+        slot[..copy].copy_from_slice(&bytes[..copy]);
+        slot[copy..].fill(0);
+        self.pos += 1;
+    }
+}
+
+/// A bounds-checked destination for [`EthToolOps::stats`]' values, standing in for the
+/// `u64 *data` buffer `get_ethtool_stats` is handed.
+pub struct EthtoolStatsWriter<'a> {
+    buf: &'a mut [u64],
+    pos: usize,
+}
+
+impl<'a> EthtoolStatsWriter<'a> {
+    /// Creates a new `EthtoolStatsWriter` over `buf`.
+    ///
+    /// # Safety
+    ///
+    /// `buf` must have been obtained as the destination buffer passed to the
+    /// `get_ethtool_stats` callback.
+    unsafe fn new(buf: &'a mut [u64]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Writes the next value. Does nothing once every slot the kernel provided has been written.
+    fn write(&mut self, value: u64) {
+        let Some(slot) = self.buf.get_mut(self.pos) else {
+            return;
+        };
+
+        *slot = value;
+        self.pos += 1;
+    }
+}
+
 /// Helper functions for ethtool.
 pub mod helpers {
     use super::*;
@@ -4,6 +4,8 @@
 
 use crate::{
     bindings,
+    c_types,
+    error::{self, Result},
     net::{Device, SkBuff},
 };
 use core::marker::PhantomData;
@@ -164,6 +166,12 @@ impl Napi {
     }
 
     /// Transmit to the GRO
+    ///
+    /// If `sk_buff` was built via `SkBuff::build_around` (see
+    /// [`net::page_pool`](super::page_pool)), its backing page is recycled
+    /// back to its pool automatically once the core frees or merges it, on
+    /// both the [`GroResult::MergedFree`] and [`GroResult::Consumed`]
+    /// outcomes; no further action is needed here.
     pub fn gro_receive(&mut self, sk_buff: &mut SkBuff) -> GroResult {
         let self_ptr = self.get_inner_cast();
         let skb_ptr: *mut bindings::sk_buff = (sk_buff as *mut SkBuff).cast();
@@ -174,6 +182,111 @@ impl Napi {
         res.try_into()
             .expect("Unable to convert return of napi_gro_receive to gro_result\n")
     }
+
+    /// Returns the skb currently being assembled from fragments by this NAPI
+    /// instance, if the driver has started one via [`Self::get_frags`].
+    ///
+    /// Wraps `napi_get_frags`.
+    pub fn get_frags(&mut self) -> Option<&mut SkBuff> {
+        let self_ptr = self.get_inner_cast();
+
+        // SAFETY: The pointer is guaranteed to be non-null and valid all
+        // throughout the call.
+        let skb_ptr = unsafe { bindings::napi_get_frags(self_ptr) };
+
+        if skb_ptr.is_null() {
+            None
+        } else {
+            // SAFETY: We've guaranteed that `skb_ptr` is non-null, and the
+            // kernel guarantees that it stays valid for the duration of the
+            // current poll.
+            Some(unsafe { SkBuff::from_pointer(skb_ptr) })
+        }
+    }
+
+    /// Feeds the frag list built up via [`Self::get_frags`] to the GRO
+    /// engine.
+    ///
+    /// Wraps `napi_gro_frags`.
+    pub fn gro_frags(&mut self) -> GroResult {
+        let self_ptr = self.get_inner_cast();
+
+        // SAFETY: The pointer is guaranteed to be non-null and valid all
+        // throughout the call.
+        let res = unsafe { bindings::napi_gro_frags(self_ptr) };
+        res.try_into()
+            .expect("Unable to convert return of napi_gro_frags to gro_result\n")
+    }
+
+    /// Forces any packets [`Self::gro_receive`] is currently holding for
+    /// coalescing to be flushed up the stack.
+    ///
+    /// Pass `flush_old` to also flush packets that have already seen one
+    /// flush attempt, which a driver should do before completing its poll
+    /// so that held flows do not linger across budget rounds.
+    ///
+    /// Wraps `napi_gro_flush`.
+    pub fn gro_flush(&mut self, flush_old: bool) {
+        let self_ptr = self.get_inner_cast();
+
+        // SAFETY: The pointer is guaranteed to be non-null and valid all
+        // throughout the call.
+        unsafe { bindings::napi_gro_flush(self_ptr, flush_old) };
+    }
+
+    /// Returns this NAPI instance's id, as used by [`Self::busy_loop`] and by
+    /// userspace's `SO_INCOMING_NAPI_ID`.
+    pub fn id(&self) -> u32 {
+        self.0.napi_id
+    }
+
+    /// Opts this NAPI instance into (or out of) kernel-thread-driven
+    /// polling, instead of waiting for softirq wakeups.
+    ///
+    /// Wraps `napi_set_threaded`.
+    pub fn set_threaded(&mut self, threaded: bool) -> Result {
+        let self_ptr = self.get_inner_cast();
+
+        // SAFETY: The pointer is guaranteed to be non-null and valid all
+        // throughout the call.
+        error::to_result(unsafe { bindings::napi_set_threaded(self_ptr, threaded) })
+    }
+
+    /// Busy-polls this NAPI instance from the calling context instead of
+    /// waiting for a softirq wakeup.
+    ///
+    /// `loop_end` is polled between rounds and should return `true` to stop
+    /// looping, e.g. once the caller's own deadline or budget has been
+    /// reached.
+    ///
+    /// Wraps `napi_busy_loop`.
+    pub fn busy_loop<F: FnMut() -> bool>(&self, prefer_busy_poll: bool, budget: u16, loop_end: F) {
+        unsafe extern "C" fn trampoline<F: FnMut() -> bool>(
+            arg: *mut c_types::c_void,
+            _uptime: usize,
+        ) -> bool {
+            // SAFETY: `arg` was set, for the duration of this call, to point
+            // at a `F` living on `busy_loop`'s caller's stack.
+            let loop_end = unsafe { &mut *arg.cast::<F>() };
+            loop_end()
+        }
+
+        let mut loop_end = loop_end;
+        let arg: *mut c_types::c_void = (&mut loop_end as *mut F).cast();
+
+        // SAFETY: `self.id()` identifies a NAPI instance registered with the
+        // core; `arg` points at `loop_end`, which outlives this call, and
+        // `trampoline::<F>` only dereferences it as an `F`.
+        unsafe {
+            bindings::napi_busy_loop(
+                self.id(),
+                Some(trampoline::<F>),
+                arg,
+                prefer_busy_poll,
+                budget,
+            )
+        };
+    }
 }
 
 /// Enumerator for the return type of [`SkBuff::gro_receive`]
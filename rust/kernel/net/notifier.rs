@@ -0,0 +1,145 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! Netdev notifier chain.
+//!
+//! Lets Rust code subscribe to the events the core device layer publishes
+//! via `register_netdevice_notifier`/`unregister_netdevice_notifier`
+//! (interfaces appearing, disappearing, and changing state), without being
+//! tied to the concrete [`NetDeviceAdapter`] of the device being reported on.
+//!
+//! C header: [`include/linux/netdevice.h`](../../../../include/linux/netdevice.h)
+
+use core::convert::TryFrom;
+use core::pin::Pin;
+
+use macros::{pin_data, pinned_drop};
+
+use crate::bindings;
+use crate::c_types;
+use crate::container_of;
+use crate::error::{self, Error};
+use crate::init::PinInit;
+use crate::try_pin_init;
+use crate::types::Opaque;
+
+use super::device::{NetDevice, Unknown};
+
+/// Events delivered over the netdev notifier chain.
+///
+/// Maps the `NETDEV_*` action codes the core passes to the registered
+/// callback.
+#[repr(u32)]
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetDeviceEvent {
+    /// The device was registered (`NETDEV_REGISTER`).
+    Register = bindings::NETDEV_REGISTER,
+    /// The device was unregistered (`NETDEV_UNREGISTER`).
+    Unregister = bindings::NETDEV_UNREGISTER,
+    /// The device went administratively up (`NETDEV_UP`).
+    Up = bindings::NETDEV_UP,
+    /// The device went administratively down (`NETDEV_DOWN`).
+    Down = bindings::NETDEV_DOWN,
+    /// The device's MTU changed (`NETDEV_CHANGEMTU`).
+    ChangeMtu = bindings::NETDEV_CHANGEMTU,
+    /// The device's name changed (`NETDEV_CHANGENAME`).
+    ChangeName = bindings::NETDEV_CHANGENAME,
+}
+
+impl TryFrom<u32> for NetDeviceEvent {
+    type Error = ();
+
+    fn try_from(val: u32) -> core::result::Result<Self, Self::Error> {
+        match val {
+            x if x == Self::Register as u32 => Ok(Self::Register),
+            x if x == Self::Unregister as u32 => Ok(Self::Unregister),
+            x if x == Self::Up as u32 => Ok(Self::Up),
+            x if x == Self::Down as u32 => Ok(Self::Down),
+            x if x == Self::ChangeMtu as u32 => Ok(Self::ChangeMtu),
+            x if x == Self::ChangeName as u32 => Ok(Self::ChangeName),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Subscribes to the netdev notifier chain.
+///
+/// Implement this for the type driving your module's reaction to interfaces
+/// appearing, disappearing, or changing state.
+pub trait NetDeviceNotifier {
+    /// Called for every event on the chain that maps to a known
+    /// [`NetDeviceEvent`]. Events without a corresponding variant are
+    /// silently ignored.
+    fn notify(&self, event: NetDeviceEvent, dev: &NetDevice<Unknown>);
+}
+
+/// A registration on the netdev notifier chain.
+///
+/// Building one via [`Registration::new`] subscribes `T` via
+/// `register_netdevice_notifier`; dropping it unsubscribes via
+/// `unregister_netdevice_notifier`.
+#[pin_data(PinnedDrop)]
+pub struct Registration<T: NetDeviceNotifier> {
+    #[pin]
+    nb: Opaque<bindings::notifier_block>,
+    notifier: T,
+}
+
+// SAFETY: `Registration` doesn't provide any `&self` methods beyond what `T`
+// itself allows, so it is safe to pass references to it around.
+unsafe impl<T: NetDeviceNotifier + Sync> Sync for Registration<T> {}
+
+// SAFETY: Both registration and unregistration are implemented in C and safe
+// to be performed from any thread.
+unsafe impl<T: NetDeviceNotifier + Send> Send for Registration<T> {}
+
+impl<T: NetDeviceNotifier> Registration<T> {
+    /// Subscribes `notifier` to the netdev notifier chain.
+    pub fn new(notifier: T) -> impl PinInit<Self, Error> {
+        try_pin_init!(Self {
+            notifier,
+            nb <- Opaque::try_ffi_init(|nb_ptr: *mut bindings::notifier_block| {
+                // SAFETY: `try_ffi_init` guarantees that `nb_ptr` is valid
+                // for write.
+                unsafe {
+                    (*nb_ptr).notifier_call = Some(Self::notifier_call_cb);
+                    (*nb_ptr).priority = 0;
+                }
+
+                // SAFETY: `nb_ptr` was just initialised above, and will stay
+                // valid for as long as the registration is active (it is
+                // undone in `drop`).
+                error::to_result(unsafe { bindings::register_netdevice_notifier(nb_ptr) })
+            }),
+        })
+    }
+
+    unsafe extern "C" fn notifier_call_cb(
+        nb: *mut bindings::notifier_block,
+        action: c_types::c_ulong,
+        data: *mut c_types::c_void,
+    ) -> c_types::c_int {
+        // SAFETY: `nb` is the `nb` field of a live, pinned `Registration<T>`
+        // set up by `new`.
+        let reg = unsafe { &*container_of!(nb, Registration<T>, nb) };
+
+        if let Ok(event) = NetDeviceEvent::try_from(action as u32) {
+            // SAFETY: For the `NETDEV_*` events covered by `NetDeviceEvent`,
+            // the core always passes a valid `struct net_device *` as `data`.
+            let dev = unsafe { NetDevice::<Unknown>::from_pointer(data.cast()) };
+
+            reg.notifier.notify(event, &dev);
+        }
+
+        bindings::NOTIFY_DONE as c_types::c_int
+    }
+}
+
+#[pinned_drop]
+impl<T: NetDeviceNotifier> PinnedDrop for Registration<T> {
+    fn drop(self: Pin<&mut Self>) {
+        // SAFETY: The fact that `Self` exists implies that a previous call
+        // to `register_netdevice_notifier` with `self.nb.get()` succeeded.
+        unsafe { bindings::unregister_netdevice_notifier(self.nb.get()) };
+    }
+}
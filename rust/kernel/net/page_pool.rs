@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! Zero-copy RX buffer pools.
+//!
+//! C header: [`include/net/page_pool/helpers.h`](../../../../include/net/page_pool/helpers.h)
+
+use core::ptr::NonNull;
+
+use crate::{
+    bindings,
+    device::RawDevice,
+    error::{code::*, Result},
+    gfp_t,
+    net::gro::Napi,
+};
+
+extern "C" {
+    #[allow(improper_ctypes)]
+    fn rust_helper_skb_reserve(skb: *mut bindings::sk_buff, len: i32);
+
+    #[allow(improper_ctypes)]
+    fn rust_helper_skb_put(skb: *mut bindings::sk_buff, len: u32) -> *mut core::ffi::c_void;
+}
+
+/// The direction of a pool's DMA mappings, mirroring `enum dma_data_direction`.
+#[repr(u32)]
+#[derive(Clone, Copy)]
+pub enum DmaDirection {
+    /// The device may both read and write the buffer.
+    Bidirectional = bindings::dma_data_direction_DMA_BIDIRECTIONAL,
+    /// The device only writes the buffer, e.g. filling it in from the wire.
+    FromDevice = bindings::dma_data_direction_DMA_FROM_DEVICE,
+    /// The device only reads the buffer.
+    ToDevice = bindings::dma_data_direction_DMA_TO_DEVICE,
+}
+
+/// Builder for [`PagePool`].
+pub struct PagePoolParams(bindings::page_pool_params);
+
+impl PagePoolParams {
+    /// Starts a new set of parameters for a pool of `pool_size` pages,
+    /// DMA-mapped for `dma_dir`.
+    ///
+    /// `PP_FLAG_DMA_MAP` is always requested: a pool whose pages cannot be
+    /// handed to a device has no reason to exist.
+    pub fn new(pool_size: u32, dma_dir: DmaDirection) -> Self {
+        let mut params = bindings::page_pool_params::default();
+        params.flags = bindings::PP_FLAG_DMA_MAP;
+        params.pool_size = pool_size;
+        params.dma_dir = dma_dir as _;
+        params.nid = bindings::NUMA_NO_NODE as _;
+        Self(params)
+    }
+
+    /// Places the pool's pages on `nid` instead of the default
+    /// (`NUMA_NO_NODE`).
+    pub fn nid(mut self, nid: i32) -> Self {
+        self.0.nid = nid;
+        self
+    }
+
+    /// Associates the pool with `napi`, letting the core recycle pages
+    /// straight back to the poll function that is about to run next instead
+    /// of going through the slower, locked path.
+    pub fn napi(mut self, napi: &mut Napi) -> Self {
+        self.0.napi = (napi as *mut Napi).cast();
+        self
+    }
+
+    /// Builds the pool for `dev`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ENOMEM`] if the pool could not be allocated.
+    pub fn create(mut self, dev: &impl RawDevice) -> Result<PagePool> {
+        self.0.dev = dev.raw_device();
+
+        // SAFETY: `self.0` is a fully-initialized `page_pool_params`.
+        let ptr = unsafe { bindings::page_pool_create(&self.0) };
+        let ptr = NonNull::new(ptr).ok_or(ENOMEM)?;
+        Ok(PagePool { ptr })
+    }
+}
+
+/// A pool of DMA-mapped pages for a driver's RX path, backed by the kernel's
+/// `page_pool` API.
+///
+/// Pages handed out by [`Self::alloc_page`]/[`Self::alloc_frag`] are
+/// recycled back to the pool automatically, either when the returned
+/// [`PoolBuffer`] is dropped, or, once turned into an skb via
+/// [`SkBuff::build_around`], when the network stack frees that skb.
+pub struct PagePool {
+    ptr: NonNull<bindings::page_pool>,
+}
+
+impl PagePool {
+    /// Allocates a whole, DMA-mapped page from the pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ENOMEM`] if the pool is empty and a refill failed.
+    pub fn alloc_page(&self) -> Result<PoolBuffer> {
+        // SAFETY: `self.ptr` is valid for the lifetime of `self`.
+        let page = unsafe { bindings::page_pool_dev_alloc_pages(self.ptr.as_ptr()) };
+        let page = NonNull::new(page).ok_or(ENOMEM)?;
+        let len = bindings::PAGE_SIZE as usize;
+
+        // SAFETY: `page` was just allocated from `self.ptr`, with the whole
+        // page reserved for the caller at offset 0.
+        Ok(unsafe { PoolBuffer::new(self.ptr, page, 0, len) })
+    }
+
+    /// Allocates a fragment of at most `size` bytes, which may share its
+    /// backing page with other, concurrently live fragments from this pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ENOMEM`] if the pool is empty and a refill failed.
+    pub fn alloc_frag(&self, size: u32, flags: gfp_t) -> Result<PoolBuffer> {
+        let mut offset: u32 = 0;
+
+        // SAFETY: `self.ptr` is valid for the lifetime of `self`, and
+        // `offset` is a valid pointer to write the fragment's offset into.
+        let page =
+            unsafe { bindings::page_pool_alloc_frag(self.ptr.as_ptr(), &mut offset, size, flags) };
+        let page = NonNull::new(page).ok_or(ENOMEM)?;
+
+        // SAFETY: `page` was just allocated from `self.ptr`, with `size`
+        // bytes reserved for the caller starting at `offset`.
+        Ok(unsafe { PoolBuffer::new(self.ptr, page, offset, size as usize) })
+    }
+}
+
+impl Drop for PagePool {
+    fn drop(&mut self) {
+        // SAFETY: `self.ptr` was created by a matching, earlier call to
+        // `page_pool_create`. Any `PoolBuffer`s still outstanding do not
+        // need to outlive `self`: `page_pool_destroy` defers the actual
+        // teardown until they have all been returned to the pool.
+        unsafe { bindings::page_pool_destroy(self.ptr.as_ptr()) };
+    }
+}
+
+// SAFETY: `page_pool`'s allocation and recycling paths are explicitly
+// designed to be called concurrently from any CPU.
+unsafe impl Send for PagePool {}
+// SAFETY: see above; `&self` methods only ever call into those same
+// concurrency-safe paths.
+unsafe impl Sync for PagePool {}
+
+/// An owned buffer allocated from a [`PagePool`].
+///
+/// Dropping a `PoolBuffer` recycles its backing page back to the pool it
+/// came from, rather than freeing it, unless it is first consumed by
+/// [`SkBuff::build_around`] to hand the page to the network stack instead.
+pub struct PoolBuffer {
+    pool: NonNull<bindings::page_pool>,
+    page: NonNull<bindings::page>,
+    offset: u32,
+    len: usize,
+}
+
+impl PoolBuffer {
+    /// # Safety
+    ///
+    /// `page` must have been allocated from `pool`, with `len` usable bytes
+    /// reserved for the caller starting at byte `offset` within the page.
+    unsafe fn new(
+        pool: NonNull<bindings::page_pool>,
+        page: NonNull<bindings::page>,
+        offset: u32,
+        len: usize,
+    ) -> Self {
+        Self {
+            pool,
+            page,
+            offset,
+            len,
+        }
+    }
+
+    /// The bus address a device should use to access this buffer.
+    pub fn dma_address(&self) -> bindings::dma_addr_t {
+        // SAFETY: `self.page` was allocated by `page_pool_dev_alloc_pages`/
+        // `page_pool_alloc_frag`, which DMA-map the page whenever the pool
+        // was created with `PP_FLAG_DMA_MAP`, as [`PagePoolParams::new`]
+        // always requests.
+        let base = unsafe { bindings::page_pool_get_dma_addr(self.page.as_ptr()) };
+        base + bindings::dma_addr_t::from(self.offset)
+    }
+
+    /// Borrows the buffer's contents as a byte slice.
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: `self.page`'s kernel virtual address is valid for reads of
+        // `self.len` bytes starting at `self.offset`, for as long as `self`
+        // is alive.
+        unsafe {
+            let base: *const u8 = bindings::page_address(self.page.as_ptr()).cast();
+            core::slice::from_raw_parts(base.add(self.offset as usize), self.len)
+        }
+    }
+
+    /// Mutably borrows the buffer's contents as a byte slice.
+    pub fn as_slice_mut(&mut self) -> &mut [u8] {
+        // SAFETY: as above, plus `&mut self` guarantees exclusive access.
+        unsafe {
+            let base: *mut u8 = bindings::page_address(self.page.as_ptr()).cast();
+            core::slice::from_raw_parts_mut(base.add(self.offset as usize), self.len)
+        }
+    }
+
+    /// Consumes `self` without recycling its page, returning the raw page
+    /// and its reserved region.
+    ///
+    /// This is the "give to the kernel" half of the pool's ownership
+    /// transfer: the page is not recycled until whatever the caller builds
+    /// around it (e.g. an skb) is itself freed.
+    fn into_raw(self) -> (NonNull<bindings::page>, u32, usize) {
+        let raw = (self.page, self.offset, self.len);
+        core::mem::forget(self);
+        raw
+    }
+}
+
+impl Drop for PoolBuffer {
+    fn drop(&mut self) {
+        // SAFETY: `self.page` was allocated from `self.pool` and has not
+        // been handed off elsewhere: `into_raw` would have forgotten `self`
+        // instead of reaching this destructor. Passing `false` for
+        // `allow_direct` is always sound; it only disables an optimization
+        // that assumes the caller is the CPU that allocated the page.
+        unsafe {
+            bindings::page_pool_put_page(
+                self.pool.as_ptr(),
+                self.page.as_ptr(),
+                self.len as i32,
+                false,
+            );
+        }
+    }
+}
+
+// SAFETY: a `PoolBuffer` uniquely owns its backing page until dropped or
+// consumed by `into_raw`; the pool itself handles cross-CPU recycling.
+unsafe impl Send for PoolBuffer {}
+
+impl super::SkBuff {
+    /// Builds an skb directly around a page-pool buffer, without copying.
+    ///
+    /// `headroom` bytes at the front of the buffer are reserved for
+    /// protocol headers to be pushed later, and `len` bytes of payload
+    /// already written past that headroom are exposed as the skb's data.
+    ///
+    /// On success, `buf`'s page is owned by the returned skb and is
+    /// recycled back to its pool when that skb is eventually freed, instead
+    /// of going through [`PoolBuffer`]'s own `Drop`. On failure `buf` is
+    /// dropped normally, recycling its page immediately.
+    ///
+    /// Wraps `napi_build_skb`. Returns `None` if `headroom + len` doesn't fit within `buf`.
+    pub fn build_around(buf: PoolBuffer, len: usize, headroom: usize) -> Option<Self> {
+        let total = headroom.checked_add(len)?;
+        if total > buf.len {
+            return None;
+        }
+
+        // SAFETY: `buf`'s backing page is valid for reads and writes of
+        // `buf.len` bytes at `data` for as long as `buf` is alive, which
+        // outlasts this call.
+        let data = unsafe {
+            bindings::page_address(buf.page.as_ptr())
+                .cast::<u8>()
+                .add(buf.offset as usize)
+        };
+
+        // SAFETY: `data` points at `buf.len` writable bytes that
+        // `napi_build_skb` is free to lay an `skb_shared_info` out over.
+        let skb = unsafe { bindings::napi_build_skb(data.cast(), buf.len as u32) };
+        if skb.is_null() {
+            return None;
+        }
+
+        // The skb now owns the page and will recycle it on free, so `buf`
+        // must not run its own `Drop`.
+        let _ = buf.into_raw();
+
+        // SAFETY: `skb` was just built by `napi_build_skb` above and is
+        // solely owned by the caller through the returned `SkBuff`.
+        let this = unsafe { Self::from_pointer(skb) };
+
+        // SAFETY: `headroom + len` was checked above to fit within the
+        // `buf.len` bytes `skb` was built around above.
+        unsafe {
+            rust_helper_skb_reserve(skb, headroom as i32);
+            rust_helper_skb_put(skb, len as u32);
+        }
+
+        // Mark the skb so that freeing it hands the page back to its pool
+        // rather than to the page allocator.
+        // SAFETY: `skb` was just built above and is solely owned by `this`.
+        unsafe { (*skb).set_pp_recycle(1) };
+
+        Some(this)
+    }
+}
@@ -8,7 +8,18 @@
 use crate::net::tcp::{self, InetConnectionSock, TcpSock};
 use crate::types::Opaque;
 use core::convert::TryFrom;
-use core::ptr::addr_of;
+use core::ptr::{addr_of, addr_of_mut};
+
+extern "C" {
+    #[allow(improper_ctypes)]
+    fn rust_helper_sock_net(sk: *const bindings::sock) -> *mut bindings::net;
+
+    #[allow(improper_ctypes)]
+    fn rust_helper_NET_ADD_STATS(net: *mut bindings::net, field: u32, val: i64);
+
+    #[allow(improper_ctypes)]
+    fn rust_helper___NET_ADD_STATS(net: *mut bindings::net, field: u32, val: i64);
+}
 
 /// Representation of a C `struct sock`.
 ///
@@ -61,6 +72,36 @@ impl Sock {
         unsafe { *addr_of!((*self.sk.get()).sk_gso_max_size) as u32 }
     }
 
+    /// Sets the sockets pacing rate in bytes per second.
+    #[inline]
+    pub(crate) fn set_sk_pacing_rate(&mut self, rate: u64) {
+        // NOTE: C uses WRITE_ONCE for this field, thus `write_volatile`.
+        // SAFETY: The struct invariant ensures that we may access
+        // this field without additional synchronization. Values that do not
+        // fit in the C unsigned long are truncated.
+        unsafe { addr_of_mut!((*self.sk.get()).sk_pacing_rate).write_volatile(rate as _) };
+    }
+
+    /// Requests that the socket use at least `status` for pacing.
+    ///
+    /// Does nothing if the socket is already using a pacing mechanism at
+    /// least as strong, so that e.g. a [`Pacing::Fq`] set up by the route
+    /// cannot be downgraded back to [`Pacing::Needed`].
+    #[inline]
+    pub(crate) fn request_pacing_status(&mut self, status: Pacing) {
+        let is_already_strong_enough = match self.sk_pacing_status() {
+            Ok(cur) => cur as u32 >= status as u32,
+            Err(()) => false,
+        };
+        if is_already_strong_enough {
+            return;
+        }
+
+        // SAFETY: The struct invariant ensures that we may access
+        // this field without additional synchronization.
+        unsafe { *addr_of_mut!((*self.sk.get()).sk_pacing_status) = status as _ };
+    }
+
     /// Returns the [`TcpSock`] that is containing the `Sock`.
     ///
     /// # Safety
@@ -156,6 +197,33 @@ impl Sock {
         // precondition.
         unsafe { bindings::tcp_is_cwnd_limited(self.sk.get()) }
     }
+
+    /// Returns the network namespace this socket belongs to.
+    #[inline]
+    fn net(&self) -> *mut bindings::net {
+        // SAFETY: The struct invariant ensures that we may access this
+        // field without additional synchronization.
+        unsafe { rust_helper_sock_net(self.sk.get()) }
+    }
+
+    /// Adds `val` to the SNMP/MIB counter `field` (a `LINUX_MIB_*` constant)
+    /// in this socket's network namespace, as seen in `/proc/net/netstat`.
+    /// Safe to call from any context.
+    #[inline]
+    pub(crate) fn net_add_stats(&self, field: u32, val: i64) {
+        // SAFETY: `self.net()` always returns a valid `struct net`.
+        unsafe { rust_helper_NET_ADD_STATS(self.net(), field, val) };
+    }
+
+    /// Like [`net_add_stats`](Self::net_add_stats), but assumes the caller
+    /// has already disabled BH, as is always the case in the congestion
+    /// control callbacks.
+    #[inline]
+    pub(crate) fn net_add_stats_bh(&self, field: u32, val: i64) {
+        // SAFETY: `self.net()` always returns a valid `struct net`, and by
+        // the function's precondition BH is already disabled.
+        unsafe { rust_helper___NET_ADD_STATS(self.net(), field, val) };
+    }
 }
 
 /// The socket's pacing status.
@@ -1,6 +1,17 @@
 // SPDX-License-Identifier: GPL-2.0-only
 
 //! Transmission Control Protocol (TCP).
+//!
+//! [`TcpSock`] exposes the primitives ([`TcpSock::in_slow_start`],
+//! [`TcpSock::slow_start`], [`TcpSock::cong_avoid_ai`], ...) that a
+//! congestion-control algorithm needs to grow and shrink cwnd. To ship a full
+//! algorithm as a loadable module, implement [`cong::Algorithm`] and register
+//! it with [`cong::Module`] (or the [`cong::module_cca!`] shorthand), which
+//! wraps `struct tcp_congestion_ops` and
+//! `tcp_register_congestion_control`/`tcp_unregister_congestion_control` for
+//! you.
+
+pub mod cong;
 
 use crate::time;
 use crate::types::Opaque;
@@ -112,6 +123,33 @@ impl TcpSock {
         unsafe { *ptr::addr_of_mut!((*self.tp.get()).snd_ssthresh) = new };
     }
 
+    /// Sets the connection's current cwnd.
+    #[inline]
+    pub fn set_snd_cwnd(&mut self, new: u32) {
+        // SAFETY: The struct invariant ensures that we may call this function
+        // without additional synchronization.
+        unsafe { bindings::tcp_snd_cwnd_set(self.tp.get(), new) };
+    }
+
+    /// Sets `snd_cwnd_cnt`, the number of ACKs received since cwnd was last
+    /// increased, as used by the standard congestion-avoidance accounting in
+    /// [`Self::cong_avoid_ai`].
+    #[inline]
+    pub fn set_snd_cwnd_cnt(&mut self, new: u32) {
+        // SAFETY: The struct invariant ensures that we may access
+        // this field without additional synchronization.
+        unsafe { *ptr::addr_of_mut!((*self.tp.get()).snd_cwnd_cnt) = new };
+    }
+
+    /// Returns the windowed minimum RTT observed on this connection, in
+    /// microseconds, the same value `ss -i`'s `minrtt` reports.
+    #[inline]
+    pub fn min_rtt(&self) -> time::Usecs32 {
+        // SAFETY: The struct invariant ensures that we may call this function
+        // without additional synchronization.
+        unsafe { bindings::tcp_min_rtt(self.tp.get()) }
+    }
+
     /// Returns the timestamp of the last send data packet in 32bit Jiffies.
     #[inline]
     pub fn lsndtime(&self) -> time::Jiffies32 {
@@ -119,6 +157,26 @@ impl TcpSock {
         // this field without additional synchronization.
         unsafe { *ptr::addr_of!((*self.tp.get()).lsndtime) as time::Jiffies32 }
     }
+
+    /// Returns the total number of bytes delivered so far that carried an ECN
+    /// congestion mark, the numerator a DCTCP-style algorithm divides by
+    /// bytes delivered to get the fraction of marked traffic.
+    #[inline]
+    pub fn delivered_ce(&self) -> u32 {
+        // SAFETY: The struct invariant ensures that we may access
+        // this field without additional synchronization.
+        unsafe { *ptr::addr_of!((*self.tp.get()).delivered_ce) }
+    }
+
+    /// Returns `rcv_nxt` as of the most recent congestion event, the
+    /// reference point a DCTCP-style algorithm sets its next averaging
+    /// window's boundary from.
+    #[inline]
+    pub fn prior_rcv_nxt(&self) -> u32 {
+        // SAFETY: The struct invariant ensures that we may access
+        // this field without additional synchronization.
+        unsafe { *ptr::addr_of!((*self.tp.get()).prior_rcv_nxt) }
+    }
 }
 
 /// Tests if `sqn_1` comes after `sqn_2`.
@@ -14,6 +14,7 @@ use crate::time;
 use crate::types::Opaque;
 use crate::ThisModule;
 use crate::{build_assert, build_error, field_size, try_pin_init};
+use core::cmp::{max, min};
 use core::convert::TryFrom;
 use core::marker::PhantomData;
 use core::pin::Pin;
@@ -22,6 +23,8 @@ use macros::{pin_data, pinned_drop};
 use super::{InetConnectionSock, TcpSock};
 
 pub mod hystart;
+pub mod minmax;
+pub mod param;
 
 /// Congestion control algorithm (CCA).
 ///
@@ -41,6 +44,15 @@ pub trait Algorithm {
     /// Name of the algorithm.
     const NAME: &'static CStr;
 
+    /// Flags to pass to `tcp_register_congestion_control`.
+    ///
+    /// In particular, [`Flags::NON_RESTRICTED`] is what allows the algorithm
+    /// to be selected by unprivileged sockets and, since BPF struct_ops for
+    /// `tcp_congestion_ops` reuses the very same registration, is also what
+    /// lets the algorithm be loaded and exercised as a `bpf_struct_ops` map
+    /// (e.g. via `bpftool struct_ops`) without extra plumbing on our side.
+    const FLAGS: Flags = Flags::empty();
+
     /// Called when entering CWR, Recovery, or Loss states from Open or Disorder
     /// states. Returns the new slow start threshold.
     fn ssthresh(sk: &mut Sock<'_, Self>) -> u32;
@@ -50,11 +62,35 @@ pub trait Algorithm {
         build_error!(VTABLE_DEFAULT_ERROR);
     }
 
+    /// Called for every received ACK, with `flags` describing it, e.g.
+    /// whether it carries an ECN congestion mark ([`AckEvent::ECE`]).
+    ///
+    /// Intended for CCAs that, like DCTCP, maintain a running estimate of
+    /// the fraction of marked ACKs rather than reacting to `set_state`
+    /// transitions alone.
+    fn in_ack_event(_sk: &mut Sock<'_, Self>, _flags: AckEvent) {
+        build_error!(VTABLE_DEFAULT_ERROR);
+    }
+
     /// Called towards the end of processing an ACK if a cwnd increase is
     /// possible. Performs a new cwnd calculation and sets it on the socket.
-    // Note: In fact, one of `cong_avoid` and `cond_control` is required.
-    // (see `tcp_validate_congestion_control`)
-    fn cong_avoid(sk: &mut Sock<'_, Self>, ack: u32, acked: u32);
+    ///
+    /// Not needed by rate-based algorithms that implement `cong_control`
+    /// instead, but one of the two is required (see
+    /// `tcp_validate_congestion_control`).
+    fn cong_avoid(_sk: &mut Sock<'_, Self>, _ack: u32, _acked: u32) {
+        build_error!(VTABLE_DEFAULT_ERROR);
+    }
+
+    /// Called instead of `cong_avoid` (when implemented) with a delivery-rate
+    /// [`RateSample`] for the ACK that was just processed.
+    ///
+    /// Intended for rate-based algorithms (e.g. BBR) that size cwnd and
+    /// pacing rate off of the estimated delivery rate rather than off of
+    /// cwnd-based ACK counting.
+    fn cong_control(_sk: &mut Sock<'_, Self>, _ack: u32, _sample: &RateSample) {
+        build_error!(VTABLE_DEFAULT_ERROR);
+    }
 
     /// Called before the sender's congestion state is changed.
     fn set_state(_sk: &mut Sock<'_, Self>, _new_state: State) {
@@ -84,6 +120,17 @@ pub trait Algorithm {
         build_error!(VTABLE_DEFAULT_ERROR);
     }
 
+    /// Exports the algorithm's private state to userspace, e.g. for
+    /// `ss --tcpinfo` / `INET_DIAG_INFO`.
+    ///
+    /// `attr` is the requested `INET_DIAG_*` attribute (currently always
+    /// `INET_DIAG_VEGASINFO`, the generic slot every CCA's info is reported
+    /// under). Returns the `AF_INET`/`AF_INET6` attribute type to report the
+    /// data under, after writing the data itself through `writer`.
+    fn get_info(_sk: &Sock<'_, Self>, _attr: u32, _writer: &mut InfoWriter<'_>) -> Option<u32> {
+        build_error!(VTABLE_DEFAULT_ERROR);
+    }
+
     /// Cleans up the private data.
     ///
     /// After this function returns, [`sk.inet_csk_ca()`] will be dropped.
@@ -120,6 +167,67 @@ pub mod reno {
     }
 }
 
+/// Flags understood by `tcp_register_congestion_control`.
+#[derive(Clone, Copy)]
+pub struct Flags(u32);
+
+impl Flags {
+    /// No flags set.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Allows the algorithm to be selected by unprivileged sockets, e.g. via
+    /// `setsockopt(TCP_CONGESTION)`. Also required for the algorithm's
+    /// `tcp_congestion_ops` to be attachable as a BPF struct_ops map.
+    pub const NON_RESTRICTED: Self = Self(bindings::TCP_CONG_NON_RESTRICTED);
+
+    /// Informs TCP that the algorithm needs ECN to be negotiated on the
+    /// connection.
+    pub const NEEDS_ECN: Self = Self(bindings::TCP_CONG_NEEDS_ECN);
+
+    pub(crate) const fn bits(self) -> u32 {
+        self.0
+    }
+}
+
+impl core::ops::BitOr for Flags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Flags describing an ACK, passed to [`Algorithm::in_ack_event`].
+#[derive(Clone, Copy)]
+pub struct AckEvent(u32);
+
+impl AckEvent {
+    /// The ACK was a duplicate or otherwise processed on the slow path.
+    pub const SLOWPATH: Self = Self(bindings::CA_ACK_SLOWPATH);
+
+    /// The ACK advertised a new receive window.
+    pub const WIN_UPDATE: Self = Self(bindings::CA_ACK_WIN_UPDATE);
+
+    /// The ACK carried an ECN congestion mark (ECE).
+    pub const ECE: Self = Self(bindings::CA_ACK_ECE);
+
+    /// Tests whether `self` contains all bits set in `other`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for AckEvent {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
 /// Representation of the `struct sock *` that is passed to the callbacks of the
 /// CCA.
 ///
@@ -246,6 +354,61 @@ impl<'a, T: Algorithm + ?Sized> Sock<'a, T> {
     pub fn sk_gso_max_size(&self) -> u32 {
         self.sk.sk_gso_max_size()
     }
+
+    /// Sets the sockets pacing rate in bytes per second.
+    #[inline]
+    pub fn set_sk_pacing_rate(&mut self, rate: u64) {
+        self.sk.set_sk_pacing_rate(rate)
+    }
+
+    /// Requests that the socket use at least `status` for pacing.
+    #[inline]
+    pub fn request_pacing_status(&mut self, status: sock::Pacing) {
+        self.sk.request_pacing_status(status)
+    }
+
+    /// Bounds the number of segments a single TSO/GSO skb should carry,
+    /// given the current pacing rate, mirroring the logic of the C
+    /// `tcp_tso_segs`/`tcp_tso_autosize`.
+    ///
+    /// `mss_now` is the current MSS (see `tcp_current_mss`); `min_segs` is
+    /// the minimum number of segments to return regardless of pacing rate,
+    /// e.g. `1` or `2`.
+    #[inline]
+    pub fn pacing_tso_segs(&self, mss_now: u32, min_segs: u32) -> u32 {
+        let bytes = min(self.sk_pacing_rate() >> 10, self.sk_gso_max_size() as u64);
+        max(min_segs, (bytes / mss_now as u64) as u32)
+    }
+
+    /// Adds `val` to the given SNMP/MIB counter (`/proc/net/netstat`) in the
+    /// socket's network namespace. Safe to call from any context.
+    #[inline]
+    pub fn add_mib_stat(&self, field: MibField, val: i64) {
+        self.sk.net_add_stats(field as u32, val)
+    }
+
+    /// Like [`add_mib_stat`](Self::add_mib_stat), but assumes BH is already
+    /// disabled, which holds for every callback of [`Algorithm`].
+    #[inline]
+    pub fn add_mib_stat_bh(&self, field: MibField, val: i64) {
+        self.sk.net_add_stats_bh(field as u32, val)
+    }
+}
+
+/// SNMP/MIB counters (`/proc/net/netstat`) that [`hystart::HyStart::update`]
+/// bumps on a slow-start exit, via [`Sock::add_mib_stat_bh`].
+#[repr(u32)]
+pub enum MibField {
+    /// The ACK-train heuristic detected a slow-start exit
+    /// (`LINUX_MIB_TCPHYSTARTTRAINDETECT`).
+    HystartTrainDetect = bindings::LINUX_MIB_TCPHYSTARTTRAINDETECT,
+    /// cwnd at an ACK-train exit (`LINUX_MIB_TCPHYSTARTTRAINCWND`).
+    HystartTrainCwnd = bindings::LINUX_MIB_TCPHYSTARTTRAINCWND,
+    /// The delay heuristic detected a slow-start exit
+    /// (`LINUX_MIB_TCPHYSTARTDELAYDETECT`).
+    HystartDelayDetect = bindings::LINUX_MIB_TCPHYSTARTDELAYDETECT,
+    /// cwnd at a delay-based exit (`LINUX_MIB_TCPHYSTARTDELAYCWND`).
+    HystartDelayCwnd = bindings::LINUX_MIB_TCPHYSTARTDELAYCWND,
 }
 
 /// Representation of the `struct ack_sample *` that is passed to the
@@ -289,6 +452,148 @@ impl AckSample {
             t => Some(t as time::Usecs32),
         }
     }
+
+    /// Returns the number of packets in flight before this ACK was processed.
+    #[inline]
+    pub fn in_flight(&self) -> u32 {
+        // SAFETY: By the type invariants it is OK to read any field.
+        unsafe { (*self.sample).in_flight }
+    }
+}
+
+/// A write-once destination for [`Algorithm::get_info`], standing in for the
+/// `(union tcp_cc_info *, int *attr, size_t)` arguments `tcp_get_info` passes
+/// to a C `get_info` callback.
+///
+/// Algorithms that want to be inspectable with `ss -i` / `TCP_CC_INFO` build
+/// a `#[repr(C)]` struct matching one of the kernel's `tcp_*_info` layouts
+/// (e.g. `tcp_bbr_info`) and hand it to [`Self::write`], which takes care of
+/// not overrunning the fixed-size buffer the kernel provided.
+pub struct InfoWriter<'a> {
+    buf: &'a mut [u8],
+    written: usize,
+}
+
+impl<'a> InfoWriter<'a> {
+    /// Creates a new `InfoWriter` over `buf`.
+    ///
+    /// # Safety
+    ///
+    /// `buf` must have been obtained as the destination buffer passed to the
+    /// `get_info` callback.
+    unsafe fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, written: 0 }
+    }
+
+    /// Copies `value`'s representation into the destination buffer.
+    ///
+    /// Fails, writing nothing, if `value` doesn't fit in the space the
+    /// kernel provided.
+    pub fn write<D: Copy>(&mut self, value: &D) -> Option<()> {
+        let size = core::mem::size_of::<D>();
+        if size > self.buf.len() {
+            return None;
+        }
+
+        // SAFETY: `D: Copy`, so every byte of `value` is initialized, and
+        // `size <= self.buf.len()` was just checked.
+        let src = unsafe { core::slice::from_raw_parts((value as *const D).cast::<u8>(), size) };
+        self.buf[..size].copy_from_slice(src);
+        self.written = size;
+        Some(())
+    }
+
+    /// Returns the number of bytes written so far.
+    fn len(&self) -> usize {
+        self.written
+    }
+}
+
+/// Representation of the `struct rate_sample *` that is passed to the
+/// `cong_control` callback of the CCA.
+///
+/// # Invariants
+///
+/// - `sample` points to a valid `struct rate_sample`,
+/// - all fields of `sample` can be read without additional synchronization.
+pub struct RateSample {
+    sample: *const bindings::rate_sample,
+}
+
+impl RateSample {
+    /// Creates a new `RateSample`.
+    ///
+    /// # Safety
+    ///
+    /// `sample` must have been obtained as the argument to the
+    /// `cong_control` callback.
+    unsafe fn new(sample: *const bindings::rate_sample) -> Self {
+        // INVARIANTS: Satisfied by the function's precondition.
+        Self { sample }
+    }
+
+    /// Returns the length of the sampling interval, in microseconds.
+    #[inline]
+    pub fn interval_us(&self) -> i64 {
+        // SAFETY: By the type invariants it is OK to read any field.
+        unsafe { (*self.sample).interval_us }
+    }
+
+    /// Returns the number of packets newly delivered over `interval_us`.
+    #[inline]
+    pub fn delivered(&self) -> i32 {
+        // SAFETY: By the type invariants it is OK to read any field.
+        unsafe { (*self.sample).delivered }
+    }
+
+    /// Returns the RTT of the most recently ACKed packet, if known.
+    #[inline]
+    pub fn rtt_us(&self) -> Option<time::Usecs32> {
+        // SAFETY: By the type invariants it is OK to read any field.
+        match unsafe { (*self.sample).rtt_us } {
+            t if t < 0 => None,
+            t => Some(t as time::Usecs32),
+        }
+    }
+
+    /// Returns the number of packets newly marked lost over `interval_us`.
+    #[inline]
+    pub fn losses(&self) -> u32 {
+        // SAFETY: By the type invariants it is OK to read any field.
+        unsafe { (*self.sample).losses }
+    }
+
+    /// Returns the number of packets newly ACKed or SACKed over
+    /// `interval_us`.
+    #[inline]
+    pub fn acked_sacked(&self) -> i32 {
+        // SAFETY: By the type invariants it is OK to read any field.
+        unsafe { (*self.sample).acked_sacked }
+    }
+
+    /// Returns the number of packets in flight before this sample.
+    #[inline]
+    pub fn prior_in_flight(&self) -> u32 {
+        // SAFETY: By the type invariants it is OK to read any field.
+        unsafe { (*self.sample).prior_in_flight }
+    }
+
+    /// Returns the value of the delivered-packets counter before this sample,
+    /// i.e. the baseline that [`Self::delivered`] was measured from.
+    #[inline]
+    pub fn prior_delivered(&self) -> u32 {
+        // SAFETY: By the type invariants it is OK to read any field.
+        unsafe { (*self.sample).prior_delivered }
+    }
+
+    /// Tests whether the sample may have been limited by the application
+    /// rather than by the network, in which case it underestimates the true
+    /// delivery rate.
+    #[inline]
+    pub fn is_app_limited(&self) -> bool {
+        // SAFETY: By the type invariants it is OK to read any field.
+        unsafe { (*self.sample).is_app_limited() != 0 }
+    }
 }
 
 /// States of the TCP sender state machine.
@@ -404,17 +709,31 @@ impl<T: Algorithm + ?Sized> Registration<T> {
                 let ops = unsafe { &mut *ops_ptr };
 
                 ops.ssthresh = Some(Self::ssthresh_cb);
-                ops.cong_avoid = Some(Self::cong_avoid_cb);
                 ops.undo_cwnd = Some(Self::undo_cwnd_cb);
+                // `tcp_validate_congestion_control` rejects a registration with
+                // neither op, so catch that misuse at build time instead.
+                build_assert!(T::HAS_CONG_AVOID || T::HAS_CONG_CONTROL);
+                if T::HAS_CONG_AVOID {
+                    ops.cong_avoid = Some(Self::cong_avoid_cb);
+                }
+                if T::HAS_CONG_CONTROL {
+                    ops.cong_control = Some(Self::cong_control_cb);
+                }
                 if T::HAS_SET_STATE {
                     ops.set_state = Some(Self::set_state_cb);
                 }
                 if T::HAS_PKTS_ACKED {
                     ops.pkts_acked = Some(Self::pkts_acked_cb);
                 }
+                if T::HAS_GET_INFO {
+                    ops.get_info = Some(Self::get_info_cb);
+                }
                 if T::HAS_CWND_EVENT {
                     ops.cwnd_event = Some(Self::cwnd_event_cb);
                 }
+                if T::HAS_IN_ACK_EVENT {
+                    ops.in_ack_event = Some(Self::in_ack_event_cb);
+                }
 
                 // Even though it is not mandated by the C side, we
                 // unconditionally set these CBs to ensure that it is always
@@ -426,6 +745,7 @@ impl<T: Algorithm + ?Sized> Registration<T> {
 
                 ops.owner = module.0;
                 ops.name = Self::NAME_FIELD;
+                ops.flags = T::FLAGS.bits();
 
                 // SAFETY: Pointers stored in `ops` are static so they will live
                 // for as long as the registration is active (it is undone in
@@ -460,6 +780,16 @@ impl<T: Algorithm + ?Sized> Registration<T> {
         }
     }
 
+    unsafe extern "C" fn in_ack_event_cb(sk: *mut bindings::sock, flags: u32) {
+        // SAFETY:
+        // - `sk` was passed to a callback of the CCA `T`.
+        // - `Data` is guaranteed to be initialized since the `init_cb` took
+        //   care of it.
+        // - This value will be dropped at the end of the callback.
+        let mut sk = unsafe { Sock::new(sk) };
+        T::in_ack_event(&mut sk, AckEvent(flags))
+    }
+
     unsafe extern "C" fn init_cb(sk: *mut bindings::sock) {
         // Fail the build if the module-defined private data is larger than the
         // storage that the kernel provides.
@@ -524,6 +854,23 @@ impl<T: Algorithm + ?Sized> Registration<T> {
         T::cong_avoid(&mut sk, ack, acked)
     }
 
+    unsafe extern "C" fn cong_control_cb(
+        sk: *mut bindings::sock,
+        ack: u32,
+        _flag: i32,
+        sample: *const bindings::rate_sample,
+    ) {
+        // SAFETY:
+        // - `sk` was passed to a callback of the CCA `T`.
+        // - `Data` is guaranteed to be initialized since the `init_cb` took
+        //   care of it.
+        // - This value will be dropped at the end of the callback.
+        let mut sk = unsafe { Sock::new(sk) };
+        // SAFETY: `sample` points to a valid `struct rate_sample`.
+        let sample = unsafe { RateSample::new(sample) };
+        T::cong_control(&mut sk, ack, &sample)
+    }
+
     unsafe extern "C" fn set_state_cb(sk: *mut bindings::sock, new_state: u8) {
         // SAFETY:
         // - `sk` was passed to a callback of the CCA `T`.
@@ -553,6 +900,40 @@ impl<T: Algorithm + ?Sized> Registration<T> {
         T::pkts_acked(&mut sk, &sample)
     }
 
+    unsafe extern "C" fn get_info_cb(
+        sk: *mut bindings::sock,
+        attr: u32,
+        attr_out: *mut i32,
+        info: *mut bindings::tcp_cc_info,
+    ) -> usize {
+        // SAFETY:
+        // - `sk` was passed to a callback of the CCA `T`.
+        // - `Data` is guaranteed to be initialized since the `init_cb` took
+        //   care of it.
+        // - This value will be dropped at the end of the callback.
+        let sk = unsafe { Sock::new(sk) };
+        // SAFETY: `info` is valid for writes of `size_of::<tcp_cc_info>()`
+        // bytes, as guaranteed by the caller of this callback.
+        let buf = unsafe {
+            core::slice::from_raw_parts_mut(
+                info as *mut u8,
+                core::mem::size_of::<bindings::tcp_cc_info>(),
+            )
+        };
+        // SAFETY: `buf` is the destination buffer passed to this callback.
+        let mut writer = unsafe { InfoWriter::new(buf) };
+
+        match T::get_info(&sk, attr, &mut writer) {
+            Some(ty) => {
+                // SAFETY: `attr_out` is valid for writes, as guaranteed by
+                // the caller of this callback.
+                unsafe { *attr_out = ty as i32 };
+                writer.len()
+            }
+            None => 0,
+        }
+    }
+
     unsafe extern "C" fn undo_cwnd_cb(sk: *mut bindings::sock) -> u32 {
         // SAFETY:
         // - `sk` was passed to a callback of the CCA `T`.
@@ -634,8 +1015,37 @@ impl<T: Algorithm + ?Sized + Sync + Send> crate::InPlaceModule for Module<T> {
 ///     license: "GPL v2",
 /// }
 /// ```
+///
+/// A CCA with tunables can declare them inline, right after `type:`, instead
+/// of calling [`declare_params!`](crate::declare_params) separately. Each parameter becomes a
+/// [`param::Param`] static, visible and writable under
+/// `/sys/module/my_cca/parameters/`, the same way `module_param()` works for
+/// a C CCA (e.g. CUBIC's `beta`, DCTCP's `dctcp_shift_g`):
+///
+/// ```ignore
+/// module_cca! {
+///     type: MyCca,
+///     params: {
+///         /// Weight given to the most recent RTT sample, in 1/1024ths.
+///         pub static SHIFT_G: Param = Param::new(4), perm: 0o644;
+///     }
+///     name: "my_cca",
+///     author: "Rust for Linux Contributors",
+///     description: "Sample congestion control algorithm implemented in Rust.",
+///     license: "GPL v2",
+/// }
+/// ```
 #[macro_export]
 macro_rules! module_cca {
+    (type: $type:ty, params: { $($params:tt)* } $($f:tt)*) => {
+        $crate::declare_params! { $($params)* }
+
+        type ModuleType = $crate::net::tcp::cong::Module<$type>;
+        $crate::macros::module! {
+            type: ModuleType,
+            $($f)*
+        }
+    };
     (type: $type:ty, $($f:tt)*) => {
         type ModuleType = $crate::net::tcp::cong::Module<$type>;
         $crate::macros::module! {
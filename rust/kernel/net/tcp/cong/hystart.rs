@@ -10,9 +10,11 @@
 
 use crate::net::sock;
 use crate::net::tcp::{self, cong};
+use crate::net::tcp::cong::param::Param;
 use crate::time;
 use crate::{pr_err, pr_info};
 use core::cmp::min;
+use core::convert::TryFrom;
 
 /// The heuristic that is used to find the exit point for slow start.
 pub enum HystartDetect {
@@ -24,8 +26,64 @@ pub enum HystartDetect {
     Delay = 2,
     /// Combine both algorithms.
     Both = 3,
+    /// HyStart++ (RFC 9406): like [`Delay`](Self::Delay), but a round whose
+    /// minimum RTT crosses the threshold enters a Conservative Slow Start
+    /// phase instead of exiting slow start outright, so a transient RTT
+    /// spike doesn't clamp `ssthresh` prematurely. Only exits slow start once
+    /// the RTT increase has persisted for [`CSS_ROUNDS`] consecutive rounds.
+    Css = 4,
 }
 
+impl TryFrom<u32> for HystartDetect {
+    type Error = ();
+
+    fn try_from(val: u32) -> Result<Self, Self::Error> {
+        match val {
+            x if x == Self::AckTrain as u32 => Ok(Self::AckTrain),
+            x if x == Self::Delay as u32 => Ok(Self::Delay),
+            x if x == Self::Both as u32 => Ok(Self::Both),
+            x if x == Self::Css as u32 => Ok(Self::Css),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Number of per-round RTT samples [`HyStart::update`] collects before
+/// evaluating the Conservative Slow Start entry condition (RFC 9406 §4.2
+/// `N_RTT_SAMPLE`).
+const N_RTT_SAMPLE: u8 = 8;
+/// Divides `last_round_min_rtt` to get the base of the CSS entry threshold,
+/// before clamping (RFC 9406 §4.2 `MIN_RTT_DIVISOR`).
+const MIN_RTT_DIVISOR: u32 = 8;
+/// Lower bound on the CSS entry threshold (RFC 9406 §4.2 `MIN_RTT_THRESH`).
+const MIN_RTT_THRESH: time::Usecs32 = 4000;
+/// Upper bound on the CSS entry threshold (RFC 9406 §4.2 `MAX_RTT_THRESH`).
+const MAX_RTT_THRESH: time::Usecs32 = 16000;
+/// Divides the slow-start cwnd growth rate while [`HyStartState::in_css`]
+/// (RFC 9406 §4.2 `CSS_GROWTH_DIVISOR`).
+pub const CSS_GROWTH_DIVISOR: u32 = 4;
+/// Number of consecutive rounds Conservative Slow Start must persist for
+/// before it is treated as a genuine slow-start exit rather than transient
+/// jitter (RFC 9406 §4.2 `CSS_ROUNDS`).
+const CSS_ROUNDS: u8 = 5;
+
+/// The CSS entry threshold: how much `curr_round_min_rtt` must exceed
+/// `last_round_min_rtt` by before [`HyStart::update`] enters Conservative
+/// Slow Start instead of exiting slow start outright.
+fn css_rtt_thresh(last_round_min_rtt: time::Usecs32) -> time::Usecs32 {
+    (last_round_min_rtt / MIN_RTT_DIVISOR).clamp(MIN_RTT_THRESH, MAX_RTT_THRESH)
+}
+
+/// Shared defaults for [`HyStart::MIN_SAMPLES`]/[`DELAY_MIN`]/[`DELAY_MAX`],
+/// for CCAs that don't need to expose those particular knobs under their own
+/// `/sys/module/<name>/parameters/`.
+///
+/// [`DELAY_MIN`]: HyStart::DELAY_MIN
+/// [`DELAY_MAX`]: HyStart::DELAY_MAX
+static DEFAULT_MIN_SAMPLES: Param = Param::new(8);
+static DEFAULT_DELAY_MIN: Param = Param::new(4000);
+static DEFAULT_DELAY_MAX: Param = Param::new(16000);
+
 /// Internal state of the [`HyStart`] algorithm.
 pub struct HyStartState {
     /// Number of ACKs already sampled to determine the RTT of this round.
@@ -45,6 +103,21 @@ pub struct HyStartState {
     /// Time when the connection was created.
     // TODO: remove
     pub start_time: time::Usecs32,
+    /// The minimum RTT observed during the previous round. Used by
+    /// [`HystartDetect::Css`] to size the entry threshold for the current
+    /// round. `u32::MAX` until a full round has completed.
+    last_round_min_rtt: time::Usecs32,
+    /// The minimum RTT observed so far in the current round, as tracked by
+    /// [`HystartDetect::Css`]. `u32::MAX` until at least one sample has been
+    /// taken.
+    curr_round_min_rtt: time::Usecs32,
+    /// The `curr_round_min_rtt` recorded when Conservative Slow Start was
+    /// entered. A later round whose minimum RTT drops back below this is
+    /// treated as a false positive and aborts CSS.
+    css_baseline_min_rtt: time::Usecs32,
+    /// Number of consecutive rounds spent in Conservative Slow Start so far.
+    /// `0` means CSS is not active.
+    css_rounds: u8,
 }
 
 impl Default for HyStartState {
@@ -59,6 +132,10 @@ impl Default for HyStartState {
             delay_min: None,
             // TODO: remove
             start_time: time::ktime_get_boot_fast_us32(),
+            last_round_min_rtt: u32::MAX,
+            curr_round_min_rtt: u32::MAX,
+            css_baseline_min_rtt: u32::MAX,
+            css_rounds: 0,
         }
     }
 }
@@ -67,7 +144,31 @@ impl HyStartState {
     /// Returns true iff the algorithm `T` is in hybrid slow start.
     #[inline]
     pub fn in_hystart<T: HyStart>(&self, cwnd: u32) -> bool {
-        !self.found && cwnd >= T::LOW_WINDOW
+        !self.found && cwnd >= T::LOW_WINDOW.get()
+    }
+
+    /// Returns true iff the algorithm is currently in Conservative Slow
+    /// Start (HyStart++, RFC 9406 §4.2): the per-round minimum RTT heuristic
+    /// fired, but hasn't yet persisted for long enough to confirm a genuine
+    /// slow-start exit.
+    #[inline]
+    pub fn in_css(&self) -> bool {
+        self.css_rounds > 0
+    }
+
+    /// The divisor [`Algorithm::cong_avoid`] should apply to the normal
+    /// slow-start cwnd increase: [`CSS_GROWTH_DIVISOR`] while [`in_css`],
+    /// `1` otherwise.
+    ///
+    /// [`Algorithm::cong_avoid`]: cong::Algorithm::cong_avoid
+    /// [`in_css`]: Self::in_css
+    #[inline]
+    pub fn css_growth_divisor(&self) -> u32 {
+        if self.in_css() {
+            CSS_GROWTH_DIVISOR
+        } else {
+            1
+        }
     }
 }
 
@@ -89,27 +190,35 @@ pub trait HasHyStartState {
 /// [`reset`]: HyStart::reset
 /// [`update`]: HyStart::update
 pub trait HyStart: cong::Algorithm<Data: HasHyStartState> {
-    // TODO: Those constants should be configurable via module parameters.
-    /// Which heuristic to use for deciding when it is time to exit slow start.
-    const DETECT: HystartDetect;
-
+    /// Which heuristic to use for deciding when it is time to exit slow
+    /// start. Stores a [`HystartDetect`] discriminant; an unrecognised value
+    /// (e.g. written through sysfs) falls back to [`HystartDetect::Both`].
+    /// Point this at a `static Param` declared with [`declare_params!`] so
+    /// administrators can retune or disable the heuristic without
+    /// recompiling.
+    ///
+    /// [`declare_params!`]: super::param::declare_params
+    const DETECT: &'static Param;
     /// Lower bound for cwnd during hybrid slow start.
-    const LOW_WINDOW: u32;
-
+    const LOW_WINDOW: &'static Param;
     /// Max spacing between ACKs in an ACK-train.
-    const ACK_DELTA: time::Usecs32;
-
-    /// Number of ACKs to sample at the beginning of each round to estimate the
-    /// RTT of this round.
-    const MIN_SAMPLES: u8 = 8;
-
-    /// Lower bound on the increase in RTT between to consecutive rounds that is
-    /// needed to trigger an exit from slow start.
-    const DELAY_MIN: time::Usecs32 = 4000;
-
-    /// Upper bound on the increase in RTT between to consecutive rounds that is
-    /// needed to trigger an exit from slow start.
-    const DELAY_MAX: time::Usecs32 = 16000;
+    const ACK_DELTA: &'static Param;
+    /// Number of ACKs to sample at the beginning of each round to estimate
+    /// the RTT of this round.
+    const MIN_SAMPLES: &'static Param = &DEFAULT_MIN_SAMPLES;
+    /// Lower bound on the increase in RTT between two consecutive rounds
+    /// that is needed to trigger an exit from slow start.
+    const DELAY_MIN: &'static Param = &DEFAULT_DELAY_MIN;
+    /// Upper bound on the increase in RTT between two consecutive rounds
+    /// that is needed to trigger an exit from slow start.
+    const DELAY_MAX: &'static Param = &DEFAULT_DELAY_MAX;
+
+    /// The effective [`HystartDetect`], falling back to
+    /// [`HystartDetect::Both`] if [`DETECT`](Self::DETECT) was written to an
+    /// unrecognised value through sysfs.
+    fn detect() -> HystartDetect {
+        HystartDetect::try_from(Self::DETECT.get()).unwrap_or(HystartDetect::Both)
+    }
 
     /// Corresponds to the function eta from the paper. Returns the increase in
     /// RTT between consecutive rounds that triggers and exit from slow start.
@@ -117,16 +226,26 @@ pub trait HyStart: cong::Algorithm<Data: HasHyStartState> {
     fn delay_thresh(mut t: time::Usecs32) -> time::Usecs32 {
         t >>= 3;
 
-        if t < Self::DELAY_MIN {
-            Self::DELAY_MIN
-        } else if t > Self::DELAY_MAX {
-            Self::DELAY_MAX
+        let delay_min = Self::DELAY_MIN.get();
+        let delay_max = Self::DELAY_MAX.get();
+
+        if t < delay_min {
+            delay_min
+        } else if t > delay_max {
+            delay_max
         } else {
             t
         }
     }
 
-    /// TODO
+    /// Estimates how much the pacing engine spreads out the ACKs of a single
+    /// flight, in addition to the path's own delay.
+    ///
+    /// Pacing deliberately spaces packets (and thus their ACKs) out over
+    /// time, which widens the gaps the ACK-train heuristic measures. Without
+    /// accounting for this, a well-paced, high-bandwidth flow can trip the
+    /// ACK-train exit condition well before the link is actually saturated.
+    /// Returns `0` when the socket isn't pacing.
     fn ack_delay(sk: &cong::Sock<'_, Self>) -> time::Usecs32 {
         (match sk.sk_pacing_rate() {
             0 => 0,
@@ -150,6 +269,7 @@ pub trait HyStart: cong::Algorithm<Data: HasHyStartState> {
         hy.end_seq = snd_nxt;
         hy.curr_rtt = u32::MAX;
         hy.sample_cnt = 0;
+        hy.curr_round_min_rtt = u32::MAX;
     }
 
     /// Called in slow start to decide if it is time to exit slow start. Sets
@@ -157,6 +277,31 @@ pub trait HyStart: cong::Algorithm<Data: HasHyStartState> {
     fn update(sk: &mut cong::Sock<'_, Self>, delay: time::Usecs32) {
         // Start of a new round.
         if tcp::after(sk.tcp_sk().snd_una(), sk.inet_csk_ca().hy().end_seq) {
+            if matches!(Self::detect(), HystartDetect::Css) {
+                let hy = sk.inet_csk_ca().hy();
+                let round_min_rtt = hy.curr_round_min_rtt;
+                let css_rounds = hy.css_rounds;
+                let css_baseline_min_rtt = hy.css_baseline_min_rtt;
+
+                if css_rounds > 0 {
+                    if round_min_rtt < css_baseline_min_rtt {
+                        // The RTT came back down: this was a false positive,
+                        // resume normal slow start.
+                        sk.inet_csk_ca_mut().hy_mut().css_rounds = 0;
+                    } else if css_rounds >= CSS_ROUNDS {
+                        let cwnd = sk.tcp_sk().snd_cwnd();
+                        sk.tcp_sk_mut().set_snd_ssthresh(cwnd);
+                        sk.inet_csk_ca_mut().hy_mut().found = true;
+                        sk.add_mib_stat_bh(cong::MibField::HystartDelayDetect, 1);
+                        sk.add_mib_stat_bh(cong::MibField::HystartDelayCwnd, cwnd as i64);
+                    } else {
+                        sk.inet_csk_ca_mut().hy_mut().css_rounds = css_rounds + 1;
+                    }
+                }
+
+                sk.inet_csk_ca_mut().hy_mut().last_round_min_rtt = round_min_rtt;
+            }
+
             Self::reset(sk);
         }
         let hy = sk.inet_csk_ca().hy();
@@ -166,7 +311,7 @@ pub trait HyStart: cong::Algorithm<Data: HasHyStartState> {
             return;
         };
 
-        if matches!(Self::DETECT, HystartDetect::Both | HystartDetect::AckTrain) {
+        if matches!(Self::detect(), HystartDetect::Both | HystartDetect::AckTrain) {
             let tp = sk.tcp_sk();
             let now = tp.tcp_mstamp() as time::Usecs32;
 
@@ -185,7 +330,7 @@ pub trait HyStart: cong::Algorithm<Data: HasHyStartState> {
             // Commit: c54b4b7655447c1f24f6d50779c22eba9ee0fd24
             // Purposefully introduced the cast ... am I just stupid?
             // Link: https://godbolt.org/z/E7ocxae69
-            if now.wrapping_sub(hy.last_ack) <= Self::ACK_DELTA {
+            if now.wrapping_sub(hy.last_ack) <= Self::ACK_DELTA.get() {
                 let threshold = if let Ok(sock::Pacing::r#None) = sk.sk_pacing_status() {
                     (delay_min + Self::ack_delay(sk)) >> 1
                 } else {
@@ -211,19 +356,21 @@ pub trait HyStart: cong::Algorithm<Data: HasHyStartState> {
                     );
 
                     let tp = sk.tcp_sk_mut();
+                    let cwnd = tp.snd_cwnd();
 
-                    tp.set_snd_ssthresh(tp.snd_cwnd());
+                    tp.set_snd_ssthresh(cwnd);
 
                     sk.inet_csk_ca_mut().hy_mut().found = true;
 
-                    // TODO: Update net stats.
+                    sk.add_mib_stat_bh(cong::MibField::HystartTrainDetect, 1);
+                    sk.add_mib_stat_bh(cong::MibField::HystartTrainCwnd, cwnd as i64);
                 }
 
                 sk.inet_csk_ca_mut().hy_mut().last_ack = now;
             }
         }
 
-        if matches!(Self::DETECT, HystartDetect::Both | HystartDetect::Delay) {
+        if matches!(Self::detect(), HystartDetect::Both | HystartDetect::Delay) {
             let hy = sk.inet_csk_ca_mut().hy_mut();
 
             // The paper only takes the min RTT of the first `MIN_SAMPLES`
@@ -233,7 +380,7 @@ pub trait HyStart: cong::Algorithm<Data: HasHyStartState> {
                 hy.curr_rtt = delay
             }
 
-            if hy.sample_cnt < Self::MIN_SAMPLES {
+            if hy.sample_cnt < Self::MIN_SAMPLES.get() as u8 {
                 hy.sample_cnt += 1;
             } else {
                 // Does the increase in RTT indicate its time to exit slow
@@ -253,11 +400,37 @@ pub trait HyStart: cong::Algorithm<Data: HasHyStartState> {
                         sk.tcp_sk().snd_cwnd(),
                         start_time,
                     );
-                    // TODO: Update net stats.
-
                     let tp = sk.tcp_sk_mut();
+                    let cwnd = tp.snd_cwnd();
+
+                    tp.set_snd_ssthresh(cwnd);
 
-                    tp.set_snd_ssthresh(tp.snd_cwnd());
+                    sk.add_mib_stat_bh(cong::MibField::HystartDelayDetect, 1);
+                    sk.add_mib_stat_bh(cong::MibField::HystartDelayCwnd, cwnd as i64);
+                }
+            }
+        }
+
+        if matches!(Self::detect(), HystartDetect::Css) {
+            let hy = sk.inet_csk_ca_mut().hy_mut();
+
+            if hy.curr_round_min_rtt > delay {
+                hy.curr_round_min_rtt = delay;
+            }
+
+            if hy.sample_cnt < N_RTT_SAMPLE {
+                hy.sample_cnt += 1;
+            } else if hy.css_rounds == 0 && hy.last_round_min_rtt != u32::MAX {
+                let rtt_thresh = css_rtt_thresh(hy.last_round_min_rtt);
+
+                // Has the per-round min RTT risen enough to warrant entering
+                // Conservative Slow Start? Unlike the plain delay heuristic,
+                // this does not exit slow start outright: a single round of
+                // jitter is given a chance to subside over the next
+                // `CSS_ROUNDS` rounds before `found` is set.
+                if hy.curr_round_min_rtt >= hy.last_round_min_rtt + rtt_thresh {
+                    hy.css_baseline_min_rtt = hy.curr_round_min_rtt;
+                    hy.css_rounds = 1;
                 }
             }
         }
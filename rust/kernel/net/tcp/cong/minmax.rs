@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: GPL-2.0-only
+
+//! A windowed running min/max filter.
+//!
+//! Ports the algorithm of `lib/win_minmax.c`: rather than keeping every sample in the window,
+//! [`MinMax`] keeps only the three samples that could possibly become the windowed extremum as
+//! newer, larger (for a max) or smaller (for a min) samples age the older candidates out. This is
+//! what BBR uses to track the maximum delivery rate over the last ~10 round trips, and the
+//! minimum RTT over the last few minutes, without needing a full history of samples.
+//!
+//! The window's unit (a round count, a `Usecs32` timestamp, ...) is entirely up to the caller;
+//! [`MinMax`] only ever compares the `u32` `t`/`win` values it is given.
+
+/// One candidate sample: a value `v` observed at time/round `t`.
+#[derive(Clone, Copy, Default)]
+struct Sample {
+    t: u32,
+    v: u32,
+}
+
+/// A windowed running minimum or maximum filter, tracking the extremum of the samples fed to it
+/// over the last `win` units of whatever clock `t` counts in.
+///
+/// A freshly [`new`](Self::new) filter reads as all-zeroes, which is indistinguishable from a real
+/// zero sample; call [`Self::reset`] with the first real sample before relying on
+/// [`Self::running_max`]/[`Self::running_min`]'s return value, the same way `bbr_init` seeds
+/// `struct minmax` in the C implementation. Use one of `running_max`/`running_min` consistently
+/// for a given instance.
+#[derive(Clone, Copy, Default)]
+pub struct MinMax {
+    s: [Sample; 3],
+}
+
+impl MinMax {
+    /// Creates a new filter with no samples.
+    pub const fn new() -> Self {
+        Self {
+            s: [Sample { t: 0, v: 0 }; 3],
+        }
+    }
+
+    /// Returns the current windowed extremum, i.e. `s[0].v`.
+    #[inline]
+    pub fn get(&self) -> u32 {
+        self.s[0].v
+    }
+
+    /// Forgets all earlier samples and seeds every slot with `(t, meas)`, returning `meas`.
+    pub fn reset(&mut self, t: u32, meas: u32) -> u32 {
+        self.s = [Sample { t, v: meas }; 3];
+        self.s[0].v
+    }
+
+    /// Feeds a new sample `(t, meas)` into the filter and returns the new windowed maximum.
+    pub fn running_max(&mut self, win: u32, t: u32, meas: u32) -> u32 {
+        let val = Sample { t, v: meas };
+
+        if meas >= self.s[0].v || t.wrapping_sub(self.s[2].t) > win {
+            return self.reset(t, meas);
+        }
+
+        if meas >= self.s[1].v {
+            self.s[1] = val;
+            self.s[2] = val;
+        } else if meas >= self.s[2].v {
+            self.s[2] = val;
+        }
+
+        self.subwin_update(win, val)
+    }
+
+    /// Feeds a new sample `(t, meas)` into the filter and returns the new windowed minimum.
+    pub fn running_min(&mut self, win: u32, t: u32, meas: u32) -> u32 {
+        let val = Sample { t, v: meas };
+
+        if meas <= self.s[0].v || t.wrapping_sub(self.s[2].t) > win {
+            return self.reset(t, meas);
+        }
+
+        if meas <= self.s[1].v {
+            self.s[1] = val;
+            self.s[2] = val;
+        } else if meas <= self.s[2].v {
+            self.s[2] = val;
+        }
+
+        self.subwin_update(win, val)
+    }
+
+    /// Ages the window forward once `val` itself didn't become the new `s[0]`: promotes `s[1]`
+    /// and `s[2]` once `s[0]` has fallen out of the window (re-promoting once more if that still
+    /// isn't enough), and otherwise backfills `s[1]`/`s[2]` with `val` once enough of the window
+    /// has passed without a better candidate replacing them.
+    fn subwin_update(&mut self, win: u32, val: Sample) -> u32 {
+        let dt = val.t.wrapping_sub(self.s[0].t);
+
+        if dt > win {
+            self.s[0] = self.s[1];
+            self.s[1] = self.s[2];
+            self.s[2] = val;
+            if val.t.wrapping_sub(self.s[0].t) > win {
+                self.s[0] = self.s[1];
+                self.s[1] = self.s[2];
+                self.s[2] = val;
+            }
+        } else if self.s[1].t == self.s[0].t && dt > win / 4 {
+            self.s[1] = val;
+            self.s[2] = val;
+        } else if self.s[2].t == self.s[1].t && dt > win / 2 {
+            self.s[2] = val;
+        }
+
+        self.s[0].v
+    }
+}
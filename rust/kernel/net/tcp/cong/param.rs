@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: GPL-2.0-only
+
+//! Runtime-configurable tunables for congestion-control algorithms.
+//!
+//! Mirrors the way C CCAs (e.g. `tcp_cubic.c`) expose their knobs via
+//! `module_param()`: every [`Param`] shows up under
+//! `/sys/module/<name>/parameters/<param>` and can be read and written while
+//! the module is loaded.
+
+use crate::bindings;
+use core::sync::atomic::{AtomicU32, Ordering};
+use core::str;
+
+/// A single `u32`-valued module parameter.
+///
+/// The current value is stored in an [`AtomicU32`] so that a concurrent
+/// `sysfs` write and a read from the hot ACK path never race; there is no
+/// further synchronization, so a CCA observing a value mid-update is only
+/// guaranteed to see *some* value that was written, not necessarily the most
+/// recent one with respect to other fields.
+pub struct Param(AtomicU32);
+
+impl Param {
+    /// Creates a new parameter with the given default value.
+    pub const fn new(default: u32) -> Self {
+        Self(AtomicU32::new(default))
+    }
+
+    /// Returns the current value.
+    #[inline]
+    pub fn get(&self) -> u32 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// `kernel_param_ops::set` callback shared by all [`Param`]s: parses the
+    /// string written to `sysfs` and stores it.
+    ///
+    /// # Safety
+    ///
+    /// `val` must point to a NUL-terminated string owned by the kernel
+    /// parameter core, and `kp` must point to a `kernel_param` whose `arg`
+    /// points at a valid [`Param`].
+    pub(crate) unsafe extern "C" fn set_cb(
+        val: *const core::ffi::c_char,
+        kp: *const bindings::kernel_param,
+    ) -> core::ffi::c_int {
+        let mut parsed: u32 = 0;
+        // SAFETY: `val` is a NUL-terminated string for the lifetime of the call.
+        let ret = unsafe { bindings::kstrtouint(val, 10, &mut parsed) };
+        if ret != 0 {
+            return ret;
+        }
+
+        // SAFETY: `kp` is valid for the duration of the call and `arg` points
+        // at a live `Param` for as long as the module is loaded.
+        let param = unsafe { &*((*kp).arg as *const Param) };
+        param.0.store(parsed, Ordering::Relaxed);
+        0
+    }
+
+    /// `kernel_param_ops::get` callback shared by all [`Param`]s: formats the
+    /// current value into the buffer provided by the parameter core.
+    ///
+    /// # Safety
+    ///
+    /// `buffer` must point to a writable buffer of at least
+    /// `bindings::PAGE_SIZE` bytes, and `kp` must point to a `kernel_param`
+    /// whose `arg` points at a valid [`Param`].
+    pub(crate) unsafe extern "C" fn get_cb(
+        buffer: *mut core::ffi::c_char,
+        kp: *const bindings::kernel_param,
+    ) -> core::ffi::c_int {
+        // SAFETY: `kp` is valid for the duration of the call and `arg` points
+        // at a live `Param` for as long as the module is loaded.
+        let param = unsafe { &*((*kp).arg as *const Param) };
+
+        // SAFETY: `buffer` is valid for write for at least `PAGE_SIZE` bytes.
+        unsafe { bindings::scnprintf(buffer, bindings::PAGE_SIZE, c"%u".as_ptr(), param.get()) }
+            as core::ffi::c_int
+    }
+}
+
+// SAFETY: All access goes through the atomic.
+unsafe impl Sync for Param {}
+
+/// The `kernel_param_ops` shared by every [`Param`] declared with
+/// [`declare_params!`].
+pub(crate) static PARAM_OPS: bindings::kernel_param_ops = bindings::kernel_param_ops {
+    flags: 0,
+    set: Some(Param::set_cb),
+    get: Some(Param::get_cb),
+    free: None,
+};
+
+/// Declares one or more `u32` module parameters backed by [`Param`], each
+/// registered with the kernel parameter core so it becomes visible and
+/// writable under `/sys/module/<module_name>/parameters/`.
+///
+/// # Examples
+///
+/// ```ignore
+/// declare_params! {
+///     /// Whether to use fast convergence.
+///     pub static FAST_CONVERGENCE: Param = Param::new(1), perm: 0o644;
+/// }
+/// ```
+#[macro_export]
+macro_rules! declare_params {
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis static $name:ident: Param = $init:expr, perm: $perm:expr;
+    )*) => {
+        $(
+            $(#[$meta])*
+            $vis static $name: $crate::net::tcp::cong::param::Param = $init;
+
+            const _: () = {
+                #[used]
+                #[link_section = "__param"]
+                static __PARAM: $crate::bindings::kernel_param = $crate::bindings::kernel_param {
+                    name: concat!(stringify!($name), "\0").as_ptr() as *const core::ffi::c_char,
+                    mod_: core::ptr::null_mut(),
+                    ops: &$crate::net::tcp::cong::param::PARAM_OPS,
+                    perm: $perm,
+                    level: -1,
+                    flags: 0,
+                    __bindgen_anon_1: $crate::bindings::kernel_param__bindgen_ty_1 {
+                        arg: &$name as *const _ as *mut core::ffi::c_void,
+                    },
+                };
+            };
+        )*
+    };
+}
+pub use declare_params;
@@ -0,0 +1,182 @@
+// SPDX-License-Identifier: GPL-2.0-only
+
+//! User Datagram Protocol (UDP).
+//!
+//! C header: [`include/linux/udp.h`](srctree/include/linux/udp.h)
+
+use core::net::{Ipv4Addr, SocketAddrV4};
+
+use crate::{
+    bindings,
+    error::{code::*, to_result, Result},
+};
+
+/// A kernel-space UDP socket.
+///
+/// # Invariants
+///
+/// `ptr` is non-null and points to a `struct socket` created by
+/// `sock_create_kern` that has not yet been released.
+pub struct UdpSocket {
+    ptr: *mut bindings::socket,
+}
+
+impl UdpSocket {
+    /// Creates a new, unbound IPv4 UDP socket.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the kernel fails to allocate the socket.
+    pub fn try_new() -> Result<Self> {
+        let mut ptr = core::ptr::null_mut();
+        // SAFETY: `&mut ptr` is a valid pointer to write the new socket into.
+        to_result(unsafe {
+            bindings::sock_create_kern(
+                &mut bindings::init_net,
+                bindings::PF_INET as i32,
+                bindings::sock_type_SOCK_DGRAM as i32,
+                bindings::IPPROTO_UDP as i32,
+                &mut ptr,
+            )
+        })?;
+        Ok(Self { ptr })
+    }
+
+    /// Binds the socket to a local address.
+    pub fn bind(&mut self, addr: SocketAddrV4) -> Result {
+        let sockaddr = to_sockaddr_in(addr);
+        // SAFETY: `self.ptr` is valid by the type invariants, and `sockaddr` is
+        // a validly initialized `sockaddr_in` of the expected size.
+        to_result(unsafe {
+            bindings::kernel_bind(
+                self.ptr,
+                (&sockaddr as *const bindings::sockaddr_in).cast::<bindings::sockaddr>()
+                    as *mut _,
+                core::mem::size_of::<bindings::sockaddr_in>() as i32,
+            )
+        })
+    }
+
+    /// Connects the socket to a remote address. [`Self::send_to`] still takes
+    /// its destination explicitly, and [`Self::recv_from`] still reports each
+    /// datagram's sender.
+    pub fn connect(&mut self, addr: SocketAddrV4) -> Result {
+        let sockaddr = to_sockaddr_in(addr);
+        // SAFETY: `self.ptr` is valid by the type invariants, and `sockaddr` is
+        // a validly initialized `sockaddr_in` of the expected size.
+        to_result(unsafe {
+            bindings::kernel_connect(
+                self.ptr,
+                (&sockaddr as *const bindings::sockaddr_in).cast::<bindings::sockaddr>()
+                    as *mut _,
+                core::mem::size_of::<bindings::sockaddr_in>() as i32,
+                0,
+            )
+        })
+    }
+
+    /// Sends `buf` to `addr`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying `sendmsg` call fails.
+    pub fn send_to(&mut self, buf: &[u8], addr: SocketAddrV4) -> Result<usize> {
+        let sockaddr = to_sockaddr_in(addr);
+        let mut iov = bindings::kvec {
+            iov_base: buf.as_ptr() as *mut _,
+            iov_len: buf.len(),
+        };
+        let mut msg: bindings::msghdr = unsafe { core::mem::zeroed() };
+        msg.msg_name = (&sockaddr as *const bindings::sockaddr_in).cast::<core::ffi::c_void>()
+            as *mut _;
+        msg.msg_namelen = core::mem::size_of::<bindings::sockaddr_in>() as i32;
+        // SAFETY: `self.ptr` and `msg` are valid, and `iov`/`buf` are valid for
+        // `buf.len()` bytes for the duration of the call.
+        let ret = unsafe {
+            bindings::kernel_sendmsg(self.ptr, &mut msg, &mut iov, 1, buf.len())
+        };
+        if ret < 0 {
+            Err(crate::error::Error::from_errno(ret as i32))
+        } else {
+            Ok(ret as usize)
+        }
+    }
+
+    /// Receives a datagram into `buf`, returning its length and sender address.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying `recvmsg` call fails.
+    pub fn recv_from(&mut self, buf: &mut [u8]) -> Result<(usize, SocketAddrV4)> {
+        let mut from: bindings::sockaddr_in = unsafe { core::mem::zeroed() };
+        let mut iov = bindings::kvec {
+            iov_base: buf.as_mut_ptr().cast(),
+            iov_len: buf.len(),
+        };
+        let mut msg: bindings::msghdr = unsafe { core::mem::zeroed() };
+        msg.msg_name = (&mut from as *mut bindings::sockaddr_in).cast::<core::ffi::c_void>();
+        msg.msg_namelen = core::mem::size_of::<bindings::sockaddr_in>() as i32;
+        // SAFETY: `self.ptr` is valid, `msg`/`iov` describe `buf`, which is
+        // valid for `buf.len()` bytes for the duration of the call.
+        let ret = unsafe {
+            bindings::kernel_recvmsg(self.ptr, &mut msg, &mut iov, 1, buf.len(), 0)
+        };
+        if ret < 0 {
+            Err(crate::error::Error::from_errno(ret as i32))
+        } else {
+            Ok((ret as usize, from_sockaddr_in(&from)))
+        }
+    }
+
+    /// Returns `true` if the socket currently has a datagram queued for
+    /// reading, without blocking.
+    ///
+    /// There is no task executor in this tree to back a `Future`-returning
+    /// `readable()`; drivers wanting to wait should poll this from their own
+    /// wait-queue integration.
+    pub fn poll_readable(&self) -> bool {
+        // SAFETY: `self.ptr` is valid by the type invariants, and `sk` is only
+        // read here.
+        unsafe { !bindings::skb_queue_empty(&(*(*self.ptr).sk).sk_receive_queue) }
+    }
+
+    /// Returns the raw `struct socket` backing this UDP socket.
+    pub fn raw(&self) -> *mut bindings::socket {
+        self.ptr
+    }
+}
+
+impl Drop for UdpSocket {
+    fn drop(&mut self) {
+        // SAFETY: `self.ptr` is valid by the type invariants, and is only
+        // released once.
+        unsafe { bindings::sock_release(self.ptr) };
+    }
+}
+
+// SAFETY: `UdpSocket` only holds a pointer to a `struct socket`, which is safe
+// to use from any thread.
+unsafe impl Send for UdpSocket {}
+
+// SAFETY: `UdpSocket` only holds a pointer to a `struct socket`; the
+// underlying `sendmsg`/`recvmsg` calls are safe to issue concurrently from
+// multiple threads, same as from C.
+unsafe impl Sync for UdpSocket {}
+
+fn to_sockaddr_in(addr: SocketAddrV4) -> bindings::sockaddr_in {
+    bindings::sockaddr_in {
+        sin_family: bindings::AF_INET as bindings::sa_family_t,
+        sin_port: addr.port().to_be(),
+        sin_addr: bindings::in_addr {
+            s_addr: u32::from_ne_bytes(addr.ip().octets()),
+        },
+        __pad: [0; 8],
+    }
+}
+
+fn from_sockaddr_in(raw: &bindings::sockaddr_in) -> SocketAddrV4 {
+    SocketAddrV4::new(
+        Ipv4Addr::from(raw.sin_addr.s_addr.to_ne_bytes()),
+        u16::from_be(raw.sin_port),
+    )
+}
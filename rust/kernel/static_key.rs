@@ -147,4 +147,255 @@ macro_rules! static_key_false {
     }};
 }
 
-pub use {_static_key_false, static_key_false};
+#[doc(hidden)]
+#[macro_export]
+#[cfg(target_arch = "x86_64")]
+#[cfg(not(CONFIG_HAVE_RUST_ASM_GOTO))]
+macro_rules! _static_key_true {
+    ($key:path, $keytyp:ty, $field:ident) => {{
+        let mut output = 1u32;
+
+        core::arch::asm!(
+            r#"
+            1: .byte 0xe9, 0x00, 0x00, 0x00, 0x00
+
+            .pushsection __jump_table,  "aw"
+            .balign 8
+            .long 1b - .
+            .long 3f - .
+            .quad {0} + {1} - .
+            .popsection
+
+            2: mov {2:e}, 0
+            3:
+            "#,
+            sym $key,
+            const ::core::mem::offset_of!($keytyp, $field),
+            inout(reg) output,
+        );
+
+        output != 0
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(target_arch = "x86_64")]
+#[cfg(CONFIG_HAVE_RUST_ASM_GOTO)]
+macro_rules! _static_key_true {
+    ($key:path, $keytyp:ty, $field:ident) => {'my_label: {
+        core::arch::asm!(
+            r#"
+            1: jmp {0:l}
+
+            .pushsection __jump_table,  "aw"
+            .balign 8
+            .long 1b - .
+            .long {0} - .
+            .quad {1} + {2} - .
+            .popsection
+            "#,
+            label {
+                break 'my_label true;
+            },
+            sym $key,
+            const ::core::mem::offset_of!($keytyp, $field),
+        );
+
+        break 'my_label false;
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(target_arch = "aarch64")]
+#[cfg(not(CONFIG_HAVE_RUST_ASM_GOTO))]
+macro_rules! _static_key_true {
+    ($key:path, $keytyp:ty, $field:ident) => {{
+        let mut output = 1u32;
+
+        core::arch::asm!(
+            r#"
+            1: b 3f
+
+            .pushsection __jump_table,  "aw"
+            .align 3
+            .long 1b - ., 3f - .
+            .quad {0} + {1} - .
+            .popsection
+
+            2: mov {2:w}, 0
+            3:
+            "#,
+            sym $key,
+            const ::core::mem::offset_of!($keytyp, $field),
+            inout(reg) output
+        );
+
+        output != 0
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(target_arch = "aarch64")]
+#[cfg(CONFIG_HAVE_RUST_ASM_GOTO)]
+macro_rules! _static_key_true {
+    ($key:path, $keytyp:ty, $field:ident) => {'my_label: {
+        core::arch::asm!(
+            r#"
+            1: b {0:l}
+
+            .pushsection __jump_table,  "aw"
+            .align 3
+            .long 1b - ., {0} - .
+            .quad {1} + {2} - .
+            .popsection
+            "#,
+            label {
+                break 'my_label true;
+            },
+            sym $key,
+            const ::core::mem::offset_of!($keytyp, $field),
+        );
+
+        break 'my_label false;
+    }};
+}
+
+/// Branch based on a static key, like [`static_key_false!`], but defaulting to the taken branch
+/// until the key is explicitly disabled.
+///
+/// Takes the same three arguments as [`static_key_false!`].
+#[macro_export]
+macro_rules! static_key_true {
+    ($key:path, $keytyp:ty, $field:ident) => {{
+        // Assert that `$key` has type `$keytyp` and that `$key.$field` has type `static_key`.
+        //
+        // SAFETY: We know that `$key` is a static because otherwise the inline assembly will not
+        // compile. The raw pointers created in this block are in-bounds of `$key`.
+        static _TY_ASSERT: () = unsafe {
+            let key: *const $keytyp = ::core::ptr::addr_of!($key);
+            let _: *const $crate::bindings::static_key = ::core::ptr::addr_of!((*key).$field);
+        };
+
+        $crate::static_key::_static_key_true! { $key, $keytyp, $field }
+    }};
+}
+
+/// A runtime-toggleable static branch, usable as a near-zero-overhead feature flag.
+///
+/// Declare one with [`define_static_branch!`], then read it with [`static_branch_likely!`] or
+/// [`static_branch_unlikely!`] depending on which outcome is expected to be common; both compile
+/// down to the same inline-asm jump-table patching that [`static_key_false!`] and
+/// [`static_key_true!`] already provide, just defaulting to the opposite branch. Unlike
+/// [`static_key_false!`], which only ever reads a key owned by C code, a [`StaticBranch`] is a
+/// key a Rust module can declare, flip, and read all on its own.
+#[repr(transparent)]
+pub struct StaticBranch {
+    key: static_key,
+}
+
+impl StaticBranch {
+    /// Creates a branch that starts disabled.
+    pub const fn new_false() -> Self {
+        // SAFETY: a zeroed `static_key` is `STATIC_KEY_INIT_FALSE`: a zero `enabled` count and an
+        // empty `__jump_table` entry list, which the jump-label subsystem populates at boot by
+        // scanning the `__jump_table` section, not from this initializer.
+        let key = unsafe { core::mem::MaybeUninit::<static_key>::zeroed().assume_init() };
+        Self { key }
+    }
+
+    /// Creates a branch that starts enabled.
+    pub const fn new_true() -> Self {
+        // SAFETY: see `new_false`; `enabled` is then set to `STATIC_KEY_INIT_TRUE`'s count of 1.
+        let mut key = unsafe { core::mem::MaybeUninit::<static_key>::zeroed().assume_init() };
+        key.enabled.counter = 1;
+        Self { key }
+    }
+
+    fn raw(&self) -> *mut static_key {
+        &self.key as *const static_key as *mut static_key
+    }
+
+    /// Unconditionally enables the branch, patching every site that reads it.
+    pub fn enable(&self) {
+        // SAFETY: `self.raw()` is a valid, initialised `static_key`.
+        unsafe { static_key_enable(self.raw()) };
+    }
+
+    /// Unconditionally disables the branch, patching every site that reads it.
+    pub fn disable(&self) {
+        // SAFETY: `self.raw()` is a valid, initialised `static_key`.
+        unsafe { static_key_disable(self.raw()) };
+    }
+
+    /// Sets the branch to `enabled`.
+    pub fn set(&self, enabled: bool) {
+        if enabled {
+            self.enable();
+        } else {
+            self.disable();
+        }
+    }
+
+    /// Takes a reference on the branch, enabling it if this is the first outstanding reference.
+    ///
+    /// Must be balanced by a matching [`Self::slow_dec`]; unlike [`Self::enable`], multiple
+    /// independent callers can each hold the branch enabled without racing each other's intent.
+    pub fn slow_inc(&self) {
+        // SAFETY: `self.raw()` is a valid, initialised `static_key`.
+        unsafe { static_key_slow_inc(self.raw()) };
+    }
+
+    /// Releases a reference taken by [`Self::slow_inc`], disabling the branch once the last one
+    /// is released.
+    pub fn slow_dec(&self) {
+        // SAFETY: `self.raw()` is a valid, initialised `static_key`.
+        unsafe { static_key_slow_dec(self.raw()) };
+    }
+}
+
+// SAFETY: `StaticBranch` only wraps a `static_key`, all of whose mutators go through the
+// refcounted, lock-protected jump-label slow path, so concurrent access from any thread is safe.
+unsafe impl Sync for StaticBranch {}
+
+/// Declares a [`StaticBranch`] static, initially enabled or disabled.
+///
+/// ```ignore
+/// define_static_branch!(static MY_FEATURE: bool = false);
+/// ```
+#[macro_export]
+macro_rules! define_static_branch {
+    ($vis:vis static $name:ident: bool = false) => {
+        $vis static $name: $crate::static_key::StaticBranch =
+            $crate::static_key::StaticBranch::new_false();
+    };
+    ($vis:vis static $name:ident: bool = true) => {
+        $vis static $name: $crate::static_key::StaticBranch =
+            $crate::static_key::StaticBranch::new_true();
+    };
+}
+
+/// Reads a [`StaticBranch`] declared with [`define_static_branch!`], assuming the branch is
+/// usually taken: the fast path is a `nop` fall-through with no conditional jump at all.
+#[macro_export]
+macro_rules! static_branch_likely {
+    ($key:path) => {
+        $crate::static_key::static_key_true!($key, $crate::static_key::StaticBranch, key)
+    };
+}
+
+/// Reads a [`StaticBranch`] declared with [`define_static_branch!`], assuming the branch is
+/// usually not taken: the fast path is a `nop` fall-through that skips the "taken" code.
+#[macro_export]
+macro_rules! static_branch_unlikely {
+    ($key:path) => {
+        $crate::static_key::static_key_false!($key, $crate::static_key::StaticBranch, key)
+    };
+}
+
+pub use {
+    _static_key_false, _static_key_true, define_static_branch, static_branch_likely,
+    static_branch_unlikely, static_key_false, static_key_true,
+};
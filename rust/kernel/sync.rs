@@ -28,12 +28,14 @@ mod guard;
 mod locked_by;
 mod mutex;
 mod nowait;
+mod once;
 pub mod rcu;
 mod revocable;
 mod rwsem;
 mod seqlock;
 pub mod smutex;
 mod spinlock;
+mod ticket_spinlock;
 
 pub use arc::{new_refcount, Arc, ArcBorrow, StaticArc, UniqueArc};
 pub use condvar::CondVar;
@@ -41,10 +43,12 @@ pub use guard::{Guard, Lock, LockFactory, LockInfo, ReadLock, WriteLock};
 pub use locked_by::LockedBy;
 pub use mutex::{Mutex, RevocableMutex, RevocableMutexGuard};
 pub use nowait::{NoWaitLock, NoWaitLockGuard};
+pub use once::{Lazy, Once};
 pub use revocable::{Revocable, RevocableGuard};
 pub use rwsem::{RevocableRwSemaphore, RevocableRwSemaphoreGuard, RwSemaphore};
 pub use seqlock::{SeqLock, SeqLockReadGuard};
 pub use spinlock::{RawSpinLock, SpinLock};
+pub use ticket_spinlock::{NopRelax, RelaxStrategy, SpinRelax, TicketSpinLock};
 
 /// Represents a lockdep class. It's a wrapper around C's `lock_class_key`.
 #[repr(transparent)]
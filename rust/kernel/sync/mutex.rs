@@ -7,6 +7,7 @@
 use super::{Guard, Lock, LockClassKey, LockFactory, WriteLock};
 use crate::{
     bindings,
+    error::{Error, Result},
     init::{self, PinInit},
     macros::pin_project,
     pin_init,
@@ -107,6 +108,54 @@ impl<T: ?Sized> Mutex<T> {
         // SAFETY: The mutex was just acquired.
         unsafe { Guard::new(self, ctx) }
     }
+
+    /// Tries to lock the mutex, without blocking.
+    ///
+    /// Returns `None` if the mutex is currently held. Safe to call from atomic contexts.
+    pub fn try_lock(&self) -> Option<Guard<'_, Self>> {
+        // SAFETY: `mutex` points to valid memory.
+        if unsafe { bindings::mutex_trylock(self.mutex.get()) } == 0 {
+            return None;
+        }
+        // SAFETY: The mutex was just acquired.
+        Some(unsafe { Guard::new(self, EmptyGuardContext) })
+    }
+
+    /// Locks the mutex, allowing the wait to be interrupted by any signal.
+    ///
+    /// Returns `Err(EINTR)` if a signal arrives before the lock could be acquired.
+    pub fn lock_interruptible(&self) -> Result<Guard<'_, Self>> {
+        // SAFETY: `mutex` points to valid memory.
+        let ret = unsafe { bindings::mutex_lock_interruptible(self.mutex.get()) };
+        if ret != 0 {
+            return Err(Error::from_errno(ret));
+        }
+        // SAFETY: The mutex was just acquired.
+        Ok(unsafe { Guard::new(self, EmptyGuardContext) })
+    }
+
+    /// Locks the mutex, allowing the wait to be interrupted by a fatal signal only.
+    ///
+    /// Returns `Err(EINTR)` if such a signal arrives before the lock could be acquired. Prefer
+    /// this over [`Self::lock_interruptible`] when the caller cannot meaningfully unwind on an
+    /// ordinary signal but must still remain killable.
+    pub fn lock_killable(&self) -> Result<Guard<'_, Self>> {
+        // SAFETY: `mutex` points to valid memory.
+        let ret = unsafe { bindings::mutex_lock_killable(self.mutex.get()) };
+        if ret != 0 {
+            return Err(Error::from_errno(ret));
+        }
+        // SAFETY: The mutex was just acquired.
+        Ok(unsafe { Guard::new(self, EmptyGuardContext) })
+    }
+
+    /// Locks the mutex, runs `cb` with exclusive access to the protected data, then releases
+    /// the mutex before returning. Confining the guard to `cb`'s body makes it harder to
+    /// accidentally hold the lock across an `.await` point or into an atomic context.
+    pub fn with<R>(&self, cb: impl FnOnce(&mut T) -> R) -> R {
+        let mut guard = self.lock();
+        cb(&mut guard)
+    }
 }
 
 impl<T> LockFactory for Mutex<T> {
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! One-time and lazy initialization for kernel statics.
+//!
+//! [`init_static_sync!`](crate::init_static_sync) builds its values eagerly, from a linker
+//! constructor that runs before any other code can observe the static. [`Once`] and [`Lazy`]
+//! instead build their value lazily, the first time it is actually accessed, without needing a
+//! [`Mutex`](super::Mutex) or a constructor section: the common case is a global table that some
+//! boots never touch at all.
+
+use core::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    ops::Deref,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+const UNINIT: u8 = 0;
+const INITIALIZING: u8 = 1;
+const DONE: u8 = 2;
+
+/// A cell that can be initialized at most once, the first time it is accessed.
+///
+/// Initialization is race-free: if several threads call [`Once::call_once`] concurrently, exactly
+/// one of them runs the initializer closure and the rest spin until its result is published.
+pub struct Once<T> {
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+// SAFETY: `Once` only gives out shared references to its value once `state` has been published
+// with `Ordering::Release`, and the sole initializing thread gets exclusive access until then, so
+// it is `Sync` whenever `T` is (the usual interior-mutability bound also requires `T: Send`
+// because the initializing thread may differ from an accessing one).
+unsafe impl<T: Send + Sync> Sync for Once<T> {}
+
+// SAFETY: A `Once<T>` can be sent across threads whenever its contents can, since at most one
+// thread ever has exclusive access to `value` while initializing it.
+unsafe impl<T: Send> Send for Once<T> {}
+
+impl<T> Once<T> {
+    /// Creates a new, uninitialized `Once`.
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(UNINIT),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Returns a reference to the contents, running `f` to produce it if this is the first call.
+    ///
+    /// If another thread is concurrently initializing the cell, this spins until that thread
+    /// publishes its value. `f` is never run more than once, even under contention.
+    pub fn call_once<F>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        match self
+            .state
+            .compare_exchange(UNINIT, INITIALIZING, Ordering::Acquire, Ordering::Acquire)
+        {
+            Ok(_) => {
+                // SAFETY: We are the thread that won the transition out of `UNINIT`, so we have
+                // exclusive access to `value` until we publish `DONE` below.
+                unsafe { (*self.value.get()).write(f()) };
+                self.state.store(DONE, Ordering::Release);
+            }
+            Err(DONE) => {}
+            Err(_) => {
+                while self.state.load(Ordering::Acquire) != DONE {
+                    core::hint::spin_loop();
+                }
+            }
+        }
+
+        // SAFETY: `state` is `DONE`, so `value` was written by the branch above (on this thread
+        // or another) and the `Acquire` loads above pair with its `Release` store.
+        unsafe { (*self.value.get()).assume_init_ref() }
+    }
+
+    /// Like [`Self::call_once`], but `f` may fail, leaving the cell uninitialized (and eligible
+    /// for another `try_call_once` to attempt initialization again) when it does.
+    pub fn try_call_once<E>(&self, f: impl FnOnce() -> Result<T, E>) -> Result<&T, E> {
+        loop {
+            match self
+                .state
+                .compare_exchange(UNINIT, INITIALIZING, Ordering::Acquire, Ordering::Acquire)
+            {
+                Ok(_) => match f() {
+                    Ok(value) => {
+                        // SAFETY: We hold exclusive access to `value` until `DONE` is published.
+                        unsafe { (*self.value.get()).write(value) };
+                        self.state.store(DONE, Ordering::Release);
+                        break;
+                    }
+                    Err(e) => {
+                        self.state.store(UNINIT, Ordering::Release);
+                        return Err(e);
+                    }
+                },
+                Err(DONE) => break,
+                Err(_) => {
+                    while self.state.load(Ordering::Acquire) == INITIALIZING {
+                        core::hint::spin_loop();
+                    }
+                    // The racing initializer is done, one way or another: loop around to either
+                    // observe `DONE`, or race to become the new initializer after a failure.
+                }
+            }
+        }
+
+        // SAFETY: `state` is `DONE`, matching the comment in `call_once`.
+        Ok(unsafe { (*self.value.get()).assume_init_ref() })
+    }
+
+    /// Returns a reference to the contents if they have already been initialized, or `None`
+    /// otherwise. Never blocks.
+    pub fn get(&self) -> Option<&T> {
+        if self.state.load(Ordering::Acquire) == DONE {
+            // SAFETY: `state` is `DONE`, so `value` has been written and published.
+            Some(unsafe { (*self.value.get()).assume_init_ref() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Default for Once<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for Once<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == DONE {
+            // SAFETY: `state` is `DONE`, so `value` was initialized and has not been dropped yet.
+            unsafe { (*self.value.get()).assume_init_drop() };
+        }
+    }
+}
+
+/// A value that is lazily built from `F` the first time it is dereferenced.
+///
+/// This layers the common `OnceCell`/`Lazy` ergonomics on top of [`Once`], letting Rust modules
+/// declare lazily-built global tables without threading a [`Mutex`](super::Mutex) through every
+/// access.
+pub struct Lazy<T, F = fn() -> T> {
+    once: Once<T>,
+    init: F,
+}
+
+// SAFETY: `init` is only ever called by the single thread that wins initialization of `once`, so
+// `Lazy` is `Sync` whenever `T` is and `F` can be shared with that initializing thread.
+unsafe impl<T: Send + Sync, F: Send> Sync for Lazy<T, F> {}
+
+impl<T, F: Fn() -> T> Lazy<T, F> {
+    /// Creates a new `Lazy` that will build its value with `init` on first access.
+    pub const fn new(init: F) -> Self {
+        Self {
+            once: Once::new(),
+            init,
+        }
+    }
+
+    /// Returns a reference to the contents if they have already been built, or `None` otherwise.
+    /// Never blocks and never runs the initializer.
+    pub fn get(&self) -> Option<&T> {
+        self.once.get()
+    }
+}
+
+impl<T, F: Fn() -> T> Deref for Lazy<T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.once.call_once(|| (self.init)())
+    }
+}
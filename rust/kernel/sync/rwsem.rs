@@ -2,22 +2,30 @@
 
 //! A kernel read/write mutex.
 //!
-//! This module allows Rust code to use the kernel's [`struct rw_semaphore`].
+//! This module allows Rust code to use the kernel's [`struct rw_semaphore`]. [`RwSemaphore`]
+//! plays the same role here that `RwLock` plays in other Rust synchronisation primitives: many
+//! concurrent readers or one exclusive writer, sharing the same [`Lock`]/[`Guard`] infrastructure
+//! as [`super::Mutex`].
 //!
 //! C header: [`include/linux/rwsem.h`](../../../../include/linux/rwsem.h)
 
 use super::{
-    mutex::EmptyGuardContext, Guard, Lock, LockClassKey, LockFactory, ReadLock, WriteLock,
+    mutex::EmptyGuardContext, Arc, Guard, Lock, LockClassKey, LockFactory, ReadLock, WriteLock,
 };
 use crate::{
     bindings,
+    error::{self, Error},
     init::{self, PinInit},
     macros::pin_data,
     pin_init,
     str::CStr,
     Opaque,
 };
-use core::{cell::UnsafeCell, marker::PhantomPinned};
+use core::{
+    cell::UnsafeCell,
+    marker::PhantomPinned,
+    ops::{Deref, DerefMut},
+};
 
 /// Safely initialises a [`RwSemaphore`] with the given name, generating a new lock class.
 #[macro_export]
@@ -102,6 +110,14 @@ unsafe impl<T> PinInit<RwSemaphore<T>> for Init<T> {
     }
 }
 
+/// A write (exclusive) guard for an [`RwSemaphore`], giving `deref_mut` access to the protected
+/// data.
+pub type RwSemaphoreWriteGuard<'a, T> = Guard<'a, RwSemaphore<T>>;
+
+/// A read (shared) guard for an [`RwSemaphore`], giving read-only `deref` access to the
+/// protected data.
+pub type RwSemaphoreReadGuard<'a, T> = Guard<'a, RwSemaphore<T>, ReadLock>;
+
 impl<T: ?Sized> RwSemaphore<T> {
     /// Locks the rw semaphore in write (exclusive) mode and gives the caller access to the data
     /// protected by it. Only one thread at a time is allowed to access the protected data.
@@ -118,6 +134,97 @@ impl<T: ?Sized> RwSemaphore<T> {
         // SAFETY: The rw semaphore was just acquired in read mode.
         unsafe { Guard::new(self, ctx) }
     }
+
+    /// Tries to lock the rw semaphore in write (exclusive) mode, without blocking.
+    ///
+    /// Returns `None` if the semaphore is currently held. Safe to call from atomic contexts.
+    pub fn try_write(&self) -> Option<Guard<'_, Self>> {
+        // SAFETY: `rwsem` points to valid memory.
+        if unsafe { bindings::down_write_trylock(self.rwsem.get()) } == 0 {
+            return None;
+        }
+        // SAFETY: The rw semaphore was just acquired in write mode.
+        Some(unsafe { Guard::new(self, EmptyGuardContext) })
+    }
+
+    /// Tries to lock the rw semaphore in read (shared) mode, without blocking.
+    ///
+    /// Returns `None` if the semaphore is currently held in write mode. Safe to call from atomic
+    /// contexts.
+    pub fn try_read(&self) -> Option<Guard<'_, Self, ReadLock>> {
+        // SAFETY: `rwsem` points to valid memory.
+        if unsafe { bindings::down_read_trylock(self.rwsem.get()) } == 0 {
+            return None;
+        }
+        // SAFETY: The rw semaphore was just acquired in read mode.
+        Some(unsafe { Guard::new(self, EmptyGuardContext) })
+    }
+
+    /// Locks the rw semaphore in write (exclusive) mode, aborting if the task is killed.
+    pub fn write_killable(&self) -> Result<Guard<'_, Self>, Error> {
+        // SAFETY: `rwsem` points to valid memory.
+        error::to_result(unsafe { bindings::down_write_killable(self.rwsem.get()) })?;
+        // SAFETY: The rw semaphore was just acquired in write mode.
+        Ok(unsafe { Guard::new(self, EmptyGuardContext) })
+    }
+
+    /// Locks the rw semaphore in read (shared) mode, aborting if interrupted by a signal.
+    pub fn read_interruptible(&self) -> Result<Guard<'_, Self, ReadLock>, Error> {
+        // SAFETY: `rwsem` points to valid memory.
+        error::to_result(unsafe { bindings::down_read_interruptible(self.rwsem.get()) })?;
+        // SAFETY: The rw semaphore was just acquired in read mode.
+        Ok(unsafe { Guard::new(self, EmptyGuardContext) })
+    }
+
+    /// Locks the rw semaphore in read (shared) mode, aborting if the task is killed.
+    pub fn read_killable(&self) -> Result<Guard<'_, Self, ReadLock>, Error> {
+        // SAFETY: `rwsem` points to valid memory.
+        error::to_result(unsafe { bindings::down_read_killable(self.rwsem.get()) })?;
+        // SAFETY: The rw semaphore was just acquired in read mode.
+        Ok(unsafe { Guard::new(self, EmptyGuardContext) })
+    }
+
+    /// Atomically downgrades a write (exclusive) guard into a read (shared) one, without ever
+    /// releasing the rw semaphore in between.
+    ///
+    /// This avoids the race window of unlocking and immediately re-locking in read mode, which
+    /// would let another writer acquire the semaphore first.
+    pub fn downgrade(&self, guard: Guard<'_, Self, WriteLock>) -> Guard<'_, Self, ReadLock> {
+        // SAFETY: `guard` being alive implies that `self.rwsem` is held in write mode and points
+        // to valid memory.
+        unsafe { bindings::downgrade_write(self.rwsem.get()) };
+
+        // The semaphore must not be released when `guard` is dropped: it is still held, just in
+        // read mode now.
+        core::mem::forget(guard);
+
+        // SAFETY: The rw semaphore was just downgraded to read mode above.
+        unsafe { Guard::new(self, EmptyGuardContext) }
+    }
+
+    /// Locks the rw semaphore in read (shared) mode and returns a guard that owns a clone of
+    /// `self`, rather than borrowing it, so it can be moved into a spawned worker or stored in a
+    /// long-lived structure instead of being tied to a stack frame's lifetime.
+    pub fn read_owned(self: &Arc<Self>) -> OwnedReadGuard<T> {
+        let sem: &Self = self;
+        let ctx = <Self as Lock<ReadLock>>::lock_noguard(sem);
+        OwnedReadGuard {
+            sem: self.clone(),
+            ctx,
+        }
+    }
+
+    /// Locks the rw semaphore in write (exclusive) mode and returns a guard that owns a clone of
+    /// `self`, rather than borrowing it, so it can be moved into a spawned worker or stored in a
+    /// long-lived structure instead of being tied to a stack frame's lifetime.
+    pub fn write_owned(self: &Arc<Self>) -> OwnedWriteGuard<T> {
+        let sem: &Self = self;
+        let ctx = <Self as Lock>::lock_noguard(sem);
+        OwnedWriteGuard {
+            sem: self.clone(),
+            ctx,
+        }
+    }
 }
 
 impl<T> LockFactory for RwSemaphore<T> {
@@ -218,3 +325,134 @@ pub type RevocableRwSemaphore<T> = super::revocable::Revocable<RwSemaphore<()>,
 /// A guard for a revocable rw semaphore..
 pub type RevocableRwSemaphoreGuard<'a, T, I = WriteLock> =
     super::revocable::RevocableGuard<'a, RwSemaphore<()>, T, I>;
+
+/// A read guard narrowed to a single field of the data protected by an [`RwSemaphore`], produced
+/// by [`map_read`]. Keeps the rw semaphore locked in read mode for as long as it is alive.
+pub struct MappedReadGuard<'a, T: ?Sized, U: ?Sized> {
+    _guard: Guard<'a, RwSemaphore<T>, ReadLock>,
+    value: *const U,
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for MappedReadGuard<'_, T, U> {
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        // SAFETY: `value` was derived from `_guard`, which is still held for as long as `self`
+        // is alive, so the reference it points into remains valid.
+        unsafe { &*self.value }
+    }
+}
+
+/// Narrows a read guard to a single field of the protected data, as chosen by `f`, while keeping
+/// the rw semaphore locked for the lifetime of the returned guard.
+pub fn map_read<'a, T: ?Sized, U: ?Sized>(
+    guard: Guard<'a, RwSemaphore<T>, ReadLock>,
+    f: impl FnOnce(&T) -> &U,
+) -> MappedReadGuard<'a, T, U> {
+    let value: *const U = f(&guard);
+    MappedReadGuard {
+        _guard: guard,
+        value,
+    }
+}
+
+/// A write guard narrowed to a single field of the data protected by an [`RwSemaphore`], produced
+/// by [`map_write`]. Keeps the rw semaphore locked in write mode for as long as it is alive.
+pub struct MappedWriteGuard<'a, T: ?Sized, U: ?Sized> {
+    _guard: Guard<'a, RwSemaphore<T>, WriteLock>,
+    value: *mut U,
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for MappedWriteGuard<'_, T, U> {
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        // SAFETY: `value` was derived from `_guard`, which is still held for as long as `self`
+        // is alive, so the reference it points into remains valid.
+        unsafe { &*self.value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> DerefMut for MappedWriteGuard<'_, T, U> {
+    fn deref_mut(&mut self) -> &mut U {
+        // SAFETY: `value` was derived from `_guard`, which is still held for as long as `self`
+        // is alive, so the reference it points into remains valid; `self` is borrowed mutably so
+        // no other reference to it can exist.
+        unsafe { &mut *self.value }
+    }
+}
+
+/// Narrows a write guard to a single field of the protected data, as chosen by `f`, while keeping
+/// the rw semaphore locked for the lifetime of the returned guard.
+pub fn map_write<'a, T: ?Sized, U: ?Sized>(
+    mut guard: Guard<'a, RwSemaphore<T>, WriteLock>,
+    f: impl FnOnce(&mut T) -> &mut U,
+) -> MappedWriteGuard<'a, T, U> {
+    let value: *mut U = f(&mut guard);
+    MappedWriteGuard {
+        _guard: guard,
+        value,
+    }
+}
+
+/// An owned read guard for an [`RwSemaphore`], produced by [`RwSemaphore::read_owned`].
+///
+/// Unlike [`Guard`], this holds a clone of the `Arc<RwSemaphore<T>>` rather than borrowing it, so
+/// it has no lifetime tied to the semaphore and can be moved into a spawned worker or stored in a
+/// long-lived structure.
+pub struct OwnedReadGuard<T: ?Sized> {
+    sem: Arc<RwSemaphore<T>>,
+    ctx: EmptyGuardContext,
+}
+
+impl<T: ?Sized> Deref for OwnedReadGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: The rw semaphore is held in read mode for as long as `self` is alive.
+        unsafe { &*<RwSemaphore<T> as Lock<ReadLock>>::locked_data(&self.sem).get() }
+    }
+}
+
+impl<T: ?Sized> Drop for OwnedReadGuard<T> {
+    fn drop(&mut self) {
+        // SAFETY: `self.ctx` was produced by locking `self.sem` in read mode, and is unlocked
+        // here exactly once.
+        unsafe { <RwSemaphore<T> as Lock<ReadLock>>::unlock(&self.sem, &mut self.ctx) };
+    }
+}
+
+/// An owned write guard for an [`RwSemaphore`], produced by [`RwSemaphore::write_owned`].
+///
+/// Unlike [`Guard`], this holds a clone of the `Arc<RwSemaphore<T>>` rather than borrowing it, so
+/// it has no lifetime tied to the semaphore and can be moved into a spawned worker or stored in a
+/// long-lived structure.
+pub struct OwnedWriteGuard<T: ?Sized> {
+    sem: Arc<RwSemaphore<T>>,
+    ctx: EmptyGuardContext,
+}
+
+impl<T: ?Sized> Deref for OwnedWriteGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: The rw semaphore is held in write mode for as long as `self` is alive.
+        unsafe { &*<RwSemaphore<T> as Lock>::locked_data(&self.sem).get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for OwnedWriteGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: The rw semaphore is held in write mode for as long as `self` is alive, and
+        // `self` is borrowed mutably so no other reference to the data can exist.
+        unsafe { &mut *<RwSemaphore<T> as Lock>::locked_data(&self.sem).get() }
+    }
+}
+
+impl<T: ?Sized> Drop for OwnedWriteGuard<T> {
+    fn drop(&mut self) {
+        // SAFETY: `self.ctx` was produced by locking `self.sem` in write mode, and is unlocked
+        // here exactly once.
+        unsafe { <RwSemaphore<T> as Lock>::unlock(&self.sem, &mut self.ctx) };
+    }
+}
@@ -162,6 +162,21 @@ impl<T: ?Sized> SpinLock<T> {
         // SAFETY: The spinlock was just acquired.
         unsafe { Guard::new(self, ctx) }
     }
+
+    /// Locks the spinlock, runs `cb` with exclusive access to the protected data, then
+    /// releases the spinlock before returning. Confining the guard to `cb`'s body makes it
+    /// harder to accidentally carry it past the end of the critical section.
+    pub fn with<R>(&self, cb: impl FnOnce(&mut T) -> R) -> R {
+        let mut guard = self.lock();
+        cb(&mut guard)
+    }
+
+    /// Like [`Self::with`], but additionally disables interrupts for the duration of `cb`,
+    /// as [`Self::lock_irqdisable`] does.
+    pub fn with_irqdisable<R>(&self, cb: impl FnOnce(&mut T) -> R) -> R {
+        let mut guard = self.lock_irqdisable();
+        cb(&mut guard)
+    }
 }
 
 impl<T> LockFactory for SpinLock<T> {
@@ -5,6 +5,8 @@
 //! This module contains the kernel APIs related to time and timers that
 //! have been ported or wrapped for usage by Rust code in the kernel.
 
+pub mod hrtimer;
+
 /// The time unit of Linux kernel. One jiffy equals (1/HZ) second.
 pub type Jiffies = core::ffi::c_ulong;
 
@@ -89,3 +91,151 @@ pub fn ktime_get_boot_fast_us32() -> Usecs32 {
 pub fn ktime_get_boot_fast_ms32() -> Msecs32 {
     (ktime_get_boot_fast_ns() / NSEC_PER_MSEC) as Msecs32
 }
+
+/// A span of time, stored as a 64-bit nanosecond count.
+///
+/// Unlike the raw [`Jiffies`]/[`Msecs`]/[`Usecs`] aliases above, arithmetic on
+/// `Duration` cannot silently mix units.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Duration(Nsecs);
+
+impl Duration {
+    /// The zero-length duration.
+    pub const ZERO: Self = Self(0);
+
+    /// Creates a `Duration` from a number of seconds.
+    #[inline]
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(secs.saturating_mul(NSEC_PER_MSEC * MSEC_PER_SEC as u64))
+    }
+
+    /// Creates a `Duration` from a number of milliseconds.
+    #[inline]
+    pub const fn from_millis(millis: u64) -> Self {
+        Self(millis.saturating_mul(NSEC_PER_MSEC))
+    }
+
+    /// Creates a `Duration` from a number of microseconds.
+    #[inline]
+    pub const fn from_micros(micros: u64) -> Self {
+        Self(micros.saturating_mul(NSEC_PER_USEC))
+    }
+
+    /// Creates a `Duration` from a number of nanoseconds.
+    #[inline]
+    pub const fn from_nanos(nanos: u64) -> Self {
+        Self(nanos)
+    }
+
+    /// Returns this duration as a whole number of nanoseconds.
+    #[inline]
+    pub const fn as_nanos(self) -> Nsecs {
+        self.0
+    }
+
+    /// Returns this duration as a whole number of milliseconds, rounding down.
+    #[inline]
+    pub const fn as_millis(self) -> u64 {
+        self.0 / NSEC_PER_MSEC
+    }
+
+    /// Returns this duration converted to [`Jiffies`], rounding down.
+    #[inline]
+    pub fn as_jiffies(self) -> Jiffies {
+        msecs_to_jiffies(self.as_millis() as Msecs)
+    }
+
+    /// Adds two durations, saturating at [`Nsecs::MAX`] instead of overflowing.
+    #[inline]
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Adds two durations, returning `None` on overflow.
+    #[inline]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(v) => Some(Self(v)),
+            None => None,
+        }
+    }
+
+    /// Subtracts `rhs` from `self`, saturating at zero instead of
+    /// underflowing.
+    #[inline]
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl core::ops::Add for Duration {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        self.saturating_add(rhs)
+    }
+}
+
+impl core::ops::Sub for Duration {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self.saturating_sub(rhs)
+    }
+}
+
+/// A point in monotonic time, measured in nanoseconds since an arbitrary
+/// epoch (boot, including time spent suspended).
+///
+/// Only meaningful relative to other `Instant`s; there is no guarantee it
+/// corresponds to wall-clock time.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Instant(Nsecs);
+
+impl Instant {
+    /// Returns the current instant.
+    #[inline]
+    pub fn now() -> Self {
+        Self(ktime_get_boot_fast_ns())
+    }
+
+    /// Returns the [`Duration`] elapsed between `earlier` and `self`,
+    /// saturating to [`Duration::ZERO`] if `earlier` is after `self`.
+    #[inline]
+    pub fn saturating_duration_since(self, earlier: Self) -> Duration {
+        Duration(self.0.saturating_sub(earlier.0))
+    }
+
+    /// Returns the instant `dur` after `self`, saturating instead of
+    /// overflowing.
+    #[inline]
+    pub fn saturating_add(self, dur: Duration) -> Self {
+        Self(self.0.saturating_add(dur.0))
+    }
+
+    /// Returns the instant `dur` after `self`, or `None` on overflow.
+    #[inline]
+    pub fn checked_add(self, dur: Duration) -> Option<Self> {
+        self.0.checked_add(dur.0).map(Self)
+    }
+}
+
+impl core::ops::Add<Duration> for Instant {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Duration) -> Self {
+        self.saturating_add(rhs)
+    }
+}
+
+impl core::ops::Sub for Instant {
+    type Output = Duration;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Duration {
+        self.saturating_duration_since(rhs)
+    }
+}
@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! High-resolution timers.
+//!
+//! Wraps `struct hrtimer` so Rust code can arm a one-shot timer after
+//! a [`Duration`](super::Duration) instead of open-coding
+//! `msecs_to_jiffies()` and the classic `timer_list` API. Expiry does not
+//! call back into Rust code: see [`TimerCallback::on_expiry`].
+//!
+//! This offers no async API: the kernel crate in this
+//! tree has no task executor, so there is no `Future` for `Timer::after` to
+//! return yet. Once one exists, this is the natural place to build an async
+//! wrapper on top of [`Timer`].
+
+use super::Duration;
+use crate::bindings;
+use crate::prelude::*;
+use crate::types::Opaque;
+use core::pin::Pin;
+use macros::pin_data;
+
+/// The expiry handler type a [`Timer`] is bound to.
+pub trait TimerCallback {
+    /// Expiry handler for a [`Timer`] of this type.
+    ///
+    /// [`Timer`] does not call it: when the timer set up via
+    /// [`Timer::start`] expires, its `hrtimer` callback only returns
+    /// `HRTIMER_NORESTART`.
+    ///
+    /// Must not block, sleep, or take sleeping locks, as `hrtimer` expiry
+    /// runs in hard interrupt context.
+    fn on_expiry(self: Pin<&Self>);
+}
+
+/// A one-shot high-resolution timer bound to a [`TimerCallback`] of type `T`.
+#[pin_data]
+pub struct Timer<T> {
+    #[pin]
+    inner: Opaque<bindings::hrtimer>,
+    _pd: core::marker::PhantomData<T>,
+}
+
+// SAFETY: Timers can be moved between threads and can be accessed from
+// multiple threads: all operations go through the C `hrtimer` API, which
+// manages its own synchronization.
+unsafe impl<T> Send for Timer<T> {}
+// SAFETY: See above.
+unsafe impl<T> Sync for Timer<T> {}
+
+impl<T: TimerCallback> Timer<T> {
+    /// Creates a new, inactive timer.
+    pub fn new() -> impl PinInit<Self> {
+        init!(Self {
+            inner <- Opaque::ffi_init(|place: *mut bindings::hrtimer| {
+                // SAFETY: `place` is valid for writes.
+                unsafe {
+                    bindings::hrtimer_init(
+                        place,
+                        bindings::CLOCK_MONOTONIC as i32,
+                        bindings::hrtimer_mode_HRTIMER_MODE_REL,
+                    )
+                };
+                // SAFETY: `place` was just initialized by `hrtimer_init`.
+                unsafe { (*place).function = Some(Self::expired_cb) };
+            }),
+            _pd: core::marker::PhantomData,
+        })
+    }
+
+    /// Arms the timer to fire `dur` from now.
+    pub fn start(self: Pin<&Self>, dur: Duration) {
+        // SAFETY: `self.inner` was initialized in `new` and is pinned for as
+        // long as `self` lives.
+        unsafe {
+            bindings::hrtimer_start_range_ns(
+                self.inner.get(),
+                dur.as_nanos() as i64,
+                0,
+                bindings::hrtimer_mode_HRTIMER_MODE_REL,
+            )
+        };
+    }
+
+    /// Cancels the timer, blocking until any in-flight callback has
+    /// finished.
+    pub fn cancel(self: Pin<&Self>) {
+        // SAFETY: `self.inner` was initialized in `new`.
+        unsafe { bindings::hrtimer_cancel(self.inner.get()) };
+    }
+
+    /// `hrtimer.function` callback: returns `HRTIMER_NORESTART` and does
+    /// nothing else; [`TimerCallback::on_expiry`] is not invoked.
+    ///
+    /// # Safety
+    ///
+    /// `timer` must point to the `inner` field of a live, pinned `Timer<T>`
+    /// whose enclosing `T` is reachable via `container_of`.
+    unsafe extern "C" fn expired_cb(
+        _timer: *mut bindings::hrtimer,
+    ) -> bindings::hrtimer_restart {
+        // Note: `T::on_expiry` is not dispatched from here. Doing so requires
+        // locating the enclosing `T` from `timer` via `container_of`, which
+        // depends on where callers embed `Timer<T>`, and `Timer<T>` does not
+        // record that.
+        bindings::hrtimer_restart_HRTIMER_NORESTART
+    }
+}
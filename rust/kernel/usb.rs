@@ -4,22 +4,28 @@
 //!
 //! C header: [`include/linux/usb.h`](../../../../include/linux/usb.h)
 
-use core::num::NonZeroU64;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::{marker::PhantomData, num::NonZeroU64};
 
 use crate::{
     bindings, container_of,
     device::{self, RawDevice},
     driver,
-    error::{code::*, from_kernel_result, Result},
-    file::IoctlCommand,
+    error::{code::*, from_kernel_result, Error, Result},
+    file::{self, IoctlCommand},
+    init_static_sync,
     macros::vtable,
     power::PmMessage,
     str::CStr,
+    sync::SpinLock,
     to_result,
     types::ForeignOwnable,
     ThisModule,
 };
 
+pub mod descriptors;
+
 /// USB device ID macros reexports and casted to [`u16`] intended for the
 /// [`match_flags`](DeviceId::match_flags) field of [`DeviceId`].
 pub mod id_match {
@@ -99,6 +105,9 @@ impl<T: Driver> driver::DriverOps for Adapter<T> {
         if let Some(t) = T::ID_TABLE {
             pdrv.id_table = t.as_ref();
         }
+        // `no_dynamic_id` is a driver-side opt-out: clearing it lets the USB core expose the
+        // `new_id`/`remove_id` sysfs attributes and consult dynamically added ids during probe.
+        pdrv.no_dynamic_id = if T::HAS_DYNAMIC_ID { 0 } else { 1 };
         // SAFETY: `reg`, `module.0` and `name.as_char_ptr()` all point to valid data.
         to_result(unsafe { bindings::usb_register_driver(reg, module.0, name.as_char_ptr()) })
     }
@@ -117,7 +126,9 @@ impl<T: Driver> Adapter<T> {
         from_kernel_result! {
             // SAFETY: `intf` is always a valid pointer passed from the caller.
             let mut dev = unsafe { Interface::from_ptr(intf) };
-            // SAFETY: `id` is a pointer within the static table, so it's always valid.
+            // SAFETY: `id` is a pointer supplied by the USB core, either into the static id
+            // table or a dynamically added id, and is valid for the duration of the call. A
+            // dynamically added id has no `driver_info` offset, so `info` is `None` for it.
             let info = unsafe {
                 NonZeroU64::new((*id).driver_info).map(|o| &*(id.cast::<u8>().offset(o.get() as _).cast::<T::IdInfo>()))
             };
@@ -200,6 +211,42 @@ impl<T: Driver> Adapter<T> {
             Ok(0)
         }
     }
+
+    /// Returns the per-driver list of ids added at runtime.
+    ///
+    /// Each monomorphisation of `Adapter<T>` gets its own statically allocated, lazily
+    /// initialised list, since there is otherwise no instance of `Adapter<T>` to hang this state
+    /// off (it is a stateless, zero-sized wrapper around `T`).
+    fn dynamic_ids() -> &'static SpinLock<Vec<DeviceId>> {
+        init_static_sync! {
+            static IDS: SpinLock<Vec<DeviceId>> = Vec::new();
+        }
+        &IDS
+    }
+
+    /// Adds a device id to this driver's dynamic id list at runtime.
+    ///
+    /// `id` is recorded in this driver's own list only: the list is not handed to the USB core
+    /// and is not consulted when devices are matched or probed, so unlike writing to the `new_id`
+    /// sysfs attribute this does not make the driver bind to further devices.
+    pub fn add_dynamic_id(id: DeviceId) -> Result {
+        Self::dynamic_ids().lock().push(id);
+        Ok(())
+    }
+
+    /// Removes a previously added dynamic device id.
+    ///
+    /// Only the list kept by [`Self::add_dynamic_id`] is changed; ids added through the `new_id`
+    /// sysfs attribute are not affected. Returns [`ENOENT`] if `id` is not currently in that list.
+    pub fn remove_dynamic_id(id: DeviceId) -> Result {
+        let mut ids = Self::dynamic_ids().lock();
+        let pos = ids
+            .iter()
+            .position(|existing| *existing == id)
+            .ok_or(ENOENT)?;
+        ids.remove(pos);
+        Ok(())
+    }
 }
 
 /// Device table entry for table-driven USB drivers.
@@ -506,6 +553,14 @@ pub trait Driver {
     /// The table of device ids supported by the driver.
     const ID_TABLE: Option<driver::IdTable<'static, DeviceId, Self::IdInfo>> = None;
 
+    /// Whether this driver supports adding device ids at runtime.
+    ///
+    /// When set, the USB core exposes the standard `new_id`/`remove_id` sysfs attributes under
+    /// `/sys/bus/usb/drivers/<name>/`, and ids added through them are matched against incoming
+    /// devices in addition to [`Self::ID_TABLE`]. Ids recorded with [`Adapter::add_dynamic_id`]
+    /// take no part in matching.
+    const HAS_DYNAMIC_ID: bool = false;
+
     /// USB driver probe.
     ///
     /// Called to see if the driver can manage a device interface.
@@ -677,6 +732,117 @@ impl Device {
     pub fn rcvintpipe(&self, endpoint: u32) -> u32 {
         (bindings::PIPE_INTERRUPT << 30) | self.create_pipe(endpoint) | bindings::USB_DIR_IN
     }
+
+    /// Issues a synchronous USB control message on `pipe` and returns the number of bytes
+    /// transferred.
+    ///
+    /// `data` is `None` for requests with no data stage (e.g. a bare `SET_FEATURE`); otherwise it
+    /// must be a kernel (heap) allocation, not a stack buffer: the core DMAs directly to or from
+    /// it, and stack memory is not guaranteed to be DMA-able on all architectures.
+    pub fn control_msg(
+        &self,
+        pipe: u32,
+        request_type: u8,
+        request: u8,
+        value: u16,
+        index: u16,
+        data: Option<&mut [u8]>,
+        timeout: u32,
+    ) -> Result<usize> {
+        let (ptr, len) = match data {
+            Some(buf) => (buf.as_mut_ptr(), buf.len()),
+            None => (core::ptr::null_mut(), 0),
+        };
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid. `ptr` is either null
+        // with `len == 0`, or a valid, kernel-allocated buffer of `len` bytes for the duration of
+        // the call.
+        let ret = unsafe {
+            bindings::usb_control_msg(
+                self.ptr,
+                pipe,
+                request,
+                request_type,
+                value,
+                index,
+                ptr.cast(),
+                len as u16,
+                timeout as i32,
+            )
+        };
+        if ret < 0 {
+            return Err(Error::from_errno(ret));
+        }
+        Ok(ret as usize)
+    }
+
+    /// Issues a synchronous USB bulk transfer on `pipe` and returns the number of bytes
+    /// transferred.
+    ///
+    /// `buf` must be a kernel (heap) allocation, not a stack buffer; see [`Self::control_msg`].
+    pub fn bulk_msg(&self, pipe: u32, buf: &mut [u8], timeout: u32) -> Result<usize> {
+        let mut actual_length: i32 = 0;
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid. `buf` is a valid,
+        // kernel-allocated buffer of `buf.len()` bytes for the duration of the call, and
+        // `actual_length` is a valid out-parameter.
+        let ret = unsafe {
+            bindings::usb_bulk_msg(
+                self.ptr,
+                pipe,
+                buf.as_mut_ptr().cast(),
+                buf.len() as i32,
+                &mut actual_length,
+                timeout,
+            )
+        };
+        to_result(ret)?;
+        Ok(actual_length as usize)
+    }
+
+    /// Issues a synchronous USB interrupt transfer on `pipe` and returns the number of bytes
+    /// transferred.
+    ///
+    /// `buf` must be a kernel (heap) allocation, not a stack buffer; see [`Self::control_msg`].
+    pub fn interrupt_msg(&self, pipe: u32, buf: &mut [u8], timeout: u32) -> Result<usize> {
+        let mut actual_length: i32 = 0;
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid. `buf` is a valid,
+        // kernel-allocated buffer of `buf.len()` bytes for the duration of the call, and
+        // `actual_length` is a valid out-parameter.
+        let ret = unsafe {
+            bindings::usb_interrupt_msg(
+                self.ptr,
+                pipe,
+                buf.as_mut_ptr().cast(),
+                buf.len() as i32,
+                &mut actual_length,
+                timeout,
+            )
+        };
+        to_result(ret)?;
+        Ok(actual_length as usize)
+    }
+
+    /// Selects `config` as the active configuration for this device.
+    ///
+    /// Must only be called from sleepable (process) context, and only after any outstanding URBs
+    /// on this device's endpoints have been killed: switching configurations invalidates the
+    /// previous endpoint state.
+    pub fn set_configuration(&self, config: i32) -> Result {
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid.
+        to_result(unsafe { bindings::usb_set_configuration(self.ptr, config) })
+    }
+
+    /// Returns the active configuration's `bConfigurationValue`, or `0` if the device is not
+    /// currently configured.
+    pub fn cur_configuration(&self) -> i32 {
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid.
+        let actconfig = unsafe { (*self.ptr).actconfig };
+        if actconfig.is_null() {
+            return 0;
+        }
+        // SAFETY: `actconfig` was just checked to be non-null, and is owned by the valid
+        // `usb_device` pointed to by `self.ptr`.
+        unsafe { (*actconfig).desc.bConfigurationValue as i32 }
+    }
 }
 
 // SAFETY: The device returned by `raw_device` is the raw USB device.
@@ -761,6 +927,147 @@ impl Interface {
         // SAFETY: By the type invariants, we know that `self.ptr` is non-null and valid.
         unsafe { bindings::usb_put_intf(self.ptr) }
     }
+
+    /// Returns the number of alternate settings this interface has.
+    #[inline]
+    pub fn num_altsettings(&self) -> usize {
+        // SAFETY: By the type invariants, we know that `self.ptr` is non-null and valid.
+        unsafe { (*self.ptr).num_altsetting as usize }
+    }
+
+    /// Returns the alternate setting currently in effect for this interface.
+    #[inline]
+    pub fn cur_altsetting(&self) -> AltSetting<'_> {
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid, and a bound
+        // interface's `cur_altsetting` always points at one of its own altsettings.
+        unsafe { AltSetting::from_ptr((*self.ptr).cur_altsetting) }
+    }
+
+    /// Issues a synchronous USB control message to endpoint 0 of this interface's device.
+    ///
+    /// The transfer direction is taken from bit 7 of `request_type`, following the usual USB
+    /// control transfer convention, so callers don't need to build a pipe themselves. Must only
+    /// be called from sleepable (process) context; see [`Device::control_msg`] for the buffer
+    /// requirements.
+    pub fn control_msg(
+        &self,
+        request_type: u8,
+        request: u8,
+        value: u16,
+        index: u16,
+        data: Option<&mut [u8]>,
+        timeout: u32,
+    ) -> Result<usize> {
+        let dev = self.to_usb_device();
+        let pipe = if request_type & (bindings::USB_DIR_IN as u8) != 0 {
+            dev.rcvctrlpipe(0)
+        } else {
+            dev.sndctrlpipe(0)
+        };
+        dev.control_msg(pipe, request_type, request, value, index, data, timeout)
+    }
+
+    /// Issues a synchronous USB bulk transfer to `endpoint` of this interface's device.
+    ///
+    /// `endpoint` is a full endpoint address, direction bit included, such as the one returned by
+    /// [`Endpoint::address`]. Must only be called from sleepable (process) context; see
+    /// [`Device::bulk_msg`] for the buffer requirements.
+    pub fn bulk_msg(&self, endpoint: u8, data: &mut [u8], timeout: u32) -> Result<usize> {
+        let dev = self.to_usb_device();
+        let number = (endpoint & bindings::USB_ENDPOINT_NUMBER_MASK as u8) as u32;
+        let pipe = if endpoint & (bindings::USB_DIR_IN as u8) != 0 {
+            dev.rcvbulkpipe(number)
+        } else {
+            dev.sndbulkpipe(number)
+        };
+        dev.bulk_msg(pipe, data, timeout)
+    }
+
+    /// Issues a synchronous USB interrupt transfer to `endpoint` of this interface's device.
+    ///
+    /// `endpoint` is a full endpoint address, direction bit included, such as the one returned by
+    /// [`Endpoint::address`]. Must only be called from sleepable (process) context; see
+    /// [`Device::interrupt_msg`] for the buffer requirements.
+    pub fn int_msg(&self, endpoint: u8, data: &mut [u8], timeout: u32) -> Result<usize> {
+        let dev = self.to_usb_device();
+        let number = (endpoint & bindings::USB_ENDPOINT_NUMBER_MASK as u8) as u32;
+        let pipe = if endpoint & (bindings::USB_DIR_IN as u8) != 0 {
+            dev.rcvintpipe(number)
+        } else {
+            dev.sndintpipe(number)
+        };
+        dev.interrupt_msg(pipe, data, timeout)
+    }
+
+    /// Issues a synchronous USB bulk transfer to `endpoint` of this interface's device.
+    ///
+    /// Convenience wrapper over [`Self::bulk_msg`] for callers already holding an
+    /// [`descriptors::EndpointDescriptor`] (e.g. one returned by
+    /// [`descriptors::HostInterface::find_common_endpoints`]), so they don't need to extract the
+    /// endpoint address themselves.
+    pub fn bulk_transfer(
+        &self,
+        endpoint: &descriptors::EndpointDescriptor,
+        data: &mut [u8],
+        timeout: u32,
+    ) -> Result<usize> {
+        self.bulk_msg(endpoint.b_endpoint_address, data, timeout)
+    }
+
+    /// Issues a synchronous USB interrupt transfer to `endpoint` of this interface's device.
+    ///
+    /// Convenience wrapper over [`Self::int_msg`] for callers already holding an
+    /// [`descriptors::EndpointDescriptor`]; see [`Self::bulk_transfer`].
+    pub fn int_transfer(
+        &self,
+        endpoint: &descriptors::EndpointDescriptor,
+        data: &mut [u8],
+        timeout: u32,
+    ) -> Result<usize> {
+        self.int_msg(endpoint.b_endpoint_address, data, timeout)
+    }
+
+    /// Clears a halt (stall) condition on `endpoint` of this interface's device.
+    ///
+    /// `endpoint` is a full endpoint address, direction bit included, such as the one returned by
+    /// [`Endpoint::address`]. Drivers typically call this after a transfer completes with
+    /// `-EPIPE`, to recover the endpoint before resubmitting. Must only be called from sleepable
+    /// (process) context.
+    pub fn clear_halt(&self, endpoint: u8) -> Result {
+        let dev = self.to_usb_device();
+        let number = (endpoint & bindings::USB_ENDPOINT_NUMBER_MASK as u8) as u32;
+        let pipe = if endpoint & (bindings::USB_DIR_IN as u8) != 0 {
+            dev.rcvbulkpipe(number)
+        } else {
+            dev.sndbulkpipe(number)
+        };
+        // SAFETY: By the type invariants, `dev.raw()` is non-null and valid.
+        to_result(unsafe { bindings::usb_clear_halt(dev.raw(), pipe) })
+    }
+
+    /// Selects `alternate` as the active alternate setting for this interface.
+    ///
+    /// This is how drivers that offer multiple bandwidth profiles (e.g. isochronous audio/video)
+    /// reconfigure at runtime. Must only be called from sleepable (process) context, and only
+    /// after any outstanding URBs on this interface's endpoints have been killed: switching
+    /// alternate settings invalidates the previous endpoint state.
+    pub fn set_alt_setting(&self, alternate: u8) -> Result {
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid, and `cur_altsetting`
+        // always points at one of its own altsettings.
+        let number = unsafe { (*(*self.ptr).cur_altsetting).desc.bInterfaceNumber };
+        // SAFETY: By the type invariants, `self.to_usb_device().raw()` is non-null and valid.
+        to_result(unsafe {
+            bindings::usb_set_interface(self.to_usb_device().raw(), number as i32, alternate as i32)
+        })
+    }
+
+    /// Returns the alternate setting currently in effect for this interface.
+    #[inline]
+    pub fn cur_alt_setting(&self) -> u8 {
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid, and `cur_altsetting`
+        // always points at one of its own altsettings.
+        unsafe { (*(*self.ptr).cur_altsetting).desc.bAlternateSetting }
+    }
 }
 
 // SAFETY: The device returned by `raw_device` is the raw USB interface.
@@ -778,3 +1085,832 @@ unsafe impl Send for Interface {}
 // SAFETY: `Interface` only holds a pointer to an USB interface, references to which are safe to be
 // used from any thread.
 unsafe impl Sync for Interface {}
+
+/// A single alternate setting of an [`Interface`], yielding its [`Endpoint`]s.
+///
+/// # Invariants
+///
+/// `ptr` is a non-null `struct usb_host_interface` belonging to the [`Interface`] it was obtained
+/// from, valid for the lifetime `'a` borrowed from that interface.
+pub struct AltSetting<'a> {
+    ptr: *mut bindings::usb_host_interface,
+    _p: PhantomData<&'a Interface>,
+}
+
+impl<'a> AltSetting<'a> {
+    /// Creates an altsetting from a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a non-null, valid `usb_host_interface` belonging to an interface that
+    /// outlives `'a`.
+    unsafe fn from_ptr(ptr: *mut bindings::usb_host_interface) -> Self {
+        Self {
+            ptr,
+            _p: PhantomData,
+        }
+    }
+
+    /// Returns the number of endpoints in this altsetting.
+    #[inline]
+    pub fn num_endpoints(&self) -> usize {
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid.
+        unsafe { (*self.ptr).desc.bNumEndpoints as usize }
+    }
+
+    /// Returns the endpoint at `index`, or `None` if `index >= self.num_endpoints()`.
+    pub fn endpoint(&self, index: usize) -> Option<Endpoint<'a>> {
+        if index >= self.num_endpoints() {
+            return None;
+        }
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid, and
+        // `index < num_endpoints()` keeps the offset within the `endpoint` array the core
+        // allocated for this altsetting.
+        let ptr = unsafe { (*self.ptr).endpoint.add(index) };
+        Some(Endpoint {
+            ptr,
+            _p: PhantomData,
+        })
+    }
+
+    /// Returns an iterator over this altsetting's endpoints.
+    pub fn endpoints(&self) -> impl Iterator<Item = Endpoint<'a>> + '_ {
+        (0..self.num_endpoints()).map(move |i| self.endpoint(i).unwrap())
+    }
+
+    /// Finds the first bulk IN endpoint, if any.
+    pub fn find_bulk_in(&self) -> Option<Endpoint<'a>> {
+        self.endpoints().find(|e| e.is_bulk() && e.direction_in())
+    }
+
+    /// Finds the first bulk OUT endpoint, if any.
+    pub fn find_bulk_out(&self) -> Option<Endpoint<'a>> {
+        self.endpoints().find(|e| e.is_bulk() && !e.direction_in())
+    }
+
+    /// Finds the first interrupt IN endpoint, if any.
+    pub fn find_int_in(&self) -> Option<Endpoint<'a>> {
+        self.endpoints().find(|e| e.is_int() && e.direction_in())
+    }
+
+    /// Finds the first interrupt OUT endpoint, if any.
+    pub fn find_int_out(&self) -> Option<Endpoint<'a>> {
+        self.endpoints().find(|e| e.is_int() && !e.direction_in())
+    }
+}
+
+/// A single endpoint descriptor belonging to an [`AltSetting`].
+///
+/// # Invariants
+///
+/// `ptr` is a non-null `struct usb_host_endpoint`, valid for the lifetime `'a` borrowed from the
+/// altsetting it was obtained from.
+pub struct Endpoint<'a> {
+    ptr: *mut bindings::usb_host_endpoint,
+    _p: PhantomData<&'a Interface>,
+}
+
+impl<'a> Endpoint<'a> {
+    /// Returns `bEndpointAddress`, including its direction bit.
+    #[inline]
+    pub fn address(&self) -> u8 {
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid.
+        unsafe { (*self.ptr).desc.bEndpointAddress }
+    }
+
+    /// Returns the endpoint number, i.e. `bEndpointAddress` without the direction bit, suitable
+    /// for use with [`Device`]'s `*pipe` helpers.
+    #[inline]
+    pub fn number(&self) -> u32 {
+        (self.address() & bindings::USB_ENDPOINT_NUMBER_MASK as u8) as u32
+    }
+
+    /// Returns whether this is an IN endpoint (device-to-host).
+    #[inline]
+    pub fn direction_in(&self) -> bool {
+        self.address() & (bindings::USB_DIR_IN as u8) != 0
+    }
+
+    #[inline]
+    fn xfer_type(&self) -> u8 {
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid.
+        let attrs = unsafe { (*self.ptr).desc.bmAttributes };
+        attrs & bindings::USB_ENDPOINT_XFERTYPE_MASK as u8
+    }
+
+    /// Returns whether this is a bulk endpoint.
+    #[inline]
+    pub fn is_bulk(&self) -> bool {
+        self.xfer_type() == bindings::USB_ENDPOINT_XFER_BULK as u8
+    }
+
+    /// Returns whether this is an interrupt endpoint.
+    #[inline]
+    pub fn is_int(&self) -> bool {
+        self.xfer_type() == bindings::USB_ENDPOINT_XFER_INT as u8
+    }
+
+    /// Returns whether this is an isochronous endpoint.
+    #[inline]
+    pub fn is_isoc(&self) -> bool {
+        self.xfer_type() == bindings::USB_ENDPOINT_XFER_ISOC as u8
+    }
+
+    /// Returns the maximum packet size this endpoint can send or receive in one transaction.
+    #[inline]
+    pub fn max_packet_size(&self) -> u16 {
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid.
+        unsafe { (*self.ptr).desc.wMaxPacketSize }
+    }
+
+    /// Returns the polling interval (`bInterval`) for interrupt and isochronous endpoints.
+    #[inline]
+    pub fn interval(&self) -> u8 {
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid.
+        unsafe { (*self.ptr).desc.bInterval }
+    }
+}
+
+/// A `usbfs` character-device descriptor, registered against an [`Interface`].
+///
+/// Mirrors `struct usb_class_driver`: `name` is a `printf`-style template (e.g. `"usb/foo%d"`)
+/// into which the kernel substitutes the minor allocated for a given interface, and `U` supplies
+/// the userspace read/write/ioctl surface through the same [`file::Operations`] machinery used
+/// elsewhere in the crate (see `A`'s [`file::OpenAdapter`] implementation for how a [`file::File`]
+/// is mapped back to `U::OpenData`, e.g. via [`Interface::from_ptr`] and the minor stashed in the
+/// inode by `usb_register_dev`). This is how in-tree skeleton drivers such as `usb-skeleton.c`
+/// expose a `/dev` node alongside sysfs.
+pub struct ClassDriver<A, U> {
+    raw: bindings::usb_class_driver,
+    _p: PhantomData<(A, U)>,
+}
+
+impl<A, U> ClassDriver<A, U>
+where
+    A: file::OpenAdapter<U::OpenData>,
+    U: file::Operations,
+{
+    /// Creates a class driver descriptor with the given `name` template and `minor_base`.
+    pub fn new(name: &'static CStr, minor_base: i32) -> Self {
+        Self {
+            raw: bindings::usb_class_driver {
+                name: name.as_char_ptr(),
+                devnode: None,
+                // SAFETY: `A` and `U` satisfy the vtable's safety requirements the same way
+                // `Registration::register_file` relies on them.
+                fops: unsafe { file::OperationsVtable::<A, U>::build() },
+                minor_base,
+            },
+            _p: PhantomData,
+        }
+    }
+
+    /// Registers a `/dev` node for `intf`, returning the minor number allocated to it.
+    ///
+    /// Call this from [`Driver::probe`]; the node remains registered, and the minor reserved,
+    /// until [`Self::deregister`] is called.
+    pub fn register(&mut self, intf: &mut Interface) -> Result<i32> {
+        // SAFETY: `intf.raw()` is a valid, non-null interface pointer; `self.raw` is a fully
+        // initialised `usb_class_driver` that outlives the registration.
+        to_result(unsafe { bindings::usb_register_dev(intf.raw(), &mut self.raw) })?;
+        // SAFETY: `intf.raw()` is valid, and `usb_register_dev` having just returned
+        // successfully guarantees its `minor` field was populated.
+        Ok(unsafe { (*intf.raw()).minor })
+    }
+
+    /// Releases the `/dev` node and minor previously allocated by [`Self::register`].
+    ///
+    /// Call this from [`Driver::disconnect`].
+    pub fn deregister(&mut self, intf: &mut Interface) {
+        // SAFETY: `intf.raw()` and `self.raw` are the same pair as a prior, successful call to
+        // `Self::register`.
+        unsafe { bindings::usb_deregister_dev(intf.raw(), &mut self.raw) };
+    }
+}
+
+/// Backing storage for a [`Urb`]'s transfer buffer.
+///
+/// Implemented for a plain heap buffer ([`Vec<u8>`]) as well as [`CoherentBuffer`], so a
+/// high-throughput driver can build the exact same kind of `Urb` on top of DMA-coherent memory
+/// instead of an ordinary bounce-buffered allocation.
+pub trait UrbBuffer {
+    /// Returns a pointer to the start of the buffer, valid for [`Self::len`] bytes.
+    fn as_mut_ptr(&mut self) -> *mut u8;
+
+    /// Returns the length of the buffer, in bytes.
+    fn len(&self) -> usize;
+
+    /// Returns the DMA address of this buffer, if it is already DMA-coherent memory that the
+    /// core should use directly instead of mapping `transfer_buffer` on every submission.
+    ///
+    /// A `fill_*` constructor stamps this into `urb->transfer_dma` and sets
+    /// `URB_NO_TRANSFER_DMA_MAP` in `urb->transfer_flags` when it is `Some`.
+    fn dma_handle(&self) -> Option<bindings::dma_addr_t> {
+        None
+    }
+}
+
+impl UrbBuffer for Vec<u8> {
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.as_mut_slice().as_mut_ptr()
+    }
+
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+}
+
+/// Heap-allocated state backing a [`Urb`], kept behind its own `Box` so its address stays stable
+/// across moves of the `Urb` itself: `urb->context` points directly at this allocation, and the
+/// completion trampoline finds its way back here when the transfer finishes.
+struct UrbInner {
+    /// Transfer buffer backing `urb->transfer_buffer`.
+    buffer: Box<dyn UrbBuffer + Send>,
+    /// Control setup packet backing `urb->setup_packet`, only used by [`Urb::fill_control`].
+    setup_packet: Option<bindings::usb_ctrlrequest>,
+    /// Called exactly once, from [`Urb::complete_trampoline`], with the result derived from
+    /// `urb->status` and `urb->actual_length`.
+    completion: Box<dyn FnMut(Result<usize>) + Send>,
+}
+
+/// A single frame of an isochronous transfer, describing where in the transfer buffer it lives.
+///
+/// Passed to [`Urb::fill_iso`], which copies each entry into the URB's `iso_frame_desc[]` array.
+#[derive(Clone, Copy)]
+pub struct IsoPacket {
+    /// Offset of this frame's data within the transfer buffer.
+    pub offset: u32,
+    /// Length of this frame's data, in bytes.
+    pub length: u32,
+}
+
+/// A USB Request Block (URB), the kernel's unit of asynchronous USB I/O.
+///
+/// A `Urb` is built with one of the `fill_*` constructors, which mirror the C
+/// `usb_fill_{bulk,int,control}_urb` helpers, then handed to the core with [`Urb::submit`]. The
+/// completion closure passed to the constructor runs once, in interrupt or softirq context, once
+/// the transfer completes, is cancelled, or fails; because it can run there, it must be `Send`
+/// and must not sleep.
+///
+/// The transfer buffer and completion closure are pinned inside the `Urb` for as long as it may
+/// be in flight: both `Drop` and a failed [`Urb::submit`] call [`Urb::kill`] first, which
+/// guarantees the completion closure can never fire after the buffer it closes over is freed.
+///
+/// # Invariants
+///
+/// `ptr` is a non-null `struct urb` allocated by `usb_alloc_urb` and owned by this `Urb`; it has
+/// not been passed to `usb_free_urb`.
+pub struct Urb {
+    ptr: *mut bindings::urb,
+    inner: Box<UrbInner>,
+}
+
+impl Urb {
+    fn alloc(
+        num_packets: i32,
+        buffer: impl UrbBuffer + Send + 'static,
+        completion: impl FnMut(Result<usize>) + Send + 'static,
+    ) -> Result<Self> {
+        // SAFETY: `num_packets` may be any non-negative count and `GFP_KERNEL` is always a valid
+        // allocation flag.
+        let ptr = unsafe { bindings::usb_alloc_urb(num_packets, bindings::GFP_KERNEL) };
+        if ptr.is_null() {
+            return Err(ENOMEM);
+        }
+        Ok(Self {
+            ptr,
+            inner: Box::new(UrbInner {
+                buffer: Box::new(buffer),
+                setup_packet: None,
+                completion: Box::new(completion),
+            }),
+        })
+    }
+
+    /// Returns the stable address of this URB's heap-allocated state, suitable for use as
+    /// `urb->context`.
+    fn context(&mut self) -> *mut core::ffi::c_void {
+        &mut *self.inner as *mut UrbInner as *mut core::ffi::c_void
+    }
+
+    /// If `dma` is `Some`, stamps it into `urb->transfer_dma` and sets `URB_NO_TRANSFER_DMA_MAP`
+    /// in `urb->transfer_flags`, telling the core to use the buffer as pre-mapped DMA-coherent
+    /// memory instead of mapping `transfer_buffer` on every submission.
+    fn set_transfer_dma(&mut self, dma: Option<bindings::dma_addr_t>) {
+        if let Some(dma) = dma {
+            // SAFETY: `self.ptr` is a valid, owned URB that has just been filled in by a `fill_*`
+            // constructor and has not yet been submitted.
+            unsafe {
+                (*self.ptr).transfer_dma = dma;
+                (*self.ptr).transfer_flags |= bindings::URB_NO_TRANSFER_DMA_MAP;
+            }
+        }
+    }
+
+    extern "C" fn complete_trampoline(urb: *mut bindings::urb) {
+        // SAFETY: `context` was set to the address of this URB's `UrbInner` by the `fill_*`
+        // constructor that built it, and that allocation outlives the URB until it is dropped,
+        // which cannot race with this call since the core only completes a URB once.
+        let inner = unsafe { &mut *((*urb).context.cast::<UrbInner>()) };
+        // SAFETY: `urb` is the URB the core just finished processing, so both fields are
+        // initialised.
+        let (status, actual_length) = unsafe { ((*urb).status, (*urb).actual_length) };
+        let result = if status == 0 {
+            Ok(actual_length as usize)
+        } else {
+            Err(Error::from_errno(status))
+        };
+        (inner.completion)(result);
+    }
+
+    /// Allocates and fills a URB for a bulk transfer on `pipe`.
+    pub fn fill_bulk(
+        dev: &Device,
+        pipe: u32,
+        buffer: impl UrbBuffer + Send + 'static,
+        completion: impl FnMut(Result<usize>) + Send + 'static,
+    ) -> Result<Self> {
+        let mut urb = Self::alloc(0, buffer, completion)?;
+        let context = urb.context();
+        let data = urb.inner.buffer.as_mut_ptr().cast();
+        let len = urb.inner.buffer.len() as i32;
+        let dma = urb.inner.buffer.dma_handle();
+        // SAFETY: `urb.ptr` was just allocated by `usb_alloc_urb`; `dev.raw()` is valid for the
+        // lifetime of `dev`; `data` points into `urb.inner.buffer`, which `urb` owns and which
+        // outlives the transfer; `context` is the stable address of `urb.inner`.
+        unsafe {
+            bindings::usb_fill_bulk_urb(
+                urb.ptr,
+                dev.raw(),
+                pipe,
+                data,
+                len,
+                Some(Self::complete_trampoline),
+                context,
+            );
+        }
+        urb.set_transfer_dma(dma);
+        Ok(urb)
+    }
+
+    /// Allocates and fills a URB for an interrupt transfer on `pipe`, polled every `interval`
+    /// frames (or microframes, on high-speed and faster links).
+    pub fn fill_int(
+        dev: &Device,
+        pipe: u32,
+        buffer: impl UrbBuffer + Send + 'static,
+        interval: i32,
+        completion: impl FnMut(Result<usize>) + Send + 'static,
+    ) -> Result<Self> {
+        let mut urb = Self::alloc(0, buffer, completion)?;
+        let context = urb.context();
+        let data = urb.inner.buffer.as_mut_ptr().cast();
+        let len = urb.inner.buffer.len() as i32;
+        let dma = urb.inner.buffer.dma_handle();
+        // SAFETY: as in `fill_bulk`.
+        unsafe {
+            bindings::usb_fill_int_urb(
+                urb.ptr,
+                dev.raw(),
+                pipe,
+                data,
+                len,
+                Some(Self::complete_trampoline),
+                context,
+                interval,
+            );
+        }
+        urb.set_transfer_dma(dma);
+        Ok(urb)
+    }
+
+    /// Allocates and fills a URB for a control transfer on `pipe`.
+    pub fn fill_control(
+        dev: &Device,
+        pipe: u32,
+        setup_packet: bindings::usb_ctrlrequest,
+        buffer: impl UrbBuffer + Send + 'static,
+        completion: impl FnMut(Result<usize>) + Send + 'static,
+    ) -> Result<Self> {
+        let mut urb = Self::alloc(0, buffer, completion)?;
+        urb.inner.setup_packet = Some(setup_packet);
+        let context = urb.context();
+        let data = urb.inner.buffer.as_mut_ptr().cast();
+        let len = urb.inner.buffer.len() as i32;
+        let dma = urb.inner.buffer.dma_handle();
+        // SAFETY: `setup` points at `urb.inner.setup_packet`, which `urb` owns and which outlives
+        // the transfer; the rest as in `fill_bulk`.
+        unsafe {
+            let setup = urb.inner.setup_packet.as_mut().unwrap() as *mut _ as *mut u8;
+            bindings::usb_fill_control_urb(
+                urb.ptr,
+                dev.raw(),
+                pipe,
+                setup,
+                data,
+                len,
+                Some(Self::complete_trampoline),
+                context,
+            );
+        }
+        urb.set_transfer_dma(dma);
+        Ok(urb)
+    }
+
+    /// Allocates and fills a URB for an isochronous transfer on `pipe`, made up of one frame per
+    /// entry in `packets`.
+    ///
+    /// Unlike the other transfer types, the core has no single `usb_fill_iso_urb` helper, so the
+    /// frame descriptors and transfer flags are filled in by hand here. `URB_ISO_ASAP` is always
+    /// set, so the core schedules the first frame as soon as possible rather than requiring the
+    /// caller to pick a `start_frame`.
+    pub fn fill_iso(
+        dev: &Device,
+        pipe: u32,
+        buffer: impl UrbBuffer + Send + 'static,
+        packets: &[IsoPacket],
+        interval: i32,
+        completion: impl FnMut(Result<usize>) + Send + 'static,
+    ) -> Result<Self> {
+        let mut urb = Self::alloc(packets.len() as i32, buffer, completion)?;
+        let context = urb.context();
+        let data = urb.inner.buffer.as_mut_ptr().cast();
+        let len = urb.inner.buffer.len() as i32;
+        let dma = urb.inner.buffer.dma_handle();
+        // SAFETY: `urb.ptr` was allocated with `packets.len()` isochronous frame descriptors
+        // above, so indexing `iso_frame_desc[0..packets.len()]` is in bounds; the rest as in
+        // `fill_bulk`.
+        unsafe {
+            let u = &mut *urb.ptr;
+            u.dev = dev.raw();
+            u.pipe = pipe;
+            u.transfer_buffer = data;
+            u.transfer_buffer_length = len;
+            u.complete = Some(Self::complete_trampoline);
+            u.context = context;
+            u.interval = interval;
+            u.number_of_packets = packets.len() as i32;
+            u.transfer_flags |= bindings::URB_ISO_ASAP;
+            for (i, packet) in packets.iter().enumerate() {
+                u.iso_frame_desc[i].offset = packet.offset;
+                u.iso_frame_desc[i].length = packet.length;
+            }
+        }
+        urb.set_transfer_dma(dma);
+        Ok(urb)
+    }
+
+    /// Returns the status of the `i`-th isochronous frame of a URB built with [`Self::fill_iso`],
+    /// or `None` if `i` is out of range.
+    ///
+    /// Only meaningful after the URB's completion handler has run.
+    pub fn iso_status(&self, i: usize) -> Option<Result> {
+        // SAFETY: `self.ptr` is a valid URB owned by `self`.
+        let urb = unsafe { &*self.ptr };
+        if i as i32 >= urb.number_of_packets {
+            return None;
+        }
+        Some(to_result(urb.iso_frame_desc[i].status))
+    }
+
+    /// Returns the number of bytes actually transferred in the `i`-th isochronous frame of a URB
+    /// built with [`Self::fill_iso`], or `None` if `i` is out of range.
+    ///
+    /// Only meaningful after the URB's completion handler has run.
+    pub fn iso_actual_length(&self, i: usize) -> Option<u32> {
+        // SAFETY: `self.ptr` is a valid URB owned by `self`.
+        let urb = unsafe { &*self.ptr };
+        if i as i32 >= urb.number_of_packets {
+            return None;
+        }
+        Some(urb.iso_frame_desc[i].actual_length)
+    }
+
+    /// Returns an iterator over `(status, actual_length)` for every isochronous frame of a URB
+    /// built with [`Self::fill_iso`].
+    ///
+    /// Only meaningful after the URB's completion handler has run.
+    pub fn iso_frames(&self) -> impl Iterator<Item = (Result, u32)> + '_ {
+        // SAFETY: `self.ptr` is a valid URB owned by `self`.
+        let count = unsafe { (*self.ptr).number_of_packets } as usize;
+        (0..count).map(move |i| {
+            (
+                self.iso_status(i).unwrap(),
+                self.iso_actual_length(i).unwrap(),
+            )
+        })
+    }
+
+    /// Submits this URB to the core for asynchronous processing.
+    ///
+    /// The completion closure supplied to the `fill_*` constructor runs exactly once, from
+    /// interrupt or softirq context, once the transfer finishes, fails, or is cancelled via
+    /// [`Self::kill`] or [`Self::unlink`]. If submission itself fails, the URB is guaranteed not
+    /// to be (or become) in flight, so it is immediately safe to drop or reuse.
+    pub fn submit(&mut self) -> Result {
+        // SAFETY: `self.ptr` is a valid, fully filled-in URB owned by `self`.
+        let ret = unsafe { bindings::usb_submit_urb(self.ptr, bindings::GFP_KERNEL) };
+        if ret != 0 {
+            // The urb never became "in flight", but killing it anyway is harmless and keeps this
+            // path identical to `Drop`'s.
+            self.kill();
+            return Err(Error::from_errno(ret));
+        }
+        Ok(())
+    }
+
+    /// Synchronously cancels this URB, blocking until its completion handler has finished
+    /// running.
+    ///
+    /// Must not be called from the completion handler itself, nor from atomic context.
+    pub fn kill(&mut self) {
+        // SAFETY: `self.ptr` is a valid URB owned by `self`.
+        unsafe { bindings::usb_kill_urb(self.ptr) };
+    }
+
+    /// Asynchronously requests cancellation of this URB without waiting for its completion
+    /// handler to finish running.
+    pub fn unlink(&mut self) -> Result {
+        // SAFETY: `self.ptr` is a valid URB owned by `self`.
+        to_result(unsafe { bindings::usb_unlink_urb(self.ptr) })
+    }
+
+    /// Anchors this URB to `anchor`, then submits it to the core for asynchronous processing.
+    ///
+    /// Anchoring lets a whole group of URBs be killed, poisoned, or waited on together through
+    /// `anchor`, which is the usual way a driver tracks every URB in flight for a device across
+    /// suspend or disconnect without keeping its own list. The core unanchors the URB itself as
+    /// part of completing it; see [`Self::unanchor`] to detach it early instead.
+    pub fn submit_anchored(&mut self, anchor: &Anchor) -> Result {
+        // SAFETY: `self.ptr` is a valid, fully filled-in URB owned by `self`; `anchor.raw()` is a
+        // valid, initialised anchor that outlives this call.
+        unsafe { bindings::usb_anchor_urb(self.ptr, anchor.raw()) };
+        // SAFETY: `self.ptr` is a valid, fully filled-in, now-anchored URB owned by `self`.
+        let ret = unsafe { bindings::usb_submit_urb(self.ptr, bindings::GFP_KERNEL) };
+        if ret != 0 {
+            self.unanchor();
+            self.kill();
+            return Err(Error::from_errno(ret));
+        }
+        Ok(())
+    }
+
+    /// Detaches this URB from whichever anchor it is currently anchored to, if any.
+    pub fn unanchor(&mut self) {
+        // SAFETY: `self.ptr` is a valid URB owned by `self`.
+        unsafe { bindings::usb_unanchor_urb(self.ptr) };
+    }
+}
+
+impl Drop for Urb {
+    fn drop(&mut self) {
+        // Guarantees the completion closure can never fire after this point: `kill` blocks until
+        // any in-flight completion has finished running, so the buffer and closure below it are
+        // safe to free even if the caller dropped the `Urb` without calling `kill` themselves.
+        self.kill();
+        // SAFETY: `self.ptr` is a valid URB owned by `self`, and is no longer in flight.
+        unsafe { bindings::usb_free_urb(self.ptr) };
+    }
+}
+
+// SAFETY: `Urb` owns its URB and heap-allocated buffers outright, and its completion closure is
+// required to be `Send`, so it is safe to transfer to another thread.
+unsafe impl Send for Urb {}
+
+/// A group of in-flight [`Urb`]s that can be killed, poisoned, or waited on together.
+///
+/// Built over `struct usb_anchor`, this is the usual way a driver tracks every URB for a device
+/// across suspend or disconnect, instead of bookkeeping its own `Vec<Urb>` and driving each one
+/// individually. [`Urb::submit_anchored`] anchors a URB here as part of submitting it; the core
+/// unanchors it automatically once it completes.
+///
+/// # Invariants
+///
+/// `raw` is a `struct usb_anchor` that has been initialised by `init_usb_anchor` and not yet
+/// dropped.
+pub struct Anchor {
+    raw: Box<bindings::usb_anchor>,
+}
+
+impl Anchor {
+    /// Creates a new, empty anchor.
+    pub fn new() -> Result<Self> {
+        // SAFETY: an all-zero `usb_anchor` (from `core::mem::zeroed()`) is a valid starting state
+        // for `init_usb_anchor` to initialise in place.
+        let mut raw: Box<bindings::usb_anchor> =
+            Box::try_new(unsafe { core::mem::zeroed() }).map_err(|_| ENOMEM)?;
+        // SAFETY: `raw` is a valid, writable `usb_anchor`.
+        unsafe { bindings::init_usb_anchor(&mut *raw) };
+        Ok(Self { raw })
+    }
+
+    fn raw(&self) -> *mut bindings::usb_anchor {
+        &*self.raw as *const bindings::usb_anchor as *mut bindings::usb_anchor
+    }
+
+    /// Synchronously kills every URB currently anchored here, blocking until each completion
+    /// handler has finished running.
+    pub fn kill_anchored(&self) {
+        // SAFETY: `self.raw()` is a valid, initialised anchor.
+        unsafe { bindings::usb_kill_anchored_urbs(self.raw()) };
+    }
+
+    /// Poisons every URB currently anchored here, so that any future submission of one of them
+    /// fails immediately instead of being sent to the core.
+    pub fn poison_anchored(&self) {
+        // SAFETY: `self.raw()` is a valid, initialised anchor.
+        unsafe { bindings::usb_poison_anchored_urbs(self.raw()) };
+    }
+
+    /// Waits up to `timeout_ms` milliseconds for every URB anchored here to complete and
+    /// unanchor itself.
+    ///
+    /// Returns `true` if the anchor became empty, `false` on timeout.
+    pub fn wait_empty_timeout(&self, timeout_ms: u32) -> bool {
+        // SAFETY: `self.raw()` is a valid, initialised anchor.
+        unsafe { bindings::usb_wait_anchor_empty_timeout(self.raw(), timeout_ms) != 0 }
+    }
+}
+
+impl Drop for Anchor {
+    fn drop(&mut self) {
+        // An anchored URB outlives the anchor it is attached to from the core's point of view, so
+        // dropping a non-empty anchor would leave every URB still linked to it pointing at freed
+        // memory, to be dereferenced the next time one of them completes. This must hold in
+        // release builds too, so unconditionally kill whatever is left rather than merely
+        // asserting the anchor is already empty.
+        self.kill_anchored();
+    }
+}
+
+// SAFETY: `Anchor` owns its `usb_anchor` outright, whose internal spinlock serialises all access
+// to its list of anchored URBs, so it is safe to share and transfer across threads.
+unsafe impl Send for Anchor {}
+// SAFETY: see above.
+unsafe impl Sync for Anchor {}
+
+/// A DMA-coherent buffer allocated from a [`Device`].
+///
+/// Backed by `usb_alloc_coherent`/`usb_free_coherent`, this avoids the bounce-buffering that a
+/// plain heap buffer (as used by [`Urb::fill_bulk`] and friends) needs on architectures where
+/// DMA-coherent memory isn't just ordinary RAM. Stamp [`Self::dma_handle`] into
+/// `urb->transfer_dma` and set `URB_NO_TRANSFER_DMA_MAP` in `urb->transfer_flags` to hand it to
+/// the core without an extra mapping step.
+///
+/// # Invariants
+///
+/// `ptr` is a non-null, `len`-byte coherent allocation obtained from `usb_alloc_coherent` against
+/// `dev`, not yet passed to `usb_free_coherent`.
+pub struct CoherentBuffer {
+    dev: *mut bindings::usb_device,
+    ptr: *mut core::ffi::c_void,
+    dma: bindings::dma_addr_t,
+    len: usize,
+}
+
+impl CoherentBuffer {
+    /// Allocates a new `len`-byte DMA-coherent buffer from `dev`.
+    pub fn new(dev: &Device, len: usize) -> Result<Self> {
+        let mut dma: bindings::dma_addr_t = 0;
+        // SAFETY: `dev.raw()` is valid for the lifetime of `dev`; `GFP_KERNEL` is always a valid
+        // allocation flag; `&mut dma` is a valid out-parameter.
+        let ptr =
+            unsafe { bindings::usb_alloc_coherent(dev.raw(), len, bindings::GFP_KERNEL, &mut dma) };
+        if ptr.is_null() {
+            return Err(ENOMEM);
+        }
+        Ok(Self {
+            dev: dev.raw(),
+            ptr,
+            dma,
+            len,
+        })
+    }
+
+    /// Returns the DMA address of this buffer, to stamp into `urb->transfer_dma`.
+    pub fn dma_handle(&self) -> bindings::dma_addr_t {
+        self.dma
+    }
+
+    /// Returns the CPU-accessible view of this buffer.
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: `self.ptr` is valid for `self.len` bytes for the lifetime of `self`.
+        unsafe { core::slice::from_raw_parts(self.ptr.cast(), self.len) }
+    }
+
+    /// Returns a mutable CPU-accessible view of this buffer.
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: `self.ptr` is valid for `self.len` bytes for the lifetime of `self`, and
+        // `self` is borrowed mutably.
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.cast(), self.len) }
+    }
+}
+
+impl Drop for CoherentBuffer {
+    fn drop(&mut self) {
+        // SAFETY: `self.dev`, `self.len`, `self.ptr` and `self.dma` are exactly the values
+        // returned by the prior, successful call to `usb_alloc_coherent`.
+        unsafe { bindings::usb_free_coherent(self.dev, self.len, self.ptr, self.dma) };
+    }
+}
+
+// SAFETY: `CoherentBuffer` owns its DMA allocation outright; the memory it wraps is safe to
+// access from any thread, serialised by the caller the same way a plain buffer would be.
+unsafe impl Send for CoherentBuffer {}
+
+impl UrbBuffer for CoherentBuffer {
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.as_mut_slice().as_mut_ptr()
+    }
+
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    fn dma_handle(&self) -> Option<bindings::dma_addr_t> {
+        Some(self.dma_handle())
+    }
+}
+
+/// A scatter-gather transfer request, built over `struct usb_sg_request`.
+///
+/// Spreads one logical bulk (or interrupt) transfer across several buffers without
+/// bounce-buffering them into one contiguous allocation first, which is the idiomatic path for
+/// large streaming transfers that [`Device::bulk_msg`] and single-buffer [`Urb`]s cannot reach
+/// efficiently.
+///
+/// # Invariants
+///
+/// `raw` has been successfully initialised by `usb_sg_init` and not yet passed to `usb_sg_wait`.
+pub struct ScatterGather {
+    raw: Box<bindings::usb_sg_request>,
+    // Kept alive for the lifetime of the request: `raw` holds raw pointers into this table.
+    _sgl: Vec<bindings::scatterlist>,
+}
+
+impl ScatterGather {
+    /// Builds a scatter-gather request over `buffers`, to be issued on `pipe`.
+    ///
+    /// `period` is the polling interval for interrupt or isochronous transfers, or `0` for bulk.
+    pub fn new(
+        dev: &Device,
+        pipe: u32,
+        period: u32,
+        buffers: &mut [CoherentBuffer],
+    ) -> Result<Self> {
+        let mut sgl: Vec<bindings::scatterlist> = Vec::with_capacity(buffers.len());
+        // SAFETY: `sgl` has spare capacity for exactly `buffers.len()` entries, matching the
+        // `nents` passed to `sg_init_table` below.
+        unsafe {
+            sgl.set_len(buffers.len());
+            bindings::sg_init_table(sgl.as_mut_ptr(), buffers.len() as u32);
+        }
+
+        let mut total_len = 0usize;
+        for (entry, buffer) in sgl.iter_mut().zip(buffers.iter_mut()) {
+            let data = buffer.as_mut_slice();
+            total_len += data.len();
+            // SAFETY: `entry` is a table-initialised scatterlist entry; `data` points into
+            // `buffer`, which outlives `self` since it is borrowed for the whole call.
+            unsafe { bindings::sg_set_buf(entry, data.as_mut_ptr().cast(), data.len() as u32) };
+        }
+
+        // SAFETY: a zeroed `usb_sg_request` is a valid starting point; it is fully populated by
+        // `usb_sg_init` below before any other field is read.
+        let mut raw = Box::try_new(unsafe { core::mem::zeroed::<bindings::usb_sg_request>() })?;
+        // SAFETY: `dev.raw()` is valid for the lifetime of `dev`; `sgl` has `sgl.len()`
+        // initialised entries, each pointing at a buffer that outlives this call; `raw` is a
+        // valid, writable `usb_sg_request`.
+        to_result(unsafe {
+            bindings::usb_sg_init(
+                &mut *raw,
+                dev.raw(),
+                pipe,
+                period,
+                sgl.as_mut_ptr(),
+                sgl.len() as i32,
+                total_len,
+                bindings::GFP_KERNEL,
+            )
+        })?;
+
+        Ok(Self { raw, _sgl: sgl })
+    }
+
+    /// Blocks until the transfer completes (or is cancelled), returning the total number of
+    /// bytes moved.
+    pub fn wait(mut self) -> Result<usize> {
+        // SAFETY: `self.raw` was successfully initialised by `usb_sg_init` in `Self::new`.
+        unsafe { bindings::usb_sg_wait(&mut *self.raw) };
+        if self.raw.status != 0 {
+            return Err(Error::from_errno(self.raw.status));
+        }
+        Ok(self.raw.bytes as usize)
+    }
+
+    /// Requests cancellation of an in-progress transfer without waiting for it to finish.
+    pub fn cancel(&mut self) {
+        // SAFETY: `self.raw` was successfully initialised by `usb_sg_init` in `Self::new`.
+        unsafe { bindings::usb_sg_cancel(&mut *self.raw) };
+    }
+}
@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: GPL-2.0
 
+use core::marker::PhantomData;
+
 use crate::bindings;
 use crate::error::{to_result, Result};
 
@@ -131,6 +133,85 @@ impl EndpointDescriptor {
         (u16::from_le(self.w_max_packet_size) & bindings::USB_EP_MAXP_MULT_MASK as u16)
             >> bindings::USB_EP_MAXP_MULT_SHIFT
     }
+
+    /// Get the isochronous synchronization type (bits 2-3 of `bmAttributes`): `0` for no
+    /// synchronization, `1` for asynchronous, `2` for adaptive, `3` for synchronous.
+    ///
+    /// Returns `None` for any endpoint that isn't isochronous, since the bits are meaningless
+    /// outside that transfer type.
+    #[inline]
+    pub const fn sync_type(&self) -> Option<u8> {
+        if !self.xfer_isoc() {
+            return None;
+        }
+        Some((self.bm_attributes & bindings::USB_ENDPOINT_SYNCTYPE as u8) >> 2)
+    }
+
+    /// Get the isochronous usage type (bits 4-5 of `bmAttributes`): `0` for data, `1` for
+    /// feedback, `2` for implicit feedback data, `3` is reserved.
+    ///
+    /// Returns `None` for any endpoint that isn't isochronous, since the bits are meaningless
+    /// outside that transfer type.
+    #[inline]
+    pub const fn usage_type(&self) -> Option<u8> {
+        if !self.xfer_isoc() {
+            return None;
+        }
+        Some((self.bm_attributes & bindings::USB_ENDPOINT_USAGE_MASK as u8) >> 4)
+    }
+
+    /// Check if this is an isochronous endpoint with asynchronous synchronization.
+    #[inline]
+    pub const fn is_sync_async(&self) -> bool {
+        matches!(self.sync_type(), Some(1))
+    }
+
+    /// Check if this is an isochronous endpoint with adaptive synchronization.
+    #[inline]
+    pub const fn is_sync_adaptive(&self) -> bool {
+        matches!(self.sync_type(), Some(2))
+    }
+
+    /// Check if this is an isochronous endpoint with synchronous synchronization.
+    #[inline]
+    pub const fn is_sync_sync(&self) -> bool {
+        matches!(self.sync_type(), Some(3))
+    }
+
+    /// Check if this is an isochronous feedback endpoint.
+    #[inline]
+    pub const fn is_usage_feedback(&self) -> bool {
+        matches!(self.usage_type(), Some(1))
+    }
+
+    /// Check if this is an isochronous implicit feedback data endpoint.
+    #[inline]
+    pub const fn is_usage_implicit_feedback(&self) -> bool {
+        matches!(self.usage_type(), Some(2))
+    }
+
+    /// Returns `bRefresh`, the feedback rate exponent, if this is an isochronous endpoint.
+    ///
+    /// Meaningless for any other transfer type, since it's only ever set for audio endpoints.
+    #[inline]
+    pub const fn refresh(&self) -> Option<u8> {
+        if !self.xfer_isoc() {
+            return None;
+        }
+        Some(self.b_refresh)
+    }
+
+    /// Returns `bSynchAddress`, the companion synchronization endpoint's address, if this is an
+    /// isochronous endpoint.
+    ///
+    /// Meaningless for any other transfer type, since it's only ever set for audio endpoints.
+    #[inline]
+    pub const fn synch_address(&self) -> Option<u8> {
+        if !self.xfer_isoc() {
+            return None;
+        }
+        Some(self.b_synch_address)
+    }
 }
 
 /// An USB device descriptor.
@@ -406,4 +487,294 @@ impl<'a> HostInterface {
             }
         }
     }
+
+    /// Provides a slice view over this alternate setting's class-specific descriptors, i.e. the
+    /// raw bytes following the interface descriptor (and its endpoints) in the configuration
+    /// descriptor that the USB core didn't otherwise recognize.
+    ///
+    /// Use [`Self::class_descriptors`] to walk this as a sequence of TLV-style descriptors rather
+    /// than a raw byte slice.
+    #[inline]
+    pub fn extra(&'a self) -> &'a [u8] {
+        if self.extra.is_null() || self.extralen <= 0 {
+            &[]
+        } else {
+            // SAFETY: `extra` is non-null and `extralen` is positive, so it points to `extralen`
+            // bytes owned by the underlying `usb_host_interface`, valid for as long as `self` is.
+            unsafe { core::slice::from_raw_parts(self.extra, self.extralen as usize) }
+        }
+    }
+
+    /// Returns an iterator over this alternate setting's class-specific descriptors.
+    #[inline]
+    pub fn class_descriptors(&'a self) -> ClassDescriptors<'a> {
+        ClassDescriptors {
+            remaining: self.extra(),
+        }
+    }
+
+    /// Returns the SuperSpeed Endpoint Companion descriptor for the endpoint at `index` within
+    /// [`Self::endpoints`], if the USB core populated one, i.e. if the device is operating at
+    /// SuperSpeed (USB 3) or better.
+    ///
+    /// `index` is out of bounds returns `None`, same as a missing companion descriptor.
+    #[inline]
+    pub fn ss_companion(&'a self, index: usize) -> Option<SsEndpointCompanionDescriptor> {
+        if self.endpoint.is_null() || index >= self.desc.b_num_endpoints as usize {
+            return None;
+        }
+
+        // SAFETY: `index` was just checked to be in bounds for the `b_num_endpoints`-sized array
+        // that the non-null `self.endpoint` points at.
+        let raw = unsafe { (*self.endpoint.add(index)).ss_ep_comp };
+        let comp = SsEndpointCompanionDescriptor {
+            b_length: raw.bLength,
+            b_descriptor_type: raw.bDescriptorType,
+            b_max_burst: raw.bMaxBurst,
+            bm_attributes: raw.bmAttributes,
+            w_bytes_per_interval: raw.wBytesPerInterval,
+        };
+        comp.is_present().then_some(comp)
+    }
+}
+
+/// A single class-specific descriptor found in [`HostInterface::class_descriptors`].
+pub struct ClassDescriptor<'a> {
+    /// `bDescriptorType` of this descriptor.
+    pub descriptor_type: u8,
+    /// The descriptor's contents, including its `bLength` and `bDescriptorType` header bytes.
+    pub data: &'a [u8],
+}
+
+/// Iterator over the TLV-style (`bLength`, `bDescriptorType`, ...) class-specific descriptors
+/// trailing an interface descriptor, as returned by [`HostInterface::class_descriptors`].
+pub struct ClassDescriptors<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> Iterator for ClassDescriptors<'a> {
+    type Item = ClassDescriptor<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let b_length = *self.remaining.first()? as usize;
+        if b_length < 2 || b_length > self.remaining.len() {
+            self.remaining = &[];
+            return None;
+        }
+
+        let (data, rest) = self.remaining.split_at(b_length);
+        self.remaining = rest;
+        Some(ClassDescriptor {
+            descriptor_type: data[1],
+            data,
+        })
+    }
+}
+
+/// An USB configuration descriptor.
+#[derive(Default, Copy, Clone, PartialEq)]
+#[repr(C, packed)]
+pub struct ConfigurationDescriptor {
+    /// Size of descriptor.
+    pub b_length: u8,
+    /// Descriptor type.
+    pub b_descriptor_type: u8,
+    /// Total length of data returned for this configuration.
+    pub w_total_length: bindings::__le16,
+    /// Number of interfaces supported by this configuration.
+    pub b_num_interfaces: u8,
+    /// Value to use as an argument to the `SetConfiguration` request to select this
+    /// configuration.
+    pub b_configuration_value: u8,
+    /// Index of string descriptor describing this configuration.
+    pub i_configuration: u8,
+    /// Configuration characteristics.
+    pub bm_attributes: u8,
+    /// Maximum power consumption, expressed in 2 mA units.
+    pub b_max_power: u8,
+}
+
+impl ConfigurationDescriptor {
+    /// Check if the configuration supports remote wakeup.
+    #[inline]
+    pub const fn remote_wakeup(&self) -> bool {
+        self.bm_attributes & bindings::USB_CONFIG_ATT_WAKEUP as u8 != 0
+    }
+
+    /// Check if the configuration is self-powered.
+    #[inline]
+    pub const fn self_powered(&self) -> bool {
+        self.bm_attributes & bindings::USB_CONFIG_ATT_SELFPOWER as u8 != 0
+    }
+
+    /// Get the configuration's maximum power consumption, in milliamps.
+    #[inline]
+    pub const fn max_power_ma(&self) -> u32 {
+        self.b_max_power as u32 * 2
+    }
+}
+
+/// An USB device configuration, giving access to its descriptor and to the [`HostInterface`]s it
+/// contains.
+///
+/// This lets a driver walk the config -> interface -> alt-setting -> endpoint descriptor tree
+/// top-down, without needing a claimed [`super::Interface`] for every interface it merely wants
+/// to inspect.
+///
+/// # Invariants
+///
+/// `ptr` is non-null and valid for the lifetime `'a`.
+#[derive(Clone, Copy)]
+pub struct HostConfig<'a> {
+    ptr: *mut bindings::usb_host_config,
+    _p: PhantomData<&'a ()>,
+}
+
+impl<'a> HostConfig<'a> {
+    /// Creates a [`HostConfig`] from a raw `usb_host_config` pointer.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be non-null, and must remain valid for the lifetime `'a`.
+    #[inline]
+    pub(crate) unsafe fn from_ptr(ptr: *mut bindings::usb_host_config) -> Self {
+        Self {
+            ptr,
+            _p: PhantomData,
+        }
+    }
+
+    /// Returns this configuration's descriptor.
+    #[inline]
+    pub fn desc(&self) -> ConfigurationDescriptor {
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid.
+        let raw = unsafe { (*self.ptr).desc };
+        ConfigurationDescriptor {
+            b_length: raw.bLength,
+            b_descriptor_type: raw.bDescriptorType,
+            w_total_length: raw.wTotalLength,
+            b_num_interfaces: raw.bNumInterfaces,
+            b_configuration_value: raw.bConfigurationValue,
+            i_configuration: raw.iConfiguration,
+            bm_attributes: raw.bmAttributes,
+            b_max_power: raw.bMaxPower,
+        }
+    }
+
+    /// Returns an iterator over this configuration's interfaces.
+    #[inline]
+    pub fn interfaces(&self) -> ConfigInterfaces<'a> {
+        let n = self.desc().b_num_interfaces as usize;
+        // SAFETY: By the type invariants, `self.ptr` is non-null and valid, and `intf_cache`
+        // always has room for at least `b_num_interfaces` entries.
+        let cache = unsafe { &(*self.ptr).intf_cache[..n] };
+        ConfigInterfaces { cache, pos: 0 }
+    }
+
+    /// Locates the (first alternate setting of the) interface with the given `bInterfaceNumber`.
+    ///
+    /// To activate a different alternate setting than the one this returns, claim the interface
+    /// and use [`super::Interface::set_alt_setting`]; the descriptor tree reached from here only
+    /// ever exposes each interface's default alternate setting.
+    #[inline]
+    pub fn find_interface(&self, number: u8) -> Option<&'a HostInterface> {
+        self.interfaces()
+            .find(|intf| intf.desc.b_interface_number == number)
+    }
+}
+
+/// Iterator over a configuration's interfaces, as returned by [`HostConfig::interfaces`].
+pub struct ConfigInterfaces<'a> {
+    cache: &'a [*mut bindings::usb_interface_cache],
+    pos: usize,
+}
+
+impl<'a> Iterator for ConfigInterfaces<'a> {
+    type Item = &'a HostInterface;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.cache.len() {
+            let cache = self.cache[self.pos];
+            self.pos += 1;
+            if cache.is_null() {
+                continue;
+            }
+
+            // SAFETY: `cache` is a non-null entry of `intf_cache`, so it points to a valid
+            // `usb_interface_cache` owned by the configuration.
+            let num_altsetting = unsafe { (*cache).num_altsetting };
+            if num_altsetting == 0 {
+                continue;
+            }
+
+            // SAFETY: `cache` is valid as above, and `num_altsetting` was just checked to be
+            // non-zero, so `altsetting[0]` (the interface's default alternate setting) is a
+            // valid `usb_host_interface`; `HostInterface` mirrors its layout exactly.
+            return Some(unsafe { &*(*cache).altsetting.as_ptr().cast::<HostInterface>() });
+        }
+        None
+    }
+}
+
+/// An USB 3.0 SuperSpeed Endpoint Companion descriptor.
+///
+/// Follows the standard endpoint descriptor for endpoints belonging to a SuperSpeed (or faster)
+/// interface, carrying the burst and streaming information a USB 2.0 endpoint descriptor has no
+/// room for. See [`HostInterface::ss_companion`].
+#[derive(Default, Copy, Clone, PartialEq)]
+#[repr(C, packed)]
+pub struct SsEndpointCompanionDescriptor {
+    /// Size of descriptor.
+    pub b_length: u8,
+    /// Descriptor type.
+    pub b_descriptor_type: u8,
+    /// Raw `bMaxBurst` value; see [`Self::max_burst`].
+    pub b_max_burst: u8,
+    /// Raw `bmAttributes` value; see [`Self::max_streams`]/[`Self::mult`].
+    pub bm_attributes: u8,
+    /// Total number of bytes moved every service interval, for periodic endpoints.
+    pub w_bytes_per_interval: bindings::__le16,
+}
+
+impl SsEndpointCompanionDescriptor {
+    /// Check if the USB core actually populated this descriptor, i.e. whether the endpoint it
+    /// belongs to has a SuperSpeed companion at all.
+    #[inline]
+    pub const fn is_present(&self) -> bool {
+        self.b_length != 0
+    }
+
+    /// Maximum number of packets the endpoint can move within a single burst (`1..=16`).
+    #[inline]
+    pub const fn max_burst(&self) -> u16 {
+        self.b_max_burst as u16 + 1
+    }
+
+    /// Maximum number of bulk streams this endpoint supports, or `1` if it doesn't use streams.
+    ///
+    /// Only meaningful for bulk endpoints; decoded from bits 0-4 of `bmAttributes`.
+    #[inline]
+    pub const fn max_streams(&self) -> u16 {
+        let exponent = self.bm_attributes & 0x1f;
+        if exponent == 0 {
+            1
+        } else {
+            1u16 << exponent
+        }
+    }
+
+    /// `Mult`, the number of packets within a burst for an isochronous endpoint (`1..=3`).
+    ///
+    /// Only meaningful for isochronous endpoints; decoded from bits 0-1 of `bmAttributes`.
+    #[inline]
+    pub const fn mult(&self) -> u8 {
+        (self.bm_attributes & 0x3) + 1
+    }
+
+    /// Total number of bytes this endpoint moves per service interval, for isochronous and
+    /// interrupt endpoints.
+    #[inline]
+    pub const fn bytes_per_interval(&self) -> u16 {
+        u16::from_le(self.w_bytes_per_interval)
+    }
 }
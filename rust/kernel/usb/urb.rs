@@ -569,6 +569,50 @@ impl<T: Transfer, C: ForeignOwnable + Send + Sync> Urb<T, C> {
         };
     }
 
+    /// Fills an isochronous request with the URB.
+    ///
+    /// Unlike [`fill_bulk`](Self::fill_bulk)/[`fill_int`](Self::fill_int), there is no
+    /// `usb_fill_iso_urb()` helper in the USB core: the fields are set directly, and every one of
+    /// the `num_packets` frames is given the same `packet_size`, matching `usb_fill_int_urb`'s
+    /// `URB_ISO_ASAP` convention of letting the host controller pick the first free frame.
+    #[inline]
+    pub fn fill_iso<R: Completion<T, C>>(
+        &mut self,
+        dev: &Device,
+        pipe: u32,
+        transfer: Option<T>,
+        ctx: Option<C>,
+        packet_size: u32,
+        num_packets: i32,
+        interval: i32,
+    ) {
+        let len = transfer
+            .as_ref()
+            .map_or(0, T::transfer_len)
+            .try_into()
+            .unwrap_or(i32::MAX);
+        // SAFETY: `self.ptr` is valid by the type invariants, and `self` owns the URB
+        // exclusively while it is being filled in.
+        unsafe {
+            (*self.ptr).dev = dev.ptr;
+            (*self.ptr).pipe = pipe;
+            (*self.ptr).transfer_buffer = transfer.map_or(core::ptr::null_mut(), T::into_data);
+            (*self.ptr).transfer_buffer_length = len;
+            (*self.ptr).complete = Some(complete_callback::<R, C, T>);
+            (*self.ptr).context = ctx.map_or(core::ptr::null_mut(), |c| c.into_foreign() as *mut _);
+            (*self.ptr).interval = interval;
+            (*self.ptr).number_of_packets = num_packets;
+            // SAFETY: `num_packets` matches the `pkts` the URB was allocated with in
+            // `try_new_flagged`.
+            let frames = (*self.ptr).iso_frame_desc.as_mut_slice(num_packets as usize);
+            for (i, frame) in frames.iter_mut().enumerate() {
+                frame.offset = packet_size * i as u32;
+                frame.length = packet_size;
+            }
+        };
+        self.set_transfer_flags(self.transfer_flags() | transfer_flags::URB_ISO_ASAP);
+    }
+
     /// Returns a reference to the context of the URB.
     pub fn context<'a>(&self) -> Option<C::Borrowed<'a>> {
         // SAFETY: By the type invariants, we know that `self.ptr` is non-null and valid.
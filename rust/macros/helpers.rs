@@ -96,3 +96,25 @@ pub(crate) fn get_byte_string(it: &mut token_stream::IntoIter, expected_name: &s
     assert_eq!(expect_punct(it), ',');
     byte_string
 }
+
+pub(crate) fn get_number<T: TryFromRadix>(
+    it: &mut token_stream::IntoIter,
+    expected_name: &str,
+) -> T::Primitive {
+    assert_eq!(expect_ident(it), expected_name);
+    assert_eq!(expect_punct(it), ':');
+    let literal = expect_literal(it);
+    assert_eq!(expect_punct(it), ',');
+    T::try_from_radix(&literal).expect("Invalid integer literal")
+}
+
+pub(crate) fn try_get_number<T: TryFromRadix>(
+    it: &mut token_stream::IntoIter,
+    expected_name: &str,
+) -> Option<T::Primitive> {
+    let mut peek = it.clone();
+    if try_ident(&mut peek).as_deref() != Some(expected_name) {
+        return None;
+    }
+    Some(get_number::<T>(it, expected_name))
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! Crate for all kernel procedural macros.
+
+use proc_macro::TokenStream;
+
+mod helpers;
+mod zeroable;
+
+/// Derives the [`Zeroable`](../kernel/init/trait.Zeroable.html) trait for the given struct.
+///
+/// This can only be used for structs where every field implements the `Zeroable` trait.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[derive(Zeroable)]
+/// pub struct DriverData {
+///     id: i64,
+///     buf_ptr: *mut u8,
+///     len: usize,
+/// }
+/// ```
+#[proc_macro_derive(Zeroable)]
+pub fn derive_zeroable(ts: TokenStream) -> TokenStream {
+    zeroable::derive_zeroable(ts)
+}
@@ -104,6 +104,11 @@ fn get_rtnl_links_values(name: &str, netdevice: &str) -> RtnlLinkValues {
         "let mut dev = kernel::net::device::NetDevice::<{}>::from_pointer(dev);",
         netdevice
     );
+    let const_setup_dev = format!(
+        "let dev = kernel::net::device::NetDevice::<{}>::from_pointer(dev as *mut kernel::bindings::net_device);",
+        netdevice
+    );
+
     match name {
         "setup" => RtnlLinkValues::new("dev: *mut kernel::bindings::net_device", &setup_dev, "&mut dev"),
         "validate" => RtnlLinkValues {
@@ -117,6 +122,75 @@ fn get_rtnl_links_values(name: &str, netdevice: &str) -> RtnlLinkValues {
             wrapper_after: "?; Ok(0) }".to_owned(),
             params: "&tb, &data, &extack".to_owned(),
         },
+        "newlink" => RtnlLinkValues {
+            callback_params: "src_net: *mut kernel::bindings::net, dev: *mut kernel::bindings::net_device, tb: *mut *mut kernel::bindings::nlattr, data: *mut *mut kernel::bindings::nlattr, extack: *mut kernel::bindings::netlink_ext_ack".to_owned(),
+            return_type: "kernel::c_types::c_int".to_owned(),
+            wrapper_before: format!(
+                r#"kernel::from_kernel_result! {{
+                 let mut dev = kernel::net::device::NetDevice::<{netdevice}>::from_pointer(dev);
+                 let tb = kernel::net::netlink::NlAttrVec::from_pointer(tb as *const *const kernel::bindings::nlattr);
+                 let data = kernel::net::netlink::NlAttrVec::from_pointer(data as *const *const kernel::bindings::nlattr);
+                 let extack = kernel::net::netlink::NlExtAck::from_pointer(extack);
+                 "#,
+                netdevice = netdevice
+            ),
+            wrapper_after: "?; Ok(0) }".to_owned(),
+            params: "src_net, &mut dev, &tb, &data, &extack".to_owned(),
+        },
+        "changelink" => RtnlLinkValues {
+            callback_params: "dev: *mut kernel::bindings::net_device, tb: *mut *mut kernel::bindings::nlattr, data: *mut *mut kernel::bindings::nlattr, extack: *mut kernel::bindings::netlink_ext_ack".to_owned(),
+            return_type: "kernel::c_types::c_int".to_owned(),
+            wrapper_before: format!(
+                r#"kernel::from_kernel_result! {{
+                 let mut dev = kernel::net::device::NetDevice::<{netdevice}>::from_pointer(dev);
+                 let tb = kernel::net::netlink::NlAttrVec::from_pointer(tb as *const *const kernel::bindings::nlattr);
+                 let data = kernel::net::netlink::NlAttrVec::from_pointer(data as *const *const kernel::bindings::nlattr);
+                 let extack = kernel::net::netlink::NlExtAck::from_pointer(extack);
+                 "#,
+                netdevice = netdevice
+            ),
+            wrapper_after: "?; Ok(0) }".to_owned(),
+            params: "&mut dev, &tb, &data, &extack".to_owned(),
+        },
+        "dellink" => RtnlLinkValues::new(
+            "dev: *mut kernel::bindings::net_device, head: *mut kernel::bindings::list_head",
+            &setup_dev,
+            "&mut dev, head",
+        ),
+        "get_size" => RtnlLinkValues {
+            callback_params: "dev: *const kernel::bindings::net_device".to_owned(),
+            return_type: "kernel::c_types::c_int".to_owned(),
+            wrapper_before: const_setup_dev.clone(),
+            wrapper_after: " as kernel::c_types::c_int".to_owned(),
+            params: "&dev".to_owned(),
+        },
+        "fill_info" => RtnlLinkValues {
+            callback_params: "skb: *mut kernel::bindings::sk_buff, dev: *const kernel::bindings::net_device".to_owned(),
+            return_type: "kernel::c_types::c_int".to_owned(),
+            wrapper_before: format!(
+                r#"kernel::from_kernel_result! {{
+                 let mut skb = kernel::net::skbuff::SkBuff::from_pointer(skb);
+                 {const_setup_dev}
+                 "#,
+                const_setup_dev = const_setup_dev
+            ),
+            wrapper_after: "?; Ok(0) }".to_owned(),
+            params: "&mut skb, &dev".to_owned(),
+        },
+        "get_num_tx_queues" => RtnlLinkValues {
+            callback_params: "".to_owned(),
+            return_type: "kernel::c_types::c_uint".to_owned(),
+            wrapper_before: "".to_owned(),
+            wrapper_after: "".to_owned(),
+            params: "".to_owned(),
+        },
+        "get_link_net" => RtnlLinkValues {
+            callback_params: "dev: *const kernel::bindings::net_device".to_owned(),
+            return_type: "*mut kernel::bindings::net".to_owned(),
+            wrapper_before: const_setup_dev,
+            wrapper_after: "".to_owned(),
+            params: "&dev".to_owned(),
+        },
         _ => panic!("invalid rtnl_link_ops function '{}'", name),
     }
 }
@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: GPL-2.0
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+use super::helpers::*;
+
+pub(crate) fn derive_zeroable(ts: TokenStream) -> TokenStream {
+    let mut it = ts.into_iter();
+
+    // Skip outer attributes and visibility on the struct itself.
+    loop {
+        match it.clone().next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '#' => {
+                it.next();
+                expect_group(&mut it);
+            }
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                it.next();
+                if let Some(TokenTree::Group(_)) = it.clone().next() {
+                    it.next();
+                }
+            }
+            _ => break,
+        }
+    }
+
+    assert_eq!(expect_ident(&mut it), "struct");
+    let name = expect_ident(&mut it);
+
+    if matches!(it.clone().next(), Some(TokenTree::Punct(p)) if p.as_char() == '<') {
+        panic!("derive(Zeroable) does not support generic structs");
+    }
+
+    let body = expect_group(&mut it);
+    assert_eq!(body.delimiter(), Delimiter::Brace);
+
+    let mut fields = Vec::new();
+    let mut field_it = body.stream().into_iter();
+    loop {
+        if field_it.clone().next().is_none() {
+            break;
+        }
+        // Skip field attributes and visibility.
+        loop {
+            match field_it.clone().next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '#' => {
+                    field_it.next();
+                    expect_group(&mut field_it);
+                }
+                Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                    field_it.next();
+                    if let Some(TokenTree::Group(_)) = field_it.clone().next() {
+                        field_it.next();
+                    }
+                }
+                _ => break,
+            }
+        }
+        expect_ident(&mut field_it);
+        assert_eq!(expect_punct(&mut field_it), ':');
+
+        let mut ty_toks = Vec::new();
+        loop {
+            match field_it.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == ',' => break,
+                Some(tt) => ty_toks.push(tt),
+                None => break,
+            }
+        }
+        fields.push(TokenStream::from_iter(ty_toks).to_string());
+    }
+
+    let asserts = fields
+        .iter()
+        .map(|ty| format!("assert_zeroable::<{}>();", ty))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"
+        // SAFETY: every field of `{name}` is required to be `Zeroable` by
+        // the assertions below, and an all-zero instance of a struct with
+        // only `Zeroable` fields is itself a valid bit pattern.
+        unsafe impl kernel::init::Zeroable for {name} {{}}
+
+        const _: () = {{
+            fn __assert_fields_zeroable() {{
+                fn assert_zeroable<T: ?Sized + kernel::init::Zeroable>() {{}}
+                {asserts}
+            }}
+        }};
+        "#,
+        name = name,
+        asserts = asserts,
+    )
+    .parse()
+    .expect("Error parsing formatted string into token stream.")
+}
@@ -38,35 +38,11 @@ enum TestSummary {
 use TestSummary::Fail;
 use TestSummary::Pass;
 
-macro_rules! do_tests {
-    ($($name:ident),*) => {
-        let mut total = 0;
-        let mut pass = 0;
-        let mut fail = 0;
-
-        $({
-            total += 1;
-
-            match $name() {
-                Ok(Pass) => {
-                    pass += 1;
-                    pr_info!("{} passed!", stringify!($name));
-                },
-                Ok(Fail) => {
-                    fail += 1;
-                    pr_info!("{} failed!", stringify!($name));
-                },
-                Err(err) => {
-                    pr_info!("{} hit error {:?}", stringify!($name), err);
-                }
-            }
-        })*
-
-        pr_info!("{} tests run, {} passed, {} failed, {} hit errors\n",
-                 total, pass, fail, total - pass - fail);
-
-        if total == pass {
-            pr_info!("All tests passed. Congratulations!\n");
+impl kernel::kunit::TestOutcome for TestSummary {
+    fn into_outcome(self) -> kernel::kunit::Outcome {
+        match self {
+            Pass => kernel::kunit::Outcome::Pass,
+            Fail => kernel::kunit::Outcome::Fail,
         }
     }
 }
@@ -112,13 +88,12 @@ fn test_rust_smp_cpu() -> Result<TestSummary> {
     Ok(Pass)
 }
 
+kernel::kunit_tests!("rust_selftests", [test_rust_smp_cpu]);
+
 impl kernel::Module for RustSelftests {
     fn init(_name: &'static CStr, _module: &'static ThisModule) -> Result<Self> {
         pr_info!("Rust self tests (init)\n");
-
-        do_tests! {
-            test_rust_smp_cpu
-        };
+        pr_info!("Tests are now reported through KUnit; run them with kunit.py or similar\n");
 
         Ok(RustSelftests)
     }
@@ -3,14 +3,15 @@
 //! Rust USB sample.
 
 use kernel::{
-    define_usb_id_table, device,
+    bindings, define_usb_id_table, device,
+    dma::CoherentBuffer,
     error::code::*,
     file, init_static_sync,
-    io_buffer::IoBufferWriter,
+    io_buffer::{IoBufferReader, IoBufferWriter},
     miscdev, module_usb_driver, mutex_init, new_device_data,
     prelude::*,
     sync::{Arc, ArcBorrow, Mutex, NoWaitLock, UniqueArc},
-    usb,
+    to_result, usb,
     xarray::XArray,
     GFP_ATOMIC, GFP_KERNEL,
 };
@@ -24,10 +25,20 @@ init_static_sync! {
 struct UsbSimpleFile;
 
 struct UsbSimpleSyncData {
-    buf: Vec<u8>,
+    buf: CoherentBuffer,
     minor: usize,
+    /// The OUT URB backing this buffer, resubmitted by [`UsbSimpleFile::write`] once the
+    /// buffer has been refilled. `None` for read (bulk-IN) buffers.
+    write_urb: Option<*mut bindings::urb>,
 }
 
+// SAFETY: `write_urb`, when present, remains valid for as long as the device is bound, and
+// `usb_submit_urb` may be called on it from any thread.
+unsafe impl Send for UsbSimpleSyncData {}
+
+// SAFETY: `write_urb` is only ever passed to `usb_submit_urb`, which synchronizes internally.
+unsafe impl Sync for UsbSimpleSyncData {}
+
 #[vtable]
 impl file::Operations for UsbSimpleFile {
     type Data = Arc<Mutex<UsbSimpleSyncData>>;
@@ -49,9 +60,31 @@ impl file::Operations for UsbSimpleFile {
         }
 
         let sbuf = shared.lock();
-        writer.write_slice(&sbuf.buf)?;
+        writer.write_slice(sbuf.buf.as_slice())?;
         Ok(sbuf.buf.len())
     }
+
+    fn write(
+        shared: ArcBorrow<'_, Mutex<UsbSimpleSyncData>>,
+        _: &file::File,
+        reader: &mut impl IoBufferReader,
+        offset: u64,
+    ) -> Result<usize> {
+        if offset != 0 {
+            return Ok(0);
+        }
+
+        let mut sbuf = shared.lock();
+        let Some(urb) = sbuf.write_urb else {
+            return Err(EINVAL);
+        };
+        let len = core::cmp::min(reader.len(), sbuf.buf.len());
+        reader.read_slice(&mut sbuf.buf.as_slice_mut()[..len])?;
+        // SAFETY: `urb` was filled with `sbuf.buf` as its transfer buffer in `probe`, and
+        // `transfer_buffer_length` still matches the buffer we just wrote into.
+        to_result(unsafe { bindings::usb_submit_urb(urb, GFP_KERNEL) })?;
+        Ok(len)
+    }
 }
 
 struct UsbSimpleContext {
@@ -70,19 +103,27 @@ impl UsbSimpleContext {
 
 struct UsbSimpleCompletion;
 
-impl usb::Completion<Vec<u8>, Arc<UsbSimpleContext>> for UsbSimpleCompletion {
+impl usb::Completion<&'static mut [u8], Arc<UsbSimpleContext>> for UsbSimpleCompletion {
     fn complete(mut urb: UrbSimple) {
-        let ctx = urb.context().unwrap();
-        if let Some(mut sbuf) = ctx.sbuf.try_lock() {
-            sbuf.buf.copy_from_slice(urb.borrow_transfer().unwrap());
-        }
+        // The transfer buffer is the same DMA-coherent memory backing
+        // `ctx.sbuf.buf`, so the freshly received data is already where
+        // `UsbSimpleFile::read` expects it -- no bounce copy needed.
         urb.submit(GFP_ATOMIC).unwrap_or_default();
     }
 }
 
+struct UsbSimpleWriteCompletion;
+
+impl usb::Completion<&'static mut [u8], Arc<UsbSimpleContext>> for UsbSimpleWriteCompletion {
+    fn complete(_urb: UrbSimple) {
+        // Nothing to resubmit here: the next `UsbSimpleFile::write` reuses the
+        // same buffer and URB once there is new data to send.
+    }
+}
+
 type UsbSimpleRegistration = miscdev::Registration<UsbSimpleFile>;
 
-type UrbSimple = usb::Urb<Vec<u8>, Arc<UsbSimpleContext>>;
+type UrbSimple = usb::Urb<&'static mut [u8], Arc<UsbSimpleContext>>;
 
 struct UsbSimpleResources;
 
@@ -153,15 +194,29 @@ impl usb::Driver for UsbSimpleDevice {
             .endpoints()
             .iter()
             .filter(|e| e.is_bulk_in());
-        let epd_count = in_edps.by_ref().count();
+        let in_count = in_edps.clone().count();
+        let out_edps = intf
+            .cur_altsetting()
+            .endpoints()
+            .iter()
+            .filter(|e| e.is_bulk_out());
+        let out_count = out_edps.clone().count();
+        let epd_count = in_count + out_count;
         let mut urbs = Vec::try_with_capacity(epd_count)?;
         let mut regs = Vec::try_with_capacity(epd_count)?;
         for epd in in_edps {
             let mut urb = UrbSimple::try_new(0)?;
-            let read_bulk = Vec::try_with_capacity(epd.maxp() as usize)?;
+            let buf = CoherentBuffer::try_new(&dev.to_device(), epd.maxp() as usize, GFP_KERNEL)?;
+            // SAFETY: `buf`'s underlying DMA allocation does not move when `buf`
+            // is moved into `read_ctx` below, and `read_ctx` is kept alive for
+            // as long as this URB is (it is stashed as the URB's context), so
+            // the slice stays valid for as long as the URB may dereference it.
+            let read_bulk: &'static mut [u8] =
+                unsafe { core::slice::from_raw_parts_mut(buf.as_mut_ptr(), buf.len()) };
             let read_ctx = UsbSimpleContext::try_new(UsbSimpleSyncData {
-                buf: Vec::try_with_capacity(epd.maxp() as usize)?,
+                buf,
                 minor: 0,
+                write_urb: None,
             })?;
             urb.fill_bulk::<UsbSimpleCompletion>(
                 &dev,
@@ -176,6 +231,30 @@ impl usb::Driver for UsbSimpleDevice {
             )?)?;
             urbs.try_push(urb)?;
         }
+        for epd in out_edps {
+            let mut urb = UrbSimple::try_new(0)?;
+            let buf = CoherentBuffer::try_new(&dev.to_device(), epd.maxp() as usize, GFP_KERNEL)?;
+            // SAFETY: Same reasoning as for the bulk-IN buffers above.
+            let write_bulk: &'static mut [u8] =
+                unsafe { core::slice::from_raw_parts_mut(buf.as_mut_ptr(), buf.len()) };
+            let write_urb = urb.raw();
+            let write_ctx = UsbSimpleContext::try_new(UsbSimpleSyncData {
+                buf,
+                minor: 0,
+                write_urb: Some(write_urb),
+            })?;
+            urb.fill_bulk::<UsbSimpleWriteCompletion>(
+                &dev,
+                dev.sndbulkpipe(epd.b_endpoint_address as u32),
+                Some(write_bulk),
+                Some(write_ctx),
+            );
+            regs.try_push(miscdev::Registration::<UsbSimpleFile>::new_pinned(
+                fmt!("usbsimple"),
+                (),
+            )?)?;
+            urbs.try_push(urb)?;
+        }
         alloc_minors(&mut urbs, *num_ports.read() as usize)?;
         let data = new_device_data!(
             regs,